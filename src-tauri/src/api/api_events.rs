@@ -0,0 +1,64 @@
+use tauri::{AppHandle, Listener};
+use tokio::sync::broadcast;
+
+/// Events forwarded to the server-sent-events stream of the HTTP API.
+const FORWARDED_EVENTS: &[&str] = &[
+  "download_group",
+  "media_add",
+  "media_add_and_download",
+  "media_cancelled",
+  "media_complete",
+  "media_countdown",
+  "media_destination",
+  "media_diagnostic",
+  "media_fatal",
+  "media_group_add",
+  "media_live_progress",
+  "media_paused",
+  "media_playlist_item",
+  "media_progress",
+  "media_progress_stage",
+  "media_resumed",
+  "media_retry",
+  "media_size",
+  "media_stopping",
+];
+
+const EVENT_BUFFER: usize = 256;
+
+#[derive(Clone, Debug)]
+pub struct ApiEvent {
+  pub name: &'static str,
+  /// The event payload, already serialized to JSON.
+  pub payload: String,
+}
+
+impl ApiEvent {
+  pub fn to_sse(&self) -> String {
+    format!("event: {}\ndata: {}\n\n", self.name, self.payload)
+  }
+}
+
+pub struct ApiEvents(broadcast::Sender<ApiEvent>);
+
+impl ApiEvents {
+  /// Listens to the forwarded events for as long as the app runs. Without subscribers the
+  /// events are dropped right away.
+  pub fn listen(app: &AppHandle) -> Self {
+    let (tx, _) = broadcast::channel(EVENT_BUFFER);
+    for &name in FORWARDED_EVENTS {
+      let tx = tx.clone();
+      app.listen_any(name, move |event| {
+        let _ = tx.send(ApiEvent {
+          name,
+          payload: event.payload().to_string(),
+        });
+      });
+    }
+    Self(tx)
+  }
+
+  pub fn subscribe(&self) -> broadcast::Receiver<ApiEvent> {
+    self.0.subscribe()
+  }
+}
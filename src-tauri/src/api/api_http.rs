@@ -0,0 +1,313 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug)]
+pub struct HttpRequest {
+  pub method: String,
+  pub path: String,
+  pub query: HashMap<String, String>,
+  /// Header names are lowercased.
+  pub headers: HashMap<String, String>,
+  pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+  /// The token from the `Authorization: Bearer` header. Tokens in the URL would end up in logs
+  /// and browser history, so they are not accepted.
+  pub fn token(&self) -> Option<&str> {
+    self
+      .headers
+      .get("authorization")
+      .and_then(|value| value.strip_prefix("Bearer "))
+      .map(str::trim)
+  }
+
+  /// The event stream ticket from the `ticket` query parameter, for clients like `EventSource`
+  /// that cannot set headers.
+  pub fn ticket(&self) -> Option<&str> {
+    self.query.get("ticket").map(String::as_str)
+  }
+
+  /// The `Origin` of a browser request if it is allowed, `None` for requests without one.
+  pub fn allowed_origin(&self, allowed: &[String]) -> Result<Option<&str>, HttpResponse> {
+    match self.headers.get("origin") {
+      None => Ok(None),
+      Some(origin) if allowed.iter().any(|allowed| allowed == origin) => Ok(Some(origin)),
+      Some(_) => Err(HttpResponse::error(403, "Origin not allowed")),
+    }
+  }
+}
+
+pub struct HttpResponse {
+  pub status: u16,
+  pub content_type: &'static str,
+  pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+  pub fn json<T: Serialize>(status: u16, value: &T) -> Self {
+    match serde_json::to_vec(value) {
+      Ok(body) => Self {
+        status,
+        content_type: "application/json",
+        body,
+      },
+      Err(e) => Self::error(500, &e.to_string()),
+    }
+  }
+
+  pub fn error(status: u16, message: &str) -> Self {
+    Self::json(status, &serde_json::json!({ "error": message }))
+  }
+
+  pub fn no_content() -> Self {
+    Self {
+      status: 204,
+      content_type: "text/plain",
+      body: Vec::new(),
+    }
+  }
+
+  /// Serializes the response, with CORS headers for an allowed `origin`.
+  pub fn to_bytes(&self, origin: Option<&str>) -> Vec<u8> {
+    let mut out = format!(
+      "HTTP/1.1 {} {}\r\n\
+       Content-Type: {}\r\n\
+       Content-Length: {}\r\n\
+       {}\
+       Connection: close\r\n\r\n",
+      self.status,
+      reason(self.status),
+      self.content_type,
+      self.body.len(),
+      cors_headers(origin),
+    )
+    .into_bytes();
+    out.extend_from_slice(&self.body);
+    out
+  }
+}
+
+pub fn cors_headers(origin: Option<&str>) -> String {
+  let Some(origin) = origin else {
+    return String::new();
+  };
+  format!(
+    "Access-Control-Allow-Origin: {origin}\r\n\
+     Vary: Origin\r\n\
+     Access-Control-Allow-Headers: authorization, content-type\r\n\
+     Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n"
+  )
+}
+
+fn reason(status: u16) -> &'static str {
+  match status {
+    200 => "OK",
+    204 => "No Content",
+    400 => "Bad Request",
+    401 => "Unauthorized",
+    403 => "Forbidden",
+    404 => "Not Found",
+    413 => "Payload Too Large",
+    503 => "Service Unavailable",
+    _ => "Internal Server Error",
+  }
+}
+
+/// Reads a single request. Errors come with the response to answer with.
+pub async fn read_request<R: AsyncRead + Unpin>(
+  stream: &mut R,
+) -> Result<HttpRequest, HttpResponse> {
+  let mut buf = Vec::with_capacity(1024);
+  let mut chunk = [0u8; 4096];
+  let head_end = loop {
+    if let Some(pos) = find_head_end(&buf) {
+      break pos;
+    }
+    if buf.len() > MAX_HEAD_BYTES {
+      return Err(HttpResponse::error(413, "Request head too large"));
+    }
+    let read = stream
+      .read(&mut chunk)
+      .await
+      .map_err(|e| HttpResponse::error(400, &e.to_string()))?;
+    if read == 0 {
+      return Err(HttpResponse::error(400, "Connection closed mid-request"));
+    }
+    buf.extend_from_slice(&chunk[..read]);
+  };
+
+  let head = std::str::from_utf8(&buf[..head_end])
+    .map_err(|_| HttpResponse::error(400, "Request head is not UTF-8"))?;
+  let mut request = parse_head(head).map_err(|e| HttpResponse::error(400, e))?;
+
+  let length: usize = match request.headers.get("content-length") {
+    Some(value) => value
+      .parse()
+      .map_err(|_| HttpResponse::error(400, "Invalid Content-Length"))?,
+    None => 0,
+  };
+  if length > MAX_BODY_BYTES {
+    return Err(HttpResponse::error(413, "Request body too large"));
+  }
+
+  let mut body = buf.split_off(head_end + 4);
+  while body.len() < length {
+    let read = stream
+      .read(&mut chunk)
+      .await
+      .map_err(|e| HttpResponse::error(400, &e.to_string()))?;
+    if read == 0 {
+      return Err(HttpResponse::error(400, "Connection closed mid-request"));
+    }
+    body.extend_from_slice(&chunk[..read]);
+  }
+  body.truncate(length);
+  request.body = body;
+  Ok(request)
+}
+
+fn find_head_end(buf: &[u8]) -> Option<usize> {
+  buf.windows(4).position(|window| window == b"\r\n\r\n")
+}
+
+fn parse_head(head: &str) -> Result<HttpRequest, &'static str> {
+  let mut lines = head.split("\r\n");
+  let mut request_line = lines.next().unwrap_or_default().split(' ');
+  let (Some(method), Some(target), Some(version)) = (
+    request_line.next(),
+    request_line.next(),
+    request_line.next(),
+  ) else {
+    return Err("Malformed request line");
+  };
+  if !version.starts_with("HTTP/1.") {
+    return Err("Unsupported HTTP version");
+  }
+
+  let mut headers = HashMap::new();
+  for line in lines {
+    let (name, value) = line.split_once(':').ok_or("Malformed header")?;
+    headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+  }
+
+  let (path, query) = target.split_once('?').unwrap_or((target, ""));
+  Ok(HttpRequest {
+    method: method.to_string(),
+    path: percent_decode(path),
+    query: parse_query(query),
+    headers,
+    body: Vec::new(),
+  })
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+  query
+    .split('&')
+    .filter(|pair| !pair.is_empty())
+    .map(|pair| {
+      let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+      (percent_decode(key), percent_decode(value))
+    })
+    .collect()
+}
+
+fn percent_decode(value: &str) -> String {
+  let bytes = value.as_bytes();
+  let mut out = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    let decoded = match bytes[i] {
+      b'%' => value
+        .get(i + 1..i + 3)
+        .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+      _ => None,
+    };
+    match (decoded, bytes[i]) {
+      (Some(byte), _) => {
+        out.push(byte);
+        i += 3;
+      }
+      (None, b'+') => {
+        out.push(b' ');
+        i += 1;
+      }
+      (None, byte) => {
+        out.push(byte);
+        i += 1;
+      }
+    }
+  }
+  String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_request_line_headers_and_query() {
+    let request =
+      parse_head("GET /api/events?token=a%2Bb&x HTTP/1.1\r\nHost: localhost\r\nX-Thing:  1 ")
+        .unwrap();
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/api/events");
+    assert_eq!(request.query["token"], "a+b");
+    assert_eq!(request.query["x"], "");
+    assert_eq!(request.headers["host"], "localhost");
+    assert_eq!(request.headers["x-thing"], "1");
+  }
+
+  #[test]
+  fn rejects_malformed_heads() {
+    assert!(parse_head("GET /").is_err());
+    assert!(parse_head("GET / SPDY/3").is_err());
+    assert!(parse_head("GET / HTTP/1.1\r\nno colon").is_err());
+  }
+
+  #[test]
+  fn reads_the_token_only_from_the_authorization_header() {
+    let mut request =
+      parse_head("GET /?token=query HTTP/1.1\r\nAuthorization: Bearer header").unwrap();
+    assert_eq!(request.token(), Some("header"));
+    request.headers.clear();
+    assert_eq!(request.token(), None);
+  }
+
+  #[test]
+  fn allows_only_configured_origins() {
+    let allowed = vec!["chrome-extension://abc".to_string()];
+    let request = parse_head("GET / HTTP/1.1\r\nOrigin: chrome-extension://abc").unwrap();
+    assert_eq!(
+      request.allowed_origin(&allowed).ok(),
+      Some(Some("chrome-extension://abc"))
+    );
+
+    let request = parse_head("GET / HTTP/1.1\r\nOrigin: https://evil.example").unwrap();
+    assert_eq!(
+      request.allowed_origin(&allowed).err().map(|r| r.status),
+      Some(403)
+    );
+
+    let request = parse_head("GET / HTTP/1.1\r\nHost: localhost").unwrap();
+    assert_eq!(request.allowed_origin(&allowed).ok(), Some(None));
+    assert_eq!(cors_headers(None), "");
+  }
+
+  #[tokio::test]
+  async fn reads_the_body_up_to_content_length() {
+    let raw = b"POST /api/info HTTP/1.1\r\nContent-Length: 4\r\n\r\n{\"a\"}";
+    let request = read_request(&mut &raw[..]).await.ok().unwrap();
+    assert_eq!(request.body, b"{\"a\"");
+  }
+
+  #[test]
+  fn keeps_invalid_escapes_verbatim() {
+    assert_eq!(percent_decode("100%"), "100%");
+    assert_eq!(percent_decode("%zz%41"), "%zzA");
+  }
+}
@@ -0,0 +1,175 @@
+use crate::api::api_http::{HttpRequest, HttpResponse};
+use crate::cli::cli_handler::show_download_group;
+use crate::commands::{group_cancel, media_download, media_info, queue_status};
+use crate::models::download::{FormatOptions, QueuePriority};
+use crate::models::payloads::{DownloadGroupPayload, MediaGroupAddPayload};
+use crate::models::DownloadItem;
+use crate::runners::template_context::TemplateContext;
+use crate::SharedPreferences;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter, Manager};
+use uuid::Uuid;
+
+/// Clients never send `DownloadOverrides`, which could point post-download hooks or cookie
+/// files anywhere. They pick one of the download profiles from the preferences by name instead.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct InfoBody {
+  url: String,
+  id: Option<String>,
+  group_id: Option<String>,
+  profile: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct DownloadBody {
+  group_id: Option<String>,
+  items: Vec<DownloadBodyItem>,
+  profile: Option<String>,
+  priority: Option<QueuePriority>,
+  start_at: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct DownloadBodyItem {
+  url: String,
+  id: Option<String>,
+  /// Replaces the format of the profile.
+  format: Option<FormatOptions>,
+}
+
+/// Answers an authorized request by calling the command the endpoint maps onto.
+pub fn route(app: &AppHandle, request: &HttpRequest) -> HttpResponse {
+  let cancel_group_id = request
+    .path
+    .strip_prefix("/api/groups/")
+    .and_then(|rest| rest.strip_suffix("/cancel"));
+
+  let result = match (request.method.as_str(), request.path.as_str()) {
+    ("GET", "/api/status") => Ok(HttpResponse::json(200, &queue_status(app.clone()))),
+    ("POST", "/api/info") => info(app, request),
+    ("POST", "/api/download") => download(app, request),
+    ("POST", _) if cancel_group_id.is_some_and(|id| !id.is_empty()) => {
+      cancel(app, cancel_group_id.unwrap_or_default())
+    }
+    _ => Err(HttpResponse::error(404, "Not found")),
+  };
+  result.unwrap_or_else(|response| response)
+}
+
+fn info(app: &AppHandle, request: &HttpRequest) -> Result<HttpResponse, HttpResponse> {
+  let body: InfoBody = parse_body(request)?;
+  let profile = app
+    .state::<SharedPreferences>()
+    .load()
+    .download_profile(body.profile.as_deref())
+    .map_err(|e| HttpResponse::error(400, &e))?;
+  let id = body.id.unwrap_or_else(|| Uuid::new_v4().to_string());
+  let group_id = body.group_id.unwrap_or_else(|| id.clone());
+
+  // The frontend only shows fetched media of groups it knows about.
+  let _ = app.emit(
+    "media_group_add",
+    MediaGroupAddPayload {
+      group_id: group_id.clone(),
+      id: id.clone(),
+      url: body.url.clone(),
+    },
+  );
+  let group_id = media_info(
+    body.url,
+    id.clone(),
+    group_id,
+    profile.overrides,
+    app.state(),
+  )
+  .map_err(|e| HttpResponse::error(500, &e))?;
+  Ok(HttpResponse::json(
+    200,
+    &json!({ "id": id, "groupId": group_id }),
+  ))
+}
+
+fn download(app: &AppHandle, request: &HttpRequest) -> Result<HttpResponse, HttpResponse> {
+  let body: DownloadBody = parse_body(request)?;
+  if body.items.is_empty() {
+    return Err(HttpResponse::error(400, "No items to download"));
+  }
+  let profile = app
+    .state::<SharedPreferences>()
+    .load()
+    .download_profile(body.profile.as_deref())
+    .map_err(|e| HttpResponse::error(400, &e))?;
+  let priority = body.priority.unwrap_or_default();
+  let items = body
+    .items
+    .into_iter()
+    .map(|item| DownloadItem {
+      id: item.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+      url: item.url,
+      format: item.format.unwrap_or_else(|| profile.format.clone()),
+      subtitle_inventory: None,
+      overrides: profile.overrides.clone(),
+      template_context: TemplateContext {
+        values: HashMap::new(),
+      },
+      priority,
+      direct_playlist: false,
+      live: None,
+    })
+    .collect::<Vec<_>>();
+  let group_id = body.group_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+  let group = DownloadGroupPayload::new(&group_id, &items);
+
+  let group_id = media_download(
+    app.clone(),
+    group_id,
+    items,
+    body.priority,
+    body.start_at,
+    app.state(),
+  )
+  .map_err(|e| HttpResponse::error(500, &e))?;
+  show_download_group(app, group);
+  Ok(HttpResponse::json(200, &json!({ "groupId": group_id })))
+}
+
+fn cancel(app: &AppHandle, group_id: &str) -> Result<HttpResponse, HttpResponse> {
+  group_cancel(
+    app.clone(),
+    group_id.to_string(),
+    app.state(),
+    app.state(),
+    app.state(),
+    app.state(),
+  );
+  Ok(HttpResponse::no_content())
+}
+
+fn parse_body<T: DeserializeOwned>(request: &HttpRequest) -> Result<T, HttpResponse> {
+  serde_json::from_slice(&request.body)
+    .map_err(|e| HttpResponse::error(400, &format!("Invalid request body: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rejects_client_supplied_overrides() {
+    let info = r#"{"url": "https://example.com", "overrides": {"postDownloadHook": {}}}"#;
+    assert!(serde_json::from_str::<InfoBody>(info).is_err());
+
+    let download = r#"{"items": [{"url": "https://example.com", "overrides": {}}]}"#;
+    assert!(serde_json::from_str::<DownloadBody>(download).is_err());
+
+    let download = r#"{"items": [{"url": "https://example.com"}], "profile": "audio"}"#;
+    let body = serde_json::from_str::<DownloadBody>(download).unwrap();
+    assert_eq!(body.profile.as_deref(), Some("audio"));
+  }
+}
@@ -0,0 +1,196 @@
+use crate::api::api_events::ApiEvents;
+use crate::api::api_http::{cors_headers, read_request, HttpRequest, HttpResponse};
+use crate::api::api_routes::route;
+use crate::api::api_token::{ensure_api_token, tokens_match, EventTickets};
+use crate::state::config_models::ApiSettings;
+use crate::stronghold::stronghold_state::StrongholdState;
+use crate::SharedConfig;
+use std::io;
+use std::net::Ipv4Addr;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::watch;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+const SSE_KEEPALIVE: Duration = Duration::from_secs(15);
+
+#[derive(Default)]
+pub struct ApiServerState {
+  running: Mutex<Option<RunningServer>>,
+  tickets: EventTickets,
+}
+
+struct RunningServer {
+  port: u16,
+  /// Dropping the sender stops the accept loop and every open event stream.
+  _shutdown: watch::Sender<()>,
+}
+
+/// Starts, stops or moves the HTTP API so it matches `settings`.
+pub fn sync_api_server(app: &AppHandle, settings: &ApiSettings) {
+  let Some(state) = app.try_state::<ApiServerState>() else {
+    return;
+  };
+  let mut running = state.running.lock().unwrap();
+  let wanted = settings.enabled.then_some(settings.port);
+  if running.as_ref().map(|server| server.port) == wanted {
+    return;
+  }
+  *running = None;
+
+  let Some(port) = wanted else {
+    tracing::info!("HTTP API stopped");
+    return;
+  };
+  if let Err(e) = ensure_api_token(&app.state::<StrongholdState>()) {
+    tracing::warn!(error = %e, "HTTP API has no token yet, requests will be refused");
+  }
+  match start(app, port) {
+    Ok(server) => {
+      tracing::info!(port, "HTTP API listening on localhost");
+      *running = Some(server);
+    }
+    Err(e) => tracing::warn!(port, error = %e, "Failed to start the HTTP API"),
+  }
+}
+
+fn start(app: &AppHandle, port: u16) -> io::Result<RunningServer> {
+  let listener = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+  listener.set_nonblocking(true)?;
+  let (shutdown, shutdown_rx) = watch::channel(());
+
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    match TcpListener::from_std(listener) {
+      Ok(listener) => accept_loop(app, listener, shutdown_rx).await,
+      Err(e) => tracing::warn!(error = %e, "Failed to register the HTTP API listener"),
+    }
+  });
+  Ok(RunningServer {
+    port,
+    _shutdown: shutdown,
+  })
+}
+
+async fn accept_loop(app: AppHandle, listener: TcpListener, mut shutdown: watch::Receiver<()>) {
+  loop {
+    tokio::select! {
+      _ = shutdown.changed() => break,
+      accepted = listener.accept() => match accepted {
+        Ok((stream, _)) => {
+          let app = app.clone();
+          let shutdown = shutdown.clone();
+          tauri::async_runtime::spawn(async move {
+            handle_connection(app, stream, shutdown).await;
+          });
+        }
+        Err(e) => tracing::warn!(error = %e, "Failed to accept an HTTP API connection"),
+      },
+    }
+  }
+}
+
+async fn handle_connection(app: AppHandle, mut stream: TcpStream, shutdown: watch::Receiver<()>) {
+  let request = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream)).await {
+    Ok(Ok(request)) => request,
+    Ok(Err(response)) => {
+      let _ = stream.write_all(&response.to_bytes(None)).await;
+      return;
+    }
+    Err(_) => return,
+  };
+
+  let allowed_origins = app
+    .state::<SharedConfig>()
+    .load()
+    .api
+    .allowed_origins
+    .clone();
+  let origin = match request.allowed_origin(&allowed_origins) {
+    Ok(origin) => origin,
+    Err(response) => {
+      let _ = stream.write_all(&response.to_bytes(None)).await;
+      return;
+    }
+  };
+
+  let response = if request.method == "OPTIONS" {
+    HttpResponse::no_content()
+  } else if request.method == "GET" && request.path == "/api/events" {
+    let ticket = request
+      .ticket()
+      .is_some_and(|ticket| app.state::<ApiServerState>().tickets.redeem(ticket));
+    match authorize(&app, &request) {
+      Err(response) if !ticket => response,
+      _ => {
+        stream_events(&app, stream, origin, shutdown).await;
+        return;
+      }
+    }
+  } else if let Err(response) = authorize(&app, &request) {
+    response
+  } else if request.method == "POST" && request.path == "/api/events/ticket" {
+    let ticket = app.state::<ApiServerState>().tickets.issue();
+    HttpResponse::json(200, &serde_json::json!({ "ticket": ticket }))
+  } else {
+    route(&app, &request)
+  };
+  let _ = stream.write_all(&response.to_bytes(origin)).await;
+}
+
+fn authorize(app: &AppHandle, request: &HttpRequest) -> Result<(), HttpResponse> {
+  let expected = app
+    .state::<StrongholdState>()
+    .load_api_token()
+    .map_err(|e| HttpResponse::error(503, &e))?
+    .ok_or_else(|| HttpResponse::error(503, "No API token configured"))?;
+  match request.token() {
+    Some(token) if tokens_match(&expected, token) => Ok(()),
+    _ => Err(HttpResponse::error(401, "Invalid or missing token")),
+  }
+}
+
+/// Streams the forwarded `media_*` events as server-sent events until the client disconnects
+/// or the server stops. Opened with the token or with a ticket from `/api/events/ticket`.
+async fn stream_events(
+  app: &AppHandle,
+  mut stream: TcpStream,
+  origin: Option<&str>,
+  mut shutdown: watch::Receiver<()>,
+) {
+  let mut events = app.state::<ApiEvents>().subscribe();
+  let head = format!(
+    "HTTP/1.1 200 OK\r\n\
+     Content-Type: text/event-stream\r\n\
+     Cache-Control: no-cache\r\n\
+     {}\
+     Connection: keep-alive\r\n\r\n",
+    cors_headers(origin),
+  );
+  if stream.write_all(head.as_bytes()).await.is_err() {
+    return;
+  }
+
+  let mut keepalive = tokio::time::interval(SSE_KEEPALIVE);
+  loop {
+    let chunk = tokio::select! {
+      _ = shutdown.changed() => break,
+      _ = keepalive.tick() => ": keepalive\n\n".to_string(),
+      event = events.recv() => match event {
+        Ok(event) => event.to_sse(),
+        Err(RecvError::Lagged(skipped)) => {
+          tracing::debug!(skipped, "HTTP API event stream fell behind");
+          continue;
+        }
+        Err(RecvError::Closed) => break,
+      },
+    };
+    if stream.write_all(chunk.as_bytes()).await.is_err() {
+      break;
+    }
+  }
+}
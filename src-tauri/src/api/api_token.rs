@@ -0,0 +1,97 @@
+use crate::stronghold::stronghold_state::StrongholdState;
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long an event stream ticket can be redeemed.
+const TICKET_TTL: Duration = Duration::from_secs(30);
+
+/// Returns the API token, creating one when the vault does not hold one yet.
+pub fn ensure_api_token(state: &StrongholdState) -> Result<String, String> {
+  match state.load_api_token()? {
+    Some(token) => Ok(token),
+    None => regenerate_api_token(state),
+  }
+}
+
+/// Replaces the API token, which locks out every client that used the old one.
+pub fn regenerate_api_token(state: &StrongholdState) -> Result<String, String> {
+  let token = random_hex(32);
+  state.store_api_token(&token)?;
+  Ok(token)
+}
+
+fn random_hex(len: usize) -> String {
+  let mut bytes = vec![0u8; len];
+  rand::rng().fill_bytes(&mut bytes);
+  hex::encode(bytes)
+}
+
+/// Short-lived, single-use tickets for the event stream. `EventSource` cannot send an
+/// `Authorization` header, so clients trade their token for a ticket to put in the URL.
+#[derive(Default)]
+pub struct EventTickets(Mutex<HashMap<String, Instant>>);
+
+impl EventTickets {
+  pub fn issue(&self) -> String {
+    self.issue_at(Instant::now())
+  }
+
+  pub fn redeem(&self, ticket: &str) -> bool {
+    self.redeem_at(ticket, Instant::now())
+  }
+
+  fn issue_at(&self, now: Instant) -> String {
+    let ticket = random_hex(16);
+    let mut tickets = self.0.lock().unwrap();
+    tickets.retain(|_, expires_at| *expires_at > now);
+    tickets.insert(ticket.clone(), now + TICKET_TTL);
+    ticket
+  }
+
+  fn redeem_at(&self, ticket: &str, now: Instant) -> bool {
+    self
+      .0
+      .lock()
+      .unwrap()
+      .remove(ticket)
+      .is_some_and(|expires_at| expires_at > now)
+  }
+}
+
+/// Compares tokens without bailing out at the first differing byte.
+pub fn tokens_match(expected: &str, given: &str) -> bool {
+  expected.len() == given.len()
+    && expected
+      .bytes()
+      .zip(given.bytes())
+      .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+      == 0
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn tokens_match_only_on_equal_tokens() {
+    assert!(tokens_match("abc123", "abc123"));
+    assert!(!tokens_match("abc123", "abc124"));
+    assert!(!tokens_match("abc123", "abc12"));
+    assert!(!tokens_match("abc123", ""));
+  }
+
+  #[test]
+  fn tickets_are_single_use_and_expire() {
+    let tickets = EventTickets::default();
+    let now = Instant::now();
+    let ticket = tickets.issue_at(now);
+    assert!(tickets.redeem_at(&ticket, now));
+    assert!(!tickets.redeem_at(&ticket, now));
+
+    let ticket = tickets.issue_at(now);
+    assert!(!tickets.redeem_at(&ticket, now + TICKET_TTL));
+    assert!(!tickets.redeem_at("unknown", now));
+  }
+}
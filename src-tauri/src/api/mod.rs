@@ -0,0 +1,5 @@
+pub mod api_events;
+pub mod api_http;
+pub mod api_routes;
+pub mod api_server;
+pub mod api_token;
@@ -0,0 +1,161 @@
+use crate::cli::cli_deep_link::{is_deep_link, parse_deep_link};
+
+/// A subcommand the app was launched with, e.g. `open-video-downloader add <url>... --download`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+  /// Adds `urls` to the queue. With `download` they are downloaded right away, using the
+  /// named profile from the preferences when one is given.
+  Add {
+    urls: Vec<String>,
+    profile: Option<String>,
+    download: bool,
+  },
+  /// Summarizes the queue. `open-video-downloader status` prints it, which the running app
+  /// answers over `reply_to`, a localhost port. Without one it shows a notification.
+  Status { reply_to: Option<u16> },
+}
+
+impl CliCommand {
+  /// Builds an `Add`, checking the options fit together.
+  pub fn add(urls: Vec<String>, profile: Option<String>, download: bool) -> Result<Self, String> {
+    if urls.is_empty() {
+      return Err("Nothing to add, pass one or more URLs".into());
+    }
+    if profile.is_some() && !download {
+      return Err("A profile only applies when downloading right away".into());
+    }
+    Ok(Self::Add {
+      urls,
+      profile,
+      download,
+    })
+  }
+}
+
+/// Parses the arguments the app was launched with, `args[0]` being the binary. Returns `Ok(None)`
+/// when they hold no subcommand, like a plain launch or `--auto-start`. An `ovd://` deep link,
+/// which the OS passes as the only argument, counts as a subcommand.
+pub fn parse_cli_args(args: &[String]) -> Result<Option<CliCommand>, String> {
+  let mut args = args.iter().skip(1).map(String::as_str);
+  match args.next() {
+    Some("add") => parse_add(args).map(Some),
+    Some("status") => parse_status(args).map(Some),
+    Some(arg) if is_deep_link(arg) => parse_deep_link(arg).map(Some),
+    _ => Ok(None),
+  }
+}
+
+fn parse_add<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<CliCommand, String> {
+  let mut urls = Vec::new();
+  let mut profile = None;
+  let mut download = false;
+
+  while let Some(arg) = args.next() {
+    if let Some(name) = arg.strip_prefix("--profile=") {
+      profile = Some(name.to_string());
+      continue;
+    }
+    match arg {
+      "--download" | "-d" => download = true,
+      "--profile" | "-p" => {
+        let name = args.next().ok_or("Missing profile name after --profile")?;
+        profile = Some(name.to_string());
+      }
+      _ if arg.starts_with('-') => return Err(format!("Unknown option: {arg}")),
+      _ => urls.push(arg.to_string()),
+    }
+  }
+
+  CliCommand::add(urls, profile, download)
+}
+
+fn parse_status<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<CliCommand, String> {
+  let reply_to = match args.next() {
+    None => None,
+    Some("--reply-to") => {
+      let port = args.next().ok_or("Missing port after --reply-to")?;
+      Some(port.parse().map_err(|_| format!("Invalid port: {port}"))?)
+    }
+    Some(arg) => return Err(format!("Unexpected argument for status: {arg}")),
+  };
+  match args.next() {
+    None => Ok(CliCommand::Status { reply_to }),
+    Some(arg) => Err(format!("Unexpected argument for status: {arg}")),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(args: &[&str]) -> Result<Option<CliCommand>, String> {
+    let args: Vec<String> = std::iter::once("ovd")
+      .chain(args.iter().copied())
+      .map(String::from)
+      .collect();
+    parse_cli_args(&args)
+  }
+
+  #[test]
+  fn launches_without_a_subcommand_are_ignored() {
+    assert_eq!(parse(&[]), Ok(None));
+    assert_eq!(parse(&["--auto-start"]), Ok(None));
+  }
+
+  #[test]
+  fn parses_add_with_options_in_any_order() {
+    assert_eq!(
+      parse(&["add", "--download", "https://a", "-p", "music", "https://b"]),
+      Ok(Some(CliCommand::Add {
+        urls: vec!["https://a".into(), "https://b".into()],
+        profile: Some("music".into()),
+        download: true,
+      }))
+    );
+    assert_eq!(
+      parse(&["add", "https://a", "--profile=music", "-d"]),
+      Ok(Some(CliCommand::Add {
+        urls: vec!["https://a".into()],
+        profile: Some("music".into()),
+        download: true,
+      }))
+    );
+  }
+
+  #[test]
+  fn rejects_incomplete_add_commands() {
+    assert!(parse(&["add"]).is_err());
+    assert!(parse(&["add", "https://a", "--profile"]).is_err());
+    assert!(parse(&["add", "https://a", "--profile", "music"]).is_err());
+    assert!(parse(&["add", "https://a", "--bogus"]).is_err());
+  }
+
+  #[test]
+  fn parses_deep_links_passed_by_the_os() {
+    assert_eq!(
+      parse(&["ovd://add?url=https%3A%2F%2Fa&start=1"]),
+      Ok(Some(CliCommand::Add {
+        urls: vec!["https://a/".into()],
+        profile: None,
+        download: true,
+      }))
+    );
+  }
+
+  #[test]
+  fn parses_status() {
+    assert_eq!(
+      parse(&["status"]),
+      Ok(Some(CliCommand::Status { reply_to: None }))
+    );
+    assert_eq!(
+      parse(&["status", "--reply-to", "41234"]),
+      Ok(Some(CliCommand::Status {
+        reply_to: Some(41234)
+      }))
+    );
+    assert!(parse(&["status", "extra"]).is_err());
+    assert!(parse(&["status", "--reply-to"]).is_err());
+    assert!(parse(&["status", "--reply-to", "port"]).is_err());
+  }
+}
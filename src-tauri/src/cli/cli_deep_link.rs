@@ -0,0 +1,95 @@
+use crate::cli::cli_args::CliCommand;
+use tauri::Url;
+
+const DEEP_LINK_PREFIX: &str = "ovd://";
+
+pub fn is_deep_link(arg: &str) -> bool {
+  arg
+    .get(..DEEP_LINK_PREFIX.len())
+    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(DEEP_LINK_PREFIX))
+}
+
+/// Parses a deep link such as `ovd://add?url=...&profile=audio&start=1` into the subcommand it
+/// stands for. `url` may be repeated, and only http(s) URLs are accepted since links can come
+/// from any web page.
+pub fn parse_deep_link(link: &str) -> Result<CliCommand, String> {
+  let url = Url::parse(link).map_err(|e| format!("Invalid deep link: {e}"))?;
+  match url.host_str() {
+    Some("add") => parse_add(&url),
+    Some("status") => Ok(CliCommand::Status { reply_to: None }),
+    other => Err(format!(
+      "Unknown deep link action: {}",
+      other.unwrap_or_default()
+    )),
+  }
+}
+
+fn parse_add(url: &Url) -> Result<CliCommand, String> {
+  let mut urls = Vec::new();
+  let mut profile = None;
+  let mut download = false;
+  for (key, value) in url.query_pairs() {
+    match key.as_ref() {
+      "url" => urls.push(media_url(&value)?),
+      "profile" => profile = Some(value.into_owned()),
+      "start" => download = matches!(value.as_ref(), "1" | "true" | "yes"),
+      _ => {}
+    }
+  }
+  CliCommand::add(urls, profile, download)
+}
+
+fn media_url(value: &str) -> Result<String, String> {
+  let url = Url::parse(value).map_err(|e| format!("Invalid URL {value}: {e}"))?;
+  match url.scheme() {
+    "http" | "https" => Ok(url.into()),
+    scheme => Err(format!("Unsupported URL scheme: {scheme}")),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_add_links() {
+    assert_eq!(
+      parse_deep_link(
+        "ovd://add?url=https%3A%2F%2Fexample.com%2Fwatch%3Fv%3D1&url=https://example.com/2&profile=audio&start=1"
+      ),
+      Ok(CliCommand::Add {
+        urls: vec![
+          "https://example.com/watch?v=1".into(),
+          "https://example.com/2".into()
+        ],
+        profile: Some("audio".into()),
+        download: true,
+      })
+    );
+    assert_eq!(
+      parse_deep_link("OVD://add/?url=https://example.com/1"),
+      Ok(CliCommand::Add {
+        urls: vec!["https://example.com/1".into()],
+        profile: None,
+        download: false,
+      })
+    );
+  }
+
+  #[test]
+  fn rejects_unsafe_or_incomplete_links() {
+    assert!(parse_deep_link("ovd://add?url=file:///etc/passwd&start=1").is_err());
+    assert!(parse_deep_link("ovd://add?url=not%20a%20url").is_err());
+    assert!(parse_deep_link("ovd://add?start=1").is_err());
+    assert!(parse_deep_link("ovd://add?url=https://example.com&profile=audio").is_err());
+    assert!(parse_deep_link("ovd://remove?url=https://example.com").is_err());
+  }
+
+  #[test]
+  fn recognizes_the_scheme_case_insensitively() {
+    assert!(is_deep_link("ovd://add"));
+    assert!(is_deep_link("OVD://status"));
+    assert!(!is_deep_link("ovd:add"));
+    assert!(!is_deep_link("https://example.com"));
+  }
+}
@@ -0,0 +1,251 @@
+use crate::cli::cli_args::{parse_cli_args, CliCommand};
+use crate::cli::cli_deep_link::is_deep_link;
+#[cfg(target_os = "macos")]
+use crate::cli::cli_deep_link::parse_deep_link;
+use crate::cli::cli_status::reply_status;
+use crate::commands::{media_download, notify, NotificationKind};
+use crate::models::download::QueuePriority;
+use crate::models::payloads::{CliAddPayload, DownloadGroupPayload};
+use crate::models::DownloadItem;
+use crate::runners::template_context::TemplateContext;
+use crate::scheduling::queue_status::queue_snapshot;
+use crate::state::preferences_models::DownloadProfile;
+use crate::{SharedConfig, SharedPreferences};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
+use uuid::Uuid;
+
+/// URLs and download groups from `add` commands that arrived before the frontend was ready to
+/// receive them.
+#[derive(Default)]
+pub struct CliState {
+  frontend: Mutex<FrontendQueue>,
+}
+
+#[derive(Default)]
+struct FrontendQueue {
+  ready: bool,
+  pending: Vec<String>,
+  pending_groups: Vec<DownloadGroupPayload>,
+}
+
+/// Runs the subcommand in `args`, if there is one. Returns whether there was, so a launch that
+/// only forwards a command leaves the window alone.
+pub fn handle_cli_args(app: &AppHandle, args: &[String]) -> bool {
+  match parse_cli_args(args) {
+    Ok(Some(command)) => {
+      let command = match args.get(1) {
+        Some(arg) if is_deep_link(arg) => restrict_deep_link(app, command),
+        _ => command,
+      };
+      run_cli_command(app, command);
+      true
+    }
+    Ok(None) => false,
+    Err(e) => {
+      tracing::warn!(error = %e, "Ignoring invalid command line");
+      false
+    }
+  }
+}
+
+/// Registers the `ovd://` scheme where that happens at runtime. Windows and Linux start the app
+/// with the link as its argument, which the single-instance plugin forwards to
+/// `handle_cli_args`. macOS hands links to the running app instead.
+pub fn setup_deep_links(app: &AppHandle) {
+  #[cfg(any(windows, target_os = "linux"))]
+  if let Err(e) = app.deep_link().register_all() {
+    tracing::warn!(error = %e, "Failed to register the ovd:// scheme");
+  }
+
+  #[cfg(target_os = "macos")]
+  {
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+      for url in event.urls() {
+        match parse_deep_link(url.as_str()) {
+          Ok(command) => run_cli_command(&handle, restrict_deep_link(&handle, command)),
+          Err(e) => tracing::warn!(error = %e, "Ignoring invalid deep link"),
+        }
+      }
+    });
+  }
+}
+
+/// Runs the subcommand this process was launched with. A `status` with a reply port that ends
+/// up here found no running app to forward it to, so it answers that and quits.
+pub fn handle_launch_args(app: &AppHandle) {
+  let args: Vec<String> = std::env::args().collect();
+  if let Ok(Some(CliCommand::Status {
+    reply_to: Some(port),
+  })) = parse_cli_args(&args)
+  {
+    if let Err(e) = reply_status(port, "Open Video Downloader is not running") {
+      tracing::warn!(error = %e, "Failed to answer the status command");
+    }
+    app.exit(0);
+    return;
+  }
+  handle_cli_args(app, &args);
+}
+
+/// Whether this process was launched to run a subcommand, in which case it starts hidden.
+pub fn launched_with_command() -> bool {
+  let args: Vec<String> = std::env::args().collect();
+  matches!(parse_cli_args(&args), Ok(Some(_)))
+}
+
+/// Hands the URLs added before the frontend loaded over to it.
+pub fn frontend_ready(app: &AppHandle) {
+  let state = app.state::<CliState>();
+  let mut frontend = state.frontend.lock().unwrap();
+  frontend.ready = true;
+  let urls = std::mem::take(&mut frontend.pending);
+  if !urls.is_empty() {
+    let _ = app.emit("cli_add", CliAddPayload { urls });
+  }
+  for group in std::mem::take(&mut frontend.pending_groups) {
+    let _ = app.emit("download_group", group);
+  }
+}
+
+/// Any web page can open an `ovd://` link, so unless the user opted in, links only add their
+/// URLs to the UI instead of downloading them right away or picking a profile.
+fn restrict_deep_link(app: &AppHandle, command: CliCommand) -> CliCommand {
+  match command {
+    CliCommand::Add {
+      urls,
+      profile,
+      download,
+    } if (download || profile.is_some())
+      && !app.state::<SharedConfig>().load().input.deep_link_downloads =>
+    {
+      tracing::info!("Deep link downloads are off, only adding the URLs of the link");
+      CliCommand::Add {
+        urls,
+        profile: None,
+        download: false,
+      }
+    }
+    command => command,
+  }
+}
+
+fn run_cli_command(app: &AppHandle, command: CliCommand) {
+  match command {
+    CliCommand::Add {
+      urls,
+      profile,
+      download: true,
+    } => {
+      if let Err(e) = queue_downloads(app, urls, profile.as_deref()) {
+        tracing::warn!(error = %e, "Failed to queue downloads from the command line");
+      }
+    }
+    CliCommand::Add { urls, .. } => {
+      let state = app.state::<CliState>();
+      let mut frontend = state.frontend.lock().unwrap();
+      if frontend.ready {
+        let _ = app.emit("cli_add", CliAddPayload { urls });
+      } else {
+        frontend.pending.extend(urls);
+      }
+    }
+    CliCommand::Status { reply_to } => report_status(app, reply_to),
+  }
+}
+
+/// Downloads `urls` straight away, without fetching their info for the GUI first.
+pub fn queue_downloads(
+  app: &AppHandle,
+  urls: Vec<String>,
+  profile: Option<&str>,
+) -> Result<(), String> {
+  let DownloadProfile { format, overrides } = app
+    .state::<SharedPreferences>()
+    .load()
+    .download_profile(profile)?;
+
+  let count = urls.len();
+  let group_id = Uuid::new_v4().to_string();
+  let items: Vec<DownloadItem> = urls
+    .into_iter()
+    .map(|url| DownloadItem {
+      id: Uuid::new_v4().to_string(),
+      url,
+      format: format.clone(),
+      subtitle_inventory: None,
+      overrides: overrides.clone(),
+      template_context: TemplateContext {
+        values: HashMap::new(),
+      },
+      priority: QueuePriority::Normal,
+      direct_playlist: false,
+      live: None,
+    })
+    .collect();
+  let group = DownloadGroupPayload::new(&group_id, &items);
+  media_download(app.clone(), group_id, items, None, None, app.state())?;
+  show_download_group(app, group);
+
+  notify(
+    app.clone(),
+    app.state(),
+    app.state(),
+    NotificationKind::QueueDownloading,
+    Some(HashMap::from([("n".to_string(), count.to_string())])),
+    true,
+  )
+}
+
+/// Adds a group queued without the frontend to its list, once it is there to receive it.
+pub fn show_download_group(app: &AppHandle, group: DownloadGroupPayload) {
+  let state = app.state::<CliState>();
+  let mut frontend = state.frontend.lock().unwrap();
+  if frontend.ready {
+    let _ = app.emit("download_group", group);
+  } else {
+    frontend.pending_groups.push(group);
+  }
+}
+
+/// Prints the queue status in the `status` launch listening on `reply_to`, or shows it as a
+/// notification when there is none, like for an `ovd://status` link.
+fn report_status(app: &AppHandle, reply_to: Option<u16>) {
+  let snapshot = queue_snapshot(app);
+  let downloading: usize = snapshot
+    .groups
+    .iter()
+    .map(|group| group.download_in_flight)
+    .sum();
+  let queued: usize = snapshot
+    .groups
+    .iter()
+    .map(|group| group.download_pending + group.fetch_pending + group.fetch_in_flight)
+    .sum();
+
+  if let Some(port) = reply_to {
+    let status = format!("{downloading} downloading, {queued} queued");
+    if let Err(e) = reply_status(port, &status) {
+      tracing::warn!(error = %e, "Failed to answer the status command");
+    }
+    return;
+  }
+
+  let params = HashMap::from([
+    ("downloading".to_string(), downloading.to_string()),
+    ("queued".to_string(), queued.to_string()),
+  ]);
+  if let Err(e) = notify(
+    app.clone(),
+    app.state(),
+    app.state(),
+    NotificationKind::QueueStatus,
+    Some(params),
+    true,
+  ) {
+    tracing::warn!(error = %e, "Failed to show the queue status");
+  }
+}
@@ -0,0 +1,78 @@
+use crate::cli::cli_args::{parse_cli_args, CliCommand};
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long `status` waits for the running app to answer.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Answers a plain `open-video-downloader status` in the process it was typed in. The
+/// single-instance plugin only forwards arguments one way, so this starts a second copy of the
+/// app with a localhost port to reply on, which the plugin forwards to the running app, and
+/// prints what comes back. Returns false for any other launch, which starts the app as usual.
+pub fn print_status_if_requested() -> bool {
+  let args: Vec<String> = std::env::args().collect();
+  if parse_cli_args(&args) != Ok(Some(CliCommand::Status { reply_to: None })) {
+    return false;
+  }
+
+  attach_parent_console();
+  match request_status() {
+    Ok(status) => println!("{status}"),
+    Err(e) => eprintln!("Failed to get the queue status: {e}"),
+  }
+  true
+}
+
+/// Sends `status` back to the `status` launch listening on `port`.
+pub fn reply_status(port: u16, status: &str) -> io::Result<()> {
+  let addr = (Ipv4Addr::LOCALHOST, port).into();
+  let mut stream = TcpStream::connect_timeout(&addr, Duration::from_secs(2))?;
+  stream.write_all(status.as_bytes())
+}
+
+fn request_status() -> io::Result<String> {
+  let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+  let port = listener.local_addr()?.port();
+  Command::new(std::env::current_exe()?)
+    .args(["status", "--reply-to", &port.to_string()])
+    .stdin(Stdio::null())
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .spawn()?;
+
+  listener.set_nonblocking(true)?;
+  let deadline = Instant::now() + REPLY_TIMEOUT;
+  let mut stream = loop {
+    match listener.accept() {
+      Ok((stream, _)) => break stream,
+      Err(e) if e.kind() == ErrorKind::WouldBlock && Instant::now() < deadline => {
+        std::thread::sleep(POLL_INTERVAL)
+      }
+      Err(e) if e.kind() == ErrorKind::WouldBlock => {
+        return Err(io::Error::new(
+          ErrorKind::TimedOut,
+          "the app did not answer",
+        ))
+      }
+      Err(e) => return Err(e),
+    }
+  };
+  stream.set_nonblocking(false)?;
+  stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+  let mut status = String::new();
+  stream.read_to_string(&mut status)?;
+  Ok(status)
+}
+
+/// Release builds on Windows have no console of their own, so print to the one `status` was
+/// typed in.
+fn attach_parent_console() {
+  #[cfg(windows)]
+  unsafe {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    AttachConsole(ATTACH_PARENT_PROCESS);
+  }
+}
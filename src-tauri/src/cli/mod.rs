@@ -0,0 +1,4 @@
+pub mod cli_args;
+pub mod cli_deep_link;
+pub mod cli_handler;
+pub mod cli_status;
@@ -0,0 +1,217 @@
+use regex::RegexSet;
+use std::collections::{HashSet, VecDeque};
+use tauri::Url;
+
+const SEEN_CAPACITY: usize = 256;
+
+/// Short and alternative domains that don't carry the name of their extractor.
+const HOST_ALIASES: &[(&str, &str)] = &[
+  ("youtu.be", "youtube"),
+  ("x.com", "twitter"),
+  ("dai.ly", "dailymotion"),
+  ("redd.it", "reddit"),
+  ("fb.watch", "facebook"),
+  ("instagr.am", "instagram"),
+  ("b23.tv", "bilibili"),
+  ("nico.ms", "niconico"),
+];
+
+/// The http(s) URLs in copied text, in order and without duplicates.
+pub fn extract_urls(text: &str) -> Vec<String> {
+  let mut urls: Vec<String> = Vec::new();
+  for word in text.split_whitespace() {
+    let word = word.trim_matches(|c: char| matches!(c, '<' | '>' | '(' | ')' | '"' | '\'' | ','));
+    let Ok(url) = Url::parse(word) else {
+      continue;
+    };
+    if matches!(url.scheme(), "http" | "https")
+      && url.host_str().is_some()
+      && !urls.iter().any(|seen| seen == word)
+    {
+      urls.push(word.to_string());
+    }
+  }
+  urls
+}
+
+/// Extractor names as printed by `yt-dlp --list-extractors`, reduced to the site part and
+/// lowercased, e.g. `youtube:tab` becomes `youtube`. The catch-all generic extractor is left out
+/// since it would accept every URL.
+pub fn extractor_names(listing: &str) -> HashSet<String> {
+  listing
+    .lines()
+    .filter_map(|line| line.split(':').next())
+    .map(|name| name.trim().to_ascii_lowercase())
+    .filter(|name| !name.is_empty() && name != "generic")
+    .collect()
+}
+
+/// Decides which copied URLs are worth picking up.
+pub struct UrlMatcher {
+  patterns: RegexSet,
+  extractors: Option<HashSet<String>>,
+}
+
+impl UrlMatcher {
+  pub fn new(patterns: &[String], extractors: Option<HashSet<String>>) -> Result<Self, String> {
+    let patterns = RegexSet::new(patterns).map_err(|e| e.to_string())?;
+    Ok(Self {
+      patterns,
+      extractors,
+    })
+  }
+
+  /// Whether there is anything to match URLs against.
+  pub fn is_empty(&self) -> bool {
+    self.patterns.is_empty() && self.extractors.is_none()
+  }
+
+  /// A URL matches one of the patterns, or its host names a known extractor, so
+  /// `music.youtube.com` matches through `youtube` and `youtu.be` through its alias. Without an
+  /// extractor list only the patterns are checked.
+  pub fn matches(&self, url: &str) -> bool {
+    if self.patterns.is_match(url) {
+      return true;
+    }
+    self.extractors.as_ref().is_some_and(|extractors| {
+      host_labels(url)
+        .iter()
+        .any(|label| extractors.contains(label))
+    })
+  }
+}
+
+/// Host labels without the top-level domain and a leading `www`, plus the extractor name of a
+/// known alias domain.
+fn host_labels(url: &str) -> Vec<String> {
+  let Some(host) = Url::parse(url)
+    .ok()
+    .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+  else {
+    return Vec::new();
+  };
+  let mut labels: Vec<String> = host.split('.').map(String::from).collect();
+  labels.pop();
+  labels.retain(|label| label != "www");
+  labels.extend(
+    HOST_ALIASES
+      .iter()
+      .filter(|(domain, _)| {
+        host == *domain
+          || host
+            .strip_suffix(domain)
+            .is_some_and(|sub| sub.ends_with('.'))
+      })
+      .map(|(_, name)| name.to_string()),
+  );
+  labels
+}
+
+/// Recently picked up URLs, so copying the same link twice only reports it once.
+#[derive(Default)]
+pub struct SeenUrls {
+  order: VecDeque<String>,
+  set: HashSet<String>,
+}
+
+impl SeenUrls {
+  /// Records `url` and returns whether it was new. The oldest entry is forgotten once the
+  /// capacity is reached.
+  pub fn insert(&mut self, url: &str) -> bool {
+    if !self.set.insert(url.to_string()) {
+      return false;
+    }
+    self.order.push_back(url.to_string());
+    if self.order.len() > SEEN_CAPACITY {
+      if let Some(oldest) = self.order.pop_front() {
+        self.set.remove(&oldest);
+      }
+    }
+    true
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn extractors() -> HashSet<String> {
+    extractor_names("youtube\nyoutube:tab\nVimeo\ngeneric\n")
+  }
+
+  #[test]
+  fn extracts_http_urls_from_text() {
+    let text = "see <https://a.example/x>, (http://b.example) ftp://c.example https://a.example/x";
+    assert_eq!(
+      extract_urls(text),
+      vec!["https://a.example/x", "http://b.example"]
+    );
+    assert!(extract_urls("just some words").is_empty());
+  }
+
+  #[test]
+  fn reduces_extractor_names_to_sites() {
+    let names = extractors();
+    assert!(names.contains("youtube"));
+    assert!(names.contains("vimeo"));
+    assert!(!names.contains("generic"));
+    assert_eq!(names.len(), 2);
+  }
+
+  #[test]
+  fn matches_hosts_against_extractors() {
+    let matcher = UrlMatcher::new(&[], Some(extractors())).unwrap();
+    assert!(matcher.matches("https://music.youtube.com/watch?v=1"));
+    assert!(matcher.matches("https://www.vimeo.com/1"));
+    assert!(!matcher.matches("https://example.com/youtube"));
+  }
+
+  #[test]
+  fn matches_patterns_alongside_extractors() {
+    let patterns = vec![r"^https://youtu\.be/".to_string()];
+    let matcher = UrlMatcher::new(&patterns, Some(extractors())).unwrap();
+    assert!(matcher.matches("https://youtu.be/abc"));
+    assert!(matcher.matches("https://youtube.com/watch?v=1"));
+
+    let matcher = UrlMatcher::new(&patterns, None).unwrap();
+    assert!(matcher.matches("https://youtu.be/abc"));
+    assert!(!matcher.matches("https://youtube.com/watch?v=1"));
+    assert!(UrlMatcher::new(&["(".to_string()], None).is_err());
+  }
+
+  #[test]
+  fn matches_nothing_without_patterns_or_extractors() {
+    let matcher = UrlMatcher::new(&[], None).unwrap();
+    assert!(matcher.is_empty());
+    assert!(!matcher.matches("https://any.example"));
+    assert!(!matcher.matches("https://youtube.com/watch?v=1"));
+  }
+
+  #[test]
+  fn matches_alias_domains_through_their_extractor() {
+    let extractors = extractor_names(
+      "youtube
+Twitter
+Dailymotion
+",
+    );
+    let matcher = UrlMatcher::new(&[], Some(extractors)).unwrap();
+    assert!(matcher.matches("https://youtu.be/abc"));
+    assert!(matcher.matches("https://x.com/user/status/1"));
+    assert!(matcher.matches("https://mobile.x.com/user/status/1"));
+    assert!(matcher.matches("https://dai.ly/x8abc"));
+    assert!(!matcher.matches("https://box.com/video"));
+    assert!(!matcher.matches("https://notyoutu.be/abc"));
+  }
+
+  #[test]
+  fn seen_urls_are_reported_once_and_bounded() {
+    let mut seen = SeenUrls::default();
+    assert!(seen.insert("https://a.example"));
+    assert!(!seen.insert("https://a.example"));
+    for i in 0..SEEN_CAPACITY {
+      seen.insert(&format!("https://b.example/{i}"));
+    }
+    assert!(seen.insert("https://a.example"));
+  }
+}
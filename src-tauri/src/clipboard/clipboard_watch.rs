@@ -0,0 +1,153 @@
+use crate::cli::cli_handler::queue_downloads;
+use crate::clipboard::clipboard_match::{extract_urls, extractor_names, SeenUrls, UrlMatcher};
+use crate::models::payloads::ClipboardUrlPayload;
+use crate::runners::ytdlp_info::run_ytdlp_list_extractors;
+use crate::state::config_models::{ClipboardWatchAction, ClipboardWatchSettings};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tokio::sync::watch;
+
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Default)]
+pub struct ClipboardWatchState {
+  running: Mutex<Option<RunningWatcher>>,
+  /// yt-dlp's extractor names, listed once and kept across restarts of the watcher.
+  extractors: Mutex<Option<HashSet<String>>>,
+}
+
+struct RunningWatcher {
+  settings: ClipboardWatchSettings,
+  /// Dropping the sender stops the polling loop.
+  _shutdown: watch::Sender<()>,
+}
+
+/// Starts, stops or restarts the clipboard watcher so it matches `settings`.
+pub fn sync_clipboard_watch(app: &AppHandle, settings: &ClipboardWatchSettings) {
+  let Some(state) = app.try_state::<ClipboardWatchState>() else {
+    return;
+  };
+  let mut running = state.running.lock().unwrap();
+  let wanted = settings.enabled.then_some(settings);
+  if running.as_ref().map(|watcher| &watcher.settings) == wanted {
+    return;
+  }
+  *running = None;
+
+  let Some(settings) = wanted else {
+    tracing::info!("Clipboard watcher stopped");
+    return;
+  };
+  let (shutdown, shutdown_rx) = watch::channel(());
+  let app = app.clone();
+  let loop_settings = settings.clone();
+  tauri::async_runtime::spawn(async move {
+    watch_loop(app, loop_settings, shutdown_rx).await;
+  });
+  tracing::info!("Clipboard watcher started");
+  *running = Some(RunningWatcher {
+    settings: settings.clone(),
+    _shutdown: shutdown,
+  });
+}
+
+async fn watch_loop(
+  app: AppHandle,
+  settings: ClipboardWatchSettings,
+  mut shutdown: watch::Receiver<()>,
+) {
+  let matcher = match build_matcher(&app, &settings).await {
+    Ok(matcher) => matcher,
+    Err(e) => {
+      tracing::warn!(error = %e, "Invalid clipboard watcher pattern, the watcher is not running");
+      return;
+    }
+  };
+
+  // Whatever was copied before the watcher started is not picked up.
+  let mut last_text = read_clipboard(&app);
+  let mut seen = SeenUrls::default();
+  let mut ticker =
+    tokio::time::interval(Duration::from_millis(settings.poll_interval_ms).max(MIN_POLL_INTERVAL));
+  loop {
+    tokio::select! {
+      _ = shutdown.changed() => break,
+      _ = ticker.tick() => {}
+    }
+    let Some(text) = read_clipboard(&app) else {
+      continue;
+    };
+    if last_text.as_deref() == Some(text.as_str()) {
+      continue;
+    }
+    let urls: Vec<String> = extract_urls(&text)
+      .into_iter()
+      .filter(|url| matcher.matches(url) && seen.insert(url))
+      .collect();
+    last_text = Some(text);
+    if !urls.is_empty() {
+      pick_up(&app, &settings, urls);
+    }
+  }
+}
+
+async fn build_matcher(
+  app: &AppHandle,
+  settings: &ClipboardWatchSettings,
+) -> Result<UrlMatcher, String> {
+  let extractors = if settings.match_extractors {
+    load_extractors(app).await
+  } else {
+    None
+  };
+  let matcher = UrlMatcher::new(&settings.patterns, extractors)?;
+  if matcher.is_empty() {
+    tracing::warn!("No patterns or extractors to match copied URLs against, none are picked up");
+  }
+  Ok(matcher)
+}
+
+async fn load_extractors(app: &AppHandle) -> Option<HashSet<String>> {
+  let state = app.state::<ClipboardWatchState>();
+  if let Some(extractors) = state.extractors.lock().unwrap().clone() {
+    return Some(extractors);
+  }
+  match run_ytdlp_list_extractors(app).await {
+    Ok(listing) => {
+      let extractors = extractor_names(&listing);
+      *state.extractors.lock().unwrap() = Some(extractors.clone());
+      Some(extractors)
+    }
+    Err(e) => {
+      tracing::warn!(error = %e, "Failed to list extractors, only the patterns are checked");
+      None
+    }
+  }
+}
+
+/// The copied text, if the clipboard holds any.
+fn read_clipboard(app: &AppHandle) -> Option<String> {
+  app
+    .clipboard()
+    .read_text()
+    .ok()
+    .filter(|text| !text.trim().is_empty())
+}
+
+fn pick_up(app: &AppHandle, settings: &ClipboardWatchSettings, urls: Vec<String>) {
+  match settings.action {
+    ClipboardWatchAction::Emit => {
+      for url in urls {
+        let _ = app.emit("clipboard_url", ClipboardUrlPayload { url });
+      }
+    }
+    ClipboardWatchAction::Queue => {
+      if let Err(e) = queue_downloads(app, urls, settings.profile.as_deref()) {
+        tracing::warn!(error = %e, "Failed to queue copied URLs");
+      }
+    }
+  }
+}
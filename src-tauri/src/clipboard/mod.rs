@@ -0,0 +1,2 @@
+pub mod clipboard_match;
+pub mod clipboard_watch;
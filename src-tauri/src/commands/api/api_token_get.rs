@@ -0,0 +1,9 @@
+use crate::api::api_token::ensure_api_token;
+use crate::stronghold::stronghold_state::StrongholdState;
+use tauri::State;
+
+/// Returns the token for the local HTTP API, creating it on first use.
+#[tauri::command]
+pub async fn api_token_get(state: State<'_, StrongholdState>) -> Result<String, String> {
+  ensure_api_token(&state)
+}
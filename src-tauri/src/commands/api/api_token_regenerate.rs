@@ -0,0 +1,8 @@
+use crate::api::api_token::regenerate_api_token;
+use crate::stronghold::stronghold_state::StrongholdState;
+use tauri::State;
+
+#[tauri::command]
+pub async fn api_token_regenerate(state: State<'_, StrongholdState>) -> Result<String, String> {
+  regenerate_api_token(&state)
+}
@@ -0,0 +1,5 @@
+pub mod api_token_get;
+pub mod api_token_regenerate;
+
+pub use api_token_get::*;
+pub use api_token_regenerate::*;
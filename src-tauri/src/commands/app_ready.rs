@@ -1,3 +1,4 @@
+use crate::cli::cli_handler::{frontend_ready, launched_with_command};
 use crate::SharedConfig;
 use std::env::args;
 use tauri::{AppHandle, Manager, State};
@@ -7,7 +8,8 @@ pub fn app_ready(app: AppHandle, cfg_handle: State<'_, SharedConfig>) {
   let args: Vec<String> = args().collect();
   let is_autostart = args.contains(&"--auto-start".to_string());
   let cfg = cfg_handle.load();
-  if cfg.system.auto_start_minimised && is_autostart {
+  frontend_ready(&app);
+  if (cfg.system.auto_start_minimised && is_autostart) || launched_with_command() {
     return;
   }
 
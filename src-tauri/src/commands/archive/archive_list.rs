@@ -0,0 +1,7 @@
+use crate::state::download_archive::{ArchiveEntry, DownloadArchive};
+use tauri::State;
+
+#[tauri::command]
+pub fn archive_list(archive: State<'_, DownloadArchive>) -> Result<Vec<ArchiveEntry>, String> {
+  archive.entries().map_err(|e| e.to_string())
+}
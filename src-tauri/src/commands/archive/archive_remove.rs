@@ -0,0 +1,12 @@
+use crate::state::download_archive::{ArchiveEntry, DownloadArchive};
+use tauri::State;
+
+/// Removes entries from the download archive so they can be downloaded again.
+/// Returns the number of removed entries. Fails while downloads that use the archive are running.
+#[tauri::command]
+pub fn archive_remove(
+  archive: State<'_, DownloadArchive>,
+  entries: Vec<ArchiveEntry>,
+) -> Result<usize, String> {
+  archive.remove(&entries).map_err(|e| e.to_string())
+}
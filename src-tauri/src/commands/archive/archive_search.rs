@@ -0,0 +1,11 @@
+use crate::state::download_archive::{ArchiveEntry, DownloadArchive};
+use tauri::State;
+
+/// Archive entries whose id or extractor contains `query`, ignoring case.
+#[tauri::command]
+pub fn archive_search(
+  archive: State<'_, DownloadArchive>,
+  query: String,
+) -> Result<Vec<ArchiveEntry>, String> {
+  archive.search(&query).map_err(|e| e.to_string())
+}
@@ -0,0 +1,7 @@
+pub mod archive_list;
+pub mod archive_remove;
+pub mod archive_search;
+
+pub use archive_list::*;
+pub use archive_remove::*;
+pub use archive_search::*;
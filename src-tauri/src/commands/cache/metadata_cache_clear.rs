@@ -0,0 +1,7 @@
+use crate::state::metadata_cache::MetadataCache;
+use tauri::State;
+
+#[tauri::command]
+pub fn metadata_cache_clear(cache: State<'_, MetadataCache>) -> Result<usize, String> {
+  cache.clear().map_err(|e| e.to_string())
+}
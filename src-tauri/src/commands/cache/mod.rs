@@ -0,0 +1,3 @@
+pub mod metadata_cache_clear;
+
+pub use metadata_cache_clear::*;
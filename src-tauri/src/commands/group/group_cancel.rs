@@ -1,14 +1,20 @@
 use crate::logging::LogStoreState;
 use crate::scheduling::dispatcher::DispatchRequest;
-use crate::scheduling::download_pipeline::DownloadSender;
-use crate::scheduling::fetch_pipeline::FetchSender;
+use crate::scheduling::download_pipeline::{
+  cancel_paused_group, complete_cancelled_downloads, DownloadSender,
+};
+use crate::scheduling::fetch_pipeline::{complete_cancelled_fetches, FetchSender};
 use crate::scheduling::group_state::cancel_group;
-use tauri::State;
+use crate::scheduling::schedule::set_group_start;
+use crate::SharedDownloadQueue;
+use tauri::{AppHandle, State};
 
 #[tauri::command]
 pub fn group_cancel(
+  app: AppHandle,
   group_id: String,
   log_state: State<'_, LogStoreState>,
+  download_queue: State<'_, SharedDownloadQueue>,
   fetch_sender: State<'_, FetchSender>,
   download_sender: State<'_, DownloadSender>,
 ) {
@@ -21,6 +27,16 @@ pub fn group_cancel(
   let _ = download_sender.0.send(DispatchRequest::Cleanup {
     group_id: group_id.clone(),
   });
+  cancel_paused_group(&download_sender.0, &app, &group_id);
+  complete_cancelled_fetches(&app, &group_id);
+  complete_cancelled_downloads(&app, &group_id);
+  if let Err(e) = set_group_start(&app, &group_id, None) {
+    tracing::warn!(group_id = %group_id, error = %e, "Failed to clear scheduled group start");
+  }
+
+  if let Err(e) = download_queue.update(&app, |value| value.remove_group(&group_id)) {
+    tracing::warn!(group_id = %group_id, error = %e, "Failed to remove group from journal");
+  }
 
   let mut store = log_state.write();
   store.remove_group(&group_id);
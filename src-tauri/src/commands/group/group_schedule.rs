@@ -0,0 +1,13 @@
+use crate::scheduling::schedule::set_group_start;
+use tauri::AppHandle;
+
+/// Defers a group until `start_at` (unix time in milliseconds), or lets it start right away
+/// when `start_at` is omitted.
+#[tauri::command]
+pub fn group_schedule(
+  app: AppHandle,
+  group_id: String,
+  start_at: Option<u64>,
+) -> Result<(), String> {
+  set_group_start(&app, &group_id, start_at)
+}
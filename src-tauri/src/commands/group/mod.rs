@@ -1,3 +1,5 @@
 pub mod group_cancel;
+pub mod group_schedule;
 
 pub use group_cancel::*;
+pub use group_schedule::*;
@@ -0,0 +1,14 @@
+use crate::models::payloads::MediaControlPayload;
+use crate::scheduling::download_pipeline::{cancel_download, DownloadSender};
+use tauri::{AppHandle, Emitter, State};
+
+#[tauri::command]
+pub fn media_cancel(
+  app: AppHandle,
+  group_id: String,
+  id: String,
+  pipeline: State<'_, DownloadSender>,
+) {
+  cancel_download(&pipeline.0, &app, &id);
+  let _ = app.emit("media_cancelled", MediaControlPayload { group_id, id });
+}
@@ -1,16 +1,27 @@
+use crate::models::download::QueuePriority;
 use crate::models::DownloadItem;
 use crate::scheduling::dispatcher::DispatchRequest;
 use crate::scheduling::download_pipeline::{DownloadRequest, DownloadSender};
-use crate::scheduling::group_state::ensure_group_running;
-use tauri::State;
+use crate::scheduling::group_state::{ensure_group_running, set_group_priority};
+use crate::scheduling::schedule::set_group_start;
+use tauri::{AppHandle, State};
 
 #[tauri::command]
 pub fn media_download(
+  app: AppHandle,
   group_id: String,
   items: Vec<DownloadItem>,
+  priority: Option<QueuePriority>,
+  start_at: Option<u64>,
   pipeline: State<'_, DownloadSender>,
-) -> String {
+) -> Result<String, String> {
   ensure_group_running(&group_id);
+  if let Some(priority) = priority {
+    set_group_priority(&group_id, priority);
+  }
+  if start_at.is_some() {
+    set_group_start(&app, &group_id, start_at)?;
+  }
 
   pipeline
     .0
@@ -18,7 +29,7 @@ pub fn media_download(
       group_id: group_id.clone(),
       items,
     }))
-    .unwrap();
+    .map_err(|e| e.to_string())?;
 
-  group_id
+  Ok(group_id)
 }
@@ -0,0 +1,9 @@
+use crate::models::payloads::MediaControlPayload;
+use crate::scheduling::download_pipeline::pause_download;
+use tauri::{AppHandle, Emitter};
+
+#[tauri::command]
+pub fn media_pause(app: AppHandle, group_id: String, id: String) {
+  pause_download(&id);
+  let _ = app.emit("media_paused", MediaControlPayload { group_id, id });
+}
@@ -0,0 +1,11 @@
+use crate::models::payloads::MediaControlPayload;
+use crate::scheduling::download_pipeline::pause_all_downloads;
+use crate::DownloadLimiter;
+use tauri::{AppHandle, Emitter, State};
+
+#[tauri::command]
+pub fn media_pause_all(app: AppHandle, limiter: State<'_, DownloadLimiter>) {
+  for (group_id, id) in pause_all_downloads(&limiter.0) {
+    let _ = app.emit("media_paused", MediaControlPayload { group_id, id });
+  }
+}
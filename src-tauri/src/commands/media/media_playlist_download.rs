@@ -0,0 +1,62 @@
+use crate::models::download::{
+  DownloadOverrides, FormatOptions, InputFilterOptions, PlaylistMode, QueuePriority,
+};
+use crate::models::DownloadItem;
+use crate::runners::template_context::TemplateContext;
+use crate::scheduling::dispatcher::DispatchRequest;
+use crate::scheduling::download_pipeline::{DownloadRequest, DownloadSender};
+use crate::scheduling::group_state::{ensure_group_running, set_group_priority};
+use tauri::State;
+
+/// Downloads a whole playlist in one yt-dlp run without expanding it first. `items` takes
+/// yt-dlp `-I` ranges like `1:5,8`. Progress for each entry is reported under
+/// `{id}:{index}`, announced by a `media_playlist_item` event.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn media_playlist_download(
+  group_id: String,
+  id: String,
+  url: String,
+  format: FormatOptions,
+  items: Option<String>,
+  overrides: Option<DownloadOverrides>,
+  template_context: TemplateContext,
+  priority: Option<QueuePriority>,
+  pipeline: State<'_, DownloadSender>,
+) -> Result<String, String> {
+  ensure_group_running(&group_id);
+  if let Some(priority) = priority {
+    set_group_priority(&group_id, priority);
+  }
+
+  let mut overrides = overrides.unwrap_or_default();
+  let input_filters = overrides
+    .input_filters
+    .get_or_insert_with(InputFilterOptions::default);
+  input_filters.playlist_mode = Some(PlaylistMode::Playlist);
+  if items.is_some() {
+    input_filters.playlist_items = items;
+  }
+
+  let item = DownloadItem {
+    id,
+    url,
+    format,
+    subtitle_inventory: None,
+    overrides: Some(overrides),
+    template_context,
+    priority: priority.unwrap_or_default(),
+    direct_playlist: true,
+    live: None,
+  };
+
+  pipeline
+    .0
+    .send(DispatchRequest::Pipeline(DownloadRequest::Batch {
+      group_id: group_id.clone(),
+      items: vec![item],
+    }))
+    .map_err(|e| e.to_string())?;
+
+  Ok(group_id)
+}
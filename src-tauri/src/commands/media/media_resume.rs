@@ -0,0 +1,14 @@
+use crate::models::payloads::MediaControlPayload;
+use crate::scheduling::download_pipeline::{resume_download, DownloadSender};
+use tauri::{AppHandle, Emitter, State};
+
+#[tauri::command]
+pub fn media_resume(
+  app: AppHandle,
+  group_id: String,
+  id: String,
+  pipeline: State<'_, DownloadSender>,
+) {
+  resume_download(&pipeline.0, &id);
+  let _ = app.emit("media_resumed", MediaControlPayload { group_id, id });
+}
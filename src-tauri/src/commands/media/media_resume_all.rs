@@ -0,0 +1,15 @@
+use crate::models::payloads::MediaControlPayload;
+use crate::scheduling::download_pipeline::{resume_all_downloads, DownloadSender};
+use crate::DownloadLimiter;
+use tauri::{AppHandle, Emitter, State};
+
+#[tauri::command]
+pub fn media_resume_all(
+  app: AppHandle,
+  limiter: State<'_, DownloadLimiter>,
+  pipeline: State<'_, DownloadSender>,
+) {
+  for (group_id, id) in resume_all_downloads(&pipeline.0, &limiter.0) {
+    let _ = app.emit("media_resumed", MediaControlPayload { group_id, id });
+  }
+}
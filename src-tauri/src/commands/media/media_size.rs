@@ -1,4 +1,4 @@
-use crate::models::download::FormatOptions;
+use crate::models::download::{DownloadOverrides, FormatOptions};
 use crate::scheduling::dispatcher::DispatchRequest;
 use crate::scheduling::fetch_pipeline::{FetchRequest, FetchSender};
 use crate::scheduling::group_state::ensure_group_running;
@@ -10,6 +10,7 @@ pub fn media_size(
   id: String,
   group_id: String,
   format: FormatOptions,
+  overrides: Option<DownloadOverrides>,
   pipeline: State<'_, FetchSender>,
 ) -> Result<String, String> {
   ensure_group_running(&group_id);
@@ -21,6 +22,7 @@ pub fn media_size(
       url,
       id,
       format,
+      overrides: Box::new(overrides),
     }))
     .map_err(|e| e.to_string())?;
 
@@ -0,0 +1,15 @@
+use crate::models::payloads::MediaControlPayload;
+use crate::scheduling::download_pipeline::{stop_download, DownloadSender};
+use tauri::{AppHandle, Emitter, State};
+
+/// Ends a livestream recording, keeping what was recorded so far.
+#[tauri::command]
+pub fn media_stop(
+  app: AppHandle,
+  group_id: String,
+  id: String,
+  pipeline: State<'_, DownloadSender>,
+) {
+  stop_download(&pipeline.0, &app, &id);
+  let _ = app.emit("media_stopping", MediaControlPayload { group_id, id });
+}
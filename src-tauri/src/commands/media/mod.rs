@@ -1,9 +1,25 @@
+pub mod media_cancel;
 pub mod media_download;
 pub mod media_info;
+pub mod media_pause;
+pub mod media_pause_all;
+pub mod media_playlist_download;
 pub mod media_playlist_expand;
+pub mod media_playlist_stream;
+pub mod media_resume;
+pub mod media_resume_all;
 pub mod media_size;
+pub mod media_stop;
 
+pub use media_cancel::*;
 pub use media_download::*;
 pub use media_info::*;
+pub use media_pause::*;
+pub use media_pause_all::*;
+pub use media_playlist_download::*;
 pub use media_playlist_expand::*;
+pub use media_playlist_stream::*;
+pub use media_resume::*;
+pub use media_resume_all::*;
 pub use media_size::*;
+pub use media_stop::*;
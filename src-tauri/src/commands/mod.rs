@@ -1,5 +1,8 @@
+pub mod api;
 pub mod app_ready;
+pub mod archive;
 pub mod binaries;
+pub mod cache;
 pub mod config;
 pub mod group;
 pub mod logging;
@@ -7,12 +10,17 @@ pub mod media;
 pub mod notifications;
 pub mod platform;
 pub mod preferences;
+pub mod queue;
 pub mod shortcuts;
 pub mod stronghold;
+pub mod subscriptions;
 pub mod updater;
 
+pub use api::*;
 pub use app_ready::*;
+pub use archive::*;
 pub use binaries::*;
+pub use cache::*;
 pub use config::*;
 pub use group::*;
 pub use logging::*;
@@ -20,6 +28,8 @@ pub use media::*;
 pub use notifications::*;
 pub use platform::*;
 pub use preferences::*;
+pub use queue::*;
 pub use shortcuts::*;
 pub use stronghold::*;
+pub use subscriptions::*;
 pub use updater::*;
@@ -15,6 +15,7 @@ pub enum NotificationKind {
   VideoReady,
   PlaylistReady,
   DownloadFailed,
+  QueueStatus,
 }
 
 impl NotificationKind {
@@ -28,6 +29,7 @@ impl NotificationKind {
       Self::VideoReady => "videoReady",
       Self::PlaylistReady => "playlistReady",
       Self::DownloadFailed => "downloadFailed",
+      Self::QueueStatus => "queueStatus",
     }
   }
 
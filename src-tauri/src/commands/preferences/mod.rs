@@ -1,7 +1,11 @@
 pub mod preferences_get;
 pub mod preferences_reset;
 pub mod preferences_set;
+pub mod profile_remove;
+pub mod profile_set;
 
 pub use preferences_get::*;
 pub use preferences_reset::*;
 pub use preferences_set::*;
+pub use profile_remove::*;
+pub use profile_set::*;
@@ -0,0 +1,21 @@
+use crate::state::preferences_models::Preferences;
+use crate::SharedPreferences;
+use tauri::{AppHandle, State};
+
+/// Deletes the download profile called `name`.
+#[tauri::command]
+pub fn profile_remove(
+  app: AppHandle,
+  pref: State<'_, SharedPreferences>,
+  name: String,
+) -> Result<Preferences, String> {
+  if !pref.load().profiles.contains_key(&name) {
+    return Err(format!("Unknown profile: {name}"));
+  }
+
+  pref
+    .update(&app, |value| {
+      value.profiles.shift_remove(&name);
+    })
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,23 @@
+use crate::state::preferences_models::{DownloadProfile, Preferences};
+use crate::SharedPreferences;
+use tauri::{AppHandle, State};
+
+/// Saves a download profile under `name`, replacing any profile that already has it.
+#[tauri::command]
+pub fn profile_set(
+  app: AppHandle,
+  pref: State<'_, SharedPreferences>,
+  name: String,
+  profile: DownloadProfile,
+) -> Result<Preferences, String> {
+  let name = name.trim().to_string();
+  if name.is_empty() {
+    return Err("A profile needs a name".into());
+  }
+
+  pref
+    .update(&app, |value| {
+      value.profiles.insert(name, profile);
+    })
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,11 @@
+pub mod queue_move;
+pub mod queue_prioritize;
+pub mod queue_restored_discard;
+pub mod queue_restored_list;
+pub mod queue_status;
+
+pub use queue_move::*;
+pub use queue_prioritize::*;
+pub use queue_restored_discard::*;
+pub use queue_restored_list::*;
+pub use queue_status::*;
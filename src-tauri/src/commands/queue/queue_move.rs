@@ -0,0 +1,20 @@
+use crate::scheduling::dispatcher::DispatchRequest;
+use crate::scheduling::download_pipeline::DownloadSender;
+use tauri::State;
+
+#[tauri::command]
+pub fn queue_move(
+  group_id: String,
+  id: String,
+  index: usize,
+  pipeline: State<'_, DownloadSender>,
+) -> Result<(), String> {
+  pipeline
+    .0
+    .send(DispatchRequest::Move {
+      group_id,
+      id,
+      index,
+    })
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,28 @@
+use crate::models::download::QueuePriority;
+use crate::scheduling::dispatcher::DispatchRequest;
+use crate::scheduling::download_pipeline::DownloadSender;
+use crate::scheduling::group_state::set_group_priority;
+use tauri::State;
+
+/// Sets the priority of a whole group, or of a single pending entry when `id` is given.
+#[tauri::command]
+pub fn queue_prioritize(
+  group_id: String,
+  id: Option<String>,
+  priority: QueuePriority,
+  pipeline: State<'_, DownloadSender>,
+) -> Result<(), String> {
+  let Some(id) = id else {
+    set_group_priority(&group_id, priority);
+    return Ok(());
+  };
+
+  pipeline
+    .0
+    .send(DispatchRequest::Prioritize {
+      group_id,
+      id,
+      priority,
+    })
+    .map_err(|e| e.to_string())
+}
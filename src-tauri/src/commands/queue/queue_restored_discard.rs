@@ -0,0 +1,29 @@
+use crate::scheduling::download_pipeline::{cancel_download, DownloadSender};
+use crate::SharedDownloadQueue;
+use tauri::{AppHandle, State};
+
+/// Cancels restored downloads and drops them from the journal. When `ids` is `None`, every
+/// restored download is discarded.
+#[tauri::command]
+pub fn queue_restored_discard(
+  app: AppHandle,
+  ids: Option<Vec<String>>,
+  download_queue: State<'_, SharedDownloadQueue>,
+  pipeline: State<'_, DownloadSender>,
+) -> Result<(), String> {
+  let discarded: Vec<String> = download_queue
+    .load()
+    .restored()
+    .into_iter()
+    .map(|entry| entry.id)
+    .filter(|id| ids.as_ref().is_none_or(|ids| ids.contains(id)))
+    .collect();
+  for id in &discarded {
+    cancel_download(&pipeline.0, &app, id);
+  }
+
+  download_queue
+    .update(&app, |value| value.discard_restored(ids.as_deref()))
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,8 @@
+use crate::state::download_queue_models::QueuedDownload;
+use crate::SharedDownloadQueue;
+use tauri::State;
+
+#[tauri::command]
+pub fn queue_restored_list(download_queue: State<'_, SharedDownloadQueue>) -> Vec<QueuedDownload> {
+  download_queue.load().restored()
+}
@@ -0,0 +1,9 @@
+use crate::models::payloads::QueueSnapshotPayload;
+use crate::scheduling::queue_status::queue_snapshot;
+use tauri::AppHandle;
+
+/// Reports what the dispatchers and pipelines are doing right now.
+#[tauri::command]
+pub fn queue_status(app: AppHandle) -> QueueSnapshotPayload {
+  queue_snapshot(&app)
+}
@@ -0,0 +1,11 @@
+pub mod subscription_add;
+pub mod subscription_list;
+pub mod subscription_remove;
+pub mod subscription_set;
+pub mod subscription_sync;
+
+pub use subscription_add::*;
+pub use subscription_list::*;
+pub use subscription_remove::*;
+pub use subscription_set::*;
+pub use subscription_sync::*;
@@ -0,0 +1,42 @@
+use crate::models::download::{DownloadOverrides, FormatOptions};
+use crate::state::subscriptions_models::{Retention, Subscription};
+use crate::SharedSubscriptions;
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+/// Subscribes to a channel or playlist. Its title is filled in by the first sync, which only
+/// queues the entries that are already listed when `backfill` is set.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn subscription_add(
+  app: AppHandle,
+  url: String,
+  format: FormatOptions,
+  overrides: Option<DownloadOverrides>,
+  interval_mins: u64,
+  retention: Retention,
+  backfill: Option<bool>,
+  subscriptions: State<'_, SharedSubscriptions>,
+) -> Result<Subscription, String> {
+  let subscription = Subscription {
+    id: Uuid::new_v4().to_string(),
+    url,
+    title: None,
+    format,
+    overrides,
+    interval_mins,
+    retention,
+    enabled: true,
+    last_synced_at: None,
+    last_error: None,
+    seen: Vec::new(),
+    pending_first_sync: true,
+    backfill: backfill.unwrap_or(false),
+    downloads: Vec::new(),
+  };
+
+  subscriptions
+    .update(&app, |value| value.insert(subscription.clone()))
+    .map_err(|e| e.to_string())?;
+  Ok(subscription)
+}
@@ -0,0 +1,16 @@
+use crate::SharedSubscriptions;
+use tauri::{AppHandle, State};
+
+#[tauri::command]
+pub fn subscription_remove(
+  app: AppHandle,
+  id: String,
+  subscriptions: State<'_, SharedSubscriptions>,
+) -> Result<(), String> {
+  subscriptions
+    .update(&app, |value| {
+      value.remove(&id);
+    })
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
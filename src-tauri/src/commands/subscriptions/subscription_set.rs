@@ -0,0 +1,30 @@
+use crate::state::subscriptions_models::{Subscription, SubscriptionPatch};
+use crate::SharedSubscriptions;
+use tauri::{AppHandle, State};
+
+/// Changes the user-editable fields of a subscription, e.g. its interval or retention or to
+/// pause it.
+#[tauri::command]
+pub fn subscription_set(
+  app: AppHandle,
+  id: String,
+  patch: SubscriptionPatch,
+  subscriptions: State<'_, SharedSubscriptions>,
+) -> Result<Subscription, String> {
+  if !subscriptions.load().entries.contains_key(&id) {
+    return Err(format!("Unknown subscription: {id}"));
+  }
+
+  let updated = subscriptions
+    .update(&app, |value| {
+      if let Some(subscription) = value.entries.get_mut(&id) {
+        subscription.apply_patch(patch);
+      }
+    })
+    .map_err(|e| e.to_string())?;
+  updated
+    .entries
+    .get(&id)
+    .cloned()
+    .ok_or_else(|| format!("Unknown subscription: {id}"))
+}
@@ -0,0 +1,8 @@
+use crate::scheduling::subscriptions::sync_subscription;
+use tauri::AppHandle;
+
+/// Syncs a subscription right away, returning the number of newly queued entries.
+#[tauri::command]
+pub async fn subscription_sync(app: AppHandle, id: String) -> Result<usize, String> {
+  sync_subscription(&app, &id).await
+}
@@ -1,4 +1,7 @@
+mod api;
 mod binaries;
+mod cli;
+mod clipboard;
 mod commands;
 mod i18n;
 mod logging;
@@ -13,18 +16,34 @@ mod stronghold;
 mod tray;
 mod window;
 
+use crate::api::api_events::ApiEvents;
+use crate::api::api_server::{sync_api_server, ApiServerState};
 use crate::binaries::binaries_manager::BinariesManager;
 use crate::binaries::binaries_state::BinariesState;
+use crate::cli::cli_handler::{handle_cli_args, handle_launch_args, setup_deep_links, CliState};
+use crate::cli::cli_status::print_status_if_requested;
+use crate::clipboard::clipboard_watch::{sync_clipboard_watch, ClipboardWatchState};
 use crate::commands::*;
 use crate::i18n::I18nManager;
 use crate::logging::LogStoreState;
 use crate::menu::setup_menu;
 use crate::paths::PathsManager;
 use crate::scheduling::concurrency::DynamicSemaphore;
-use crate::scheduling::download_pipeline::{setup_download_dispatcher, DownloadSender};
+use crate::scheduling::dispatch_stats::DispatchStats;
+use crate::scheduling::download_pipeline::{
+  flush_journal, restore_downloads, setup_download_dispatcher, DownloadSender,
+};
 use crate::scheduling::fetch_pipeline::{setup_fetch_dispatcher, FetchSender};
+use crate::scheduling::host_limiter::HostLimiter;
+use crate::scheduling::queue_status::start_snapshot_ticker;
+use crate::scheduling::subscriptions::start_subscription_sync;
 use crate::state::config::ConfigHandle;
+use crate::state::download_archive::DownloadArchive;
+use crate::state::download_queue::DownloadQueueHandle;
+use crate::state::group_starts::GroupStartsHandle;
+use crate::state::metadata_cache::MetadataCache;
 use crate::state::preferences::PreferencesHandle;
+use crate::state::subscriptions::SubscriptionsHandle;
 use crate::tray::{create_tray, TrayState};
 use crate::window::{restore_main_window, setup_close_behaviour, track_main_window};
 use sentry::ClientInitGuard;
@@ -38,6 +57,9 @@ use tracing_subscriber::{fmt, prelude::*};
 
 type SharedConfig = Arc<ConfigHandle>;
 type SharedPreferences = Arc<PreferencesHandle>;
+type SharedDownloadQueue = Arc<DownloadQueueHandle>;
+type SharedSubscriptions = Arc<SubscriptionsHandle>;
+type SharedGroupStarts = Arc<GroupStartsHandle>;
 
 #[derive(Clone)]
 pub struct DownloadLimiter(pub Arc<DynamicSemaphore>);
@@ -45,15 +67,32 @@ pub struct DownloadLimiter(pub Arc<DynamicSemaphore>);
 #[derive(Clone)]
 pub struct FetchLimiter(pub Arc<DynamicSemaphore>);
 
+#[derive(Clone)]
+pub struct DownloadHostLimiter(pub Arc<HostLimiter>);
+
+#[derive(Clone)]
+pub struct FetchHostLimiter(pub Arc<HostLimiter>);
+
+#[derive(Clone)]
+pub struct FetchDispatchStats(pub Arc<DispatchStats>);
+
+#[derive(Clone)]
+pub struct DownloadDispatchStats(pub Arc<DispatchStats>);
+
 /// # Panics
 ///
 /// Will panic if an error occurs during tauri setup.
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+  if print_status_if_requested() {
+    return;
+  }
+
   let app = tauri::Builder::default()
     .plugin(tauri_plugin_notification::init())
     .plugin(tauri_plugin_autostart::Builder::new().build())
     .plugin(tauri_plugin_updater::Builder::new().build())
+    .plugin(tauri_plugin_deep_link::init())
     .plugin(tauri_plugin_store::Builder::new().build())
     .plugin(tauri_plugin_dialog::init())
     .plugin(tauri_plugin_clipboard_manager::init())
@@ -65,8 +104,10 @@ pub fn run() {
       MacosLauncher::LaunchAgent,
       Some(vec!["--auto-start"]),
     ))
-    .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-      reopen_window(app)
+    .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+      if !handle_cli_args(app, &args) {
+        reopen_window(app)
+      }
     }))
     .setup(|app| {
       let handle = app.handle();
@@ -97,6 +138,22 @@ pub fn run() {
       let shared_preferences = Arc::new(preferences_handle);
       handle.manage::<SharedPreferences>(shared_preferences);
 
+      // setup download queue journal
+      let download_queue_handle = DownloadQueueHandle::init(handle)?;
+      handle.manage::<SharedDownloadQueue>(Arc::new(download_queue_handle));
+
+      // setup scheduled group starts, pruned against the journal
+      let group_starts_handle = GroupStartsHandle::init(handle)?;
+      handle.manage::<SharedGroupStarts>(Arc::new(group_starts_handle));
+
+      // setup subscriptions
+      let subscriptions_handle = SubscriptionsHandle::init(handle)?;
+      handle.manage::<SharedSubscriptions>(Arc::new(subscriptions_handle));
+
+      // setup download archive
+      handle.manage(DownloadArchive::new(path_handle.app_dir()));
+      handle.manage(MetadataCache::new(path_handle.app_dir()));
+
       create_main_window(handle, &path_handle)?;
 
       // setup i18n management
@@ -119,21 +176,35 @@ pub fn run() {
       // manage update store
       handle.manage(Mutex::new(UpdateStore::default()));
 
+      // forward events to HTTP API clients
+      handle.manage(ApiEvents::listen(handle));
+
       // manage log store
       handle.manage(LogStoreState::new());
 
       // setup dispatchers
       let cfg_snapshot = handle.state::<SharedConfig>().load();
-      let max_concurrency = cfg_snapshot.performance.max_concurrency;
-      let download_limiter = Arc::new(DynamicSemaphore::new(max_concurrency));
-      let fetch_limiter = Arc::new(DynamicSemaphore::new(max_concurrency));
+      let performance = &cfg_snapshot.performance;
+      let download_limiter = Arc::new(DynamicSemaphore::new(performance.max_concurrency));
+      let fetch_limiter = Arc::new(DynamicSemaphore::new(performance.max_fetch_concurrency));
+      let host_limiter = Arc::new(HostLimiter::new(
+        performance.max_concurrency_per_host.unwrap_or(0),
+      ));
+      let fetch_host_limiter = Arc::new(HostLimiter::new(
+        performance.max_concurrency_per_host.unwrap_or(0),
+      ));
       handle.manage(DownloadLimiter(download_limiter.clone()));
       handle.manage(FetchLimiter(fetch_limiter.clone()));
+      handle.manage(DownloadHostLimiter(host_limiter.clone()));
+      handle.manage(FetchHostLimiter(fetch_host_limiter.clone()));
 
-      let fetch_dispatcher = setup_fetch_dispatcher(handle, fetch_limiter);
+      let fetch_dispatcher = setup_fetch_dispatcher(handle, fetch_limiter, fetch_host_limiter);
       handle.manage(FetchSender(fetch_dispatcher.sender()));
-      let download_dispatcher = setup_download_dispatcher(handle, download_limiter);
+      let download_dispatcher = setup_download_dispatcher(handle, download_limiter, host_limiter);
       handle.manage(DownloadSender(download_dispatcher.sender()));
+      handle.manage(FetchDispatchStats(fetch_dispatcher.stats()));
+      handle.manage(DownloadDispatchStats(download_dispatcher.stats()));
+      start_snapshot_ticker(handle);
 
       // setup binaries
       handle.manage(BinariesState::default());
@@ -152,6 +223,30 @@ pub fn run() {
         stronghold_state::init_on_startup(handle, &state_ref);
       }
 
+      // queue the downloads that were journaled when the app last exited, once the binaries and
+      // the vault with their credentials are set up
+      restore_downloads(handle);
+
+      // the first sync runs right away, so it also waits for the vault to list private channels
+      // and playlists with their credentials
+      start_subscription_sync(handle);
+
+      // setup local HTTP API
+      handle.manage(ApiServerState::default());
+      sync_api_server(handle, &handle.state::<SharedConfig>().load().api);
+
+      // setup background clipboard watcher
+      handle.manage(ClipboardWatchState::default());
+      sync_clipboard_watch(
+        handle,
+        &handle.state::<SharedConfig>().load().clipboard_watch,
+      );
+
+      // run the subcommand or deep link this instance was launched with
+      handle.manage(CliState::default());
+      setup_deep_links(handle);
+      handle_launch_args(handle);
+
       // configure app menu
       setup_menu(handle);
 
@@ -163,10 +258,28 @@ pub fn run() {
     .invoke_handler(tauri::generate_handler![
       app_ready,
       media_size,
+      media_stop,
       media_info,
+      media_playlist_download,
       media_playlist_expand,
+      media_playlist_stream,
       media_download,
+      media_pause,
+      media_resume,
+      media_cancel,
+      media_pause_all,
+      media_resume_all,
       group_cancel,
+      group_schedule,
+      queue_restored_list,
+      queue_restored_discard,
+      queue_move,
+      queue_prioritize,
+      queue_status,
+      archive_list,
+      archive_search,
+      archive_remove,
+      metadata_cache_clear,
       logging_subscribe,
       logging_unsubscribe,
       config_get,
@@ -175,6 +288,8 @@ pub fn run() {
       preferences_get,
       preferences_reset,
       preferences_set,
+      profile_set,
+      profile_remove,
       binaries_check,
       binaries_ensure,
       updater_check,
@@ -185,22 +300,33 @@ pub fn run() {
       stronghold_keys,
       stronghold_get,
       stronghold_set,
+      api_token_get,
+      api_token_regenerate,
+      subscription_add,
+      subscription_list,
+      subscription_remove,
+      subscription_set,
+      subscription_sync,
       get_platform,
       notify,
     ])
     .build(tauri::generate_context!())
     .expect("error while running tauri application");
 
-  app.run(|_app_handle, _event| {
+  app.run(|app_handle, event| {
+    if matches!(event, tauri::RunEvent::Exit) {
+      flush_journal(app_handle);
+    }
+
     #[cfg(target_os = "macos")]
     {
       if let tauri::RunEvent::Reopen {
         has_visible_windows,
         ..
-      } = _event
+      } = event
       {
         if !has_visible_windows {
-          reopen_window(_app_handle);
+          reopen_window(app_handle);
         }
       }
     }
@@ -79,7 +79,19 @@ pub enum TranscodePolicy {
   AllowReencode,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(
+  Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum QueuePriority {
+  Low,
+  #[default]
+  Normal,
+  High,
+  Urgent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DownloadItem {
   pub id: String,
@@ -90,6 +102,24 @@ pub struct DownloadItem {
   #[serde(default)]
   pub overrides: Option<DownloadOverrides>,
   pub template_context: TemplateContext,
+  #[serde(default)]
+  pub priority: QueuePriority,
+  /// Downloads `url` as a playlist in a single yt-dlp run, limited to
+  /// `overrides.input_filters.playlist_items` when set.
+  #[serde(default)]
+  pub direct_playlist: bool,
+  /// Records `url` as a livestream.
+  #[serde(default)]
+  pub live: Option<LiveRecording>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LiveRecording {
+  /// Records from the start of the stream instead of from now, `--live-from-start`.
+  pub from_start: bool,
+  /// Stops the recording gracefully once it ran this long.
+  pub max_duration_secs: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -115,6 +145,26 @@ pub struct DownloadOverrides {
   pub sponsor_block: Option<SponsorBlockOverrides>,
   pub input: Option<InputOverrides>,
   pub input_filters: Option<InputFilterOptions>,
+  pub post_download_hook: Option<PostDownloadHookOverrides>,
+}
+
+/// Hooks are only configured in the settings, a download can turn them off or pick one by name.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostDownloadHookOverrides {
+  pub enabled: Option<bool>,
+  /// Name of a hook in `PostDownloadHookSettings::profiles` to run instead of the default one.
+  pub profile: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum HookFailurePolicy {
+  /// Log the failure and keep the download successful.
+  #[default]
+  Ignore,
+  /// Report the download as failed.
+  FailDownload,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -185,6 +235,8 @@ pub struct NetworkOverrides {
   pub proxy: Option<String>,
   pub impersonate: Option<String>,
   pub extractor_args: Option<String>,
+  /// Fixed `--limit-rate` for this download (e.g. `2M`), excluded from the shared budget.
+  pub limit_rate: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -211,6 +263,8 @@ pub struct SponsorBlockOverrides {
 #[serde(rename_all = "camelCase")]
 pub struct InputOverrides {
   pub prefer_video_in_mixed_links: Option<bool>,
+  pub use_download_archive: Option<bool>,
+  pub wait_for_scheduled: Option<bool>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
@@ -7,12 +7,12 @@ pub mod ytdlp;
 
 pub use download::{DownloadItem, TrackType};
 pub use parsed::{
-  Chapter, MediaCodec, MediaFormat, MediaTrack, ParsedMedia, ParsedPlaylist, ParsedSingleVideo,
-  PlaylistEntry, SubtitleInventory,
+  Chapter, FormatStream, MediaCodec, MediaFormat, MediaTrack, ParsedMedia, ParsedPlaylist,
+  ParsedSingleVideo, PlaylistEntry, SubtitleInventory,
 };
 pub use payloads::{MediaAddPayload, MediaDiagnosticPayload, MediaFatalPayload};
 pub use progress::{
-  MediaDestination, MediaProgress, MediaProgressComplete, MediaProgressStage, ProgressCategory,
-  ProgressEvent, ProgressStage,
+  MediaDestination, MediaLiveProgress, MediaPlaylistItem, MediaProgress, MediaProgressComplete,
+  MediaProgressStage, ProgressCategory, ProgressEvent, ProgressStage,
 };
 pub use ytdlp::YtdlpInfo;
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 #[allow(clippy::large_enum_variant)]
 pub enum ParsedMedia {
@@ -9,6 +9,17 @@ pub enum ParsedMedia {
   Livestream(ParsedLivestream),
 }
 
+impl ParsedMedia {
+  /// Re-assigns the id of the request this media was fetched for.
+  pub fn set_id(&mut self, id: String) {
+    match self {
+      ParsedMedia::Single(single) => single.id = id,
+      ParsedMedia::Playlist(playlist) => playlist.id = id,
+      ParsedMedia::Livestream(livestream) => livestream.id = id,
+    }
+  }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MediaCodec {
@@ -27,6 +38,28 @@ pub struct MediaFormat {
   pub video_codecs: Vec<MediaCodec>,
   pub audio_track_ids: Vec<String>,
   pub video_track_ids: Vec<String>,
+  /// The yt-dlp formats grouped under this entry, used to estimate download sizes locally.
+  #[serde(default)]
+  pub streams: Vec<FormatStream>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatStream {
+  pub format_id: String,
+  pub height: Option<u64>,
+  pub fps: Option<u64>,
+  pub abr: Option<u64>,
+  pub tbr: Option<f64>,
+  pub language: Option<String>,
+  pub audio_channels: Option<u64>,
+  pub ext: Option<String>,
+  pub vcodec: Option<String>,
+  pub acodec: Option<String>,
+  pub has_video: bool,
+  pub has_audio: bool,
+  /// Exact size when yt-dlp reports one, its estimate otherwise.
+  pub filesize: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,6 +114,9 @@ pub struct ParsedSingleVideo {
   pub subtitle_inventory: SubtitleInventory,
   pub chapters: Vec<Chapter>,
   pub filesize: Option<u64>,
+  /// Unix timestamp an upcoming premiere or stream is scheduled to start at.
+  #[serde(default)]
+  pub scheduled_start: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,13 +138,48 @@ pub struct ParsedPlaylist {
 pub struct PlaylistEntry {
   pub video_url: String,
   pub index: usize,
+  /// Id the entry would have in the download archive, when yt-dlp reported one.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub archive_id: Option<String>,
+  #[serde(default)]
+  pub title: Option<String>,
+  #[serde(default)]
+  pub duration: Option<f64>,
+  #[serde(default)]
+  pub thumbnail: Option<String>,
+  #[serde(default)]
+  pub uploader: Option<String>,
+  /// `YYYYMMDD`, as yt-dlp formats it.
+  #[serde(default)]
+  pub upload_date: Option<String>,
+  /// e.g. `public`, `unlisted`, `subscriber_only` or `needs_auth`.
+  #[serde(default)]
+  pub availability: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ParsedLivestream {
   pub id: String,
   pub url: Option<String>,
   pub title: Option<String>,
   pub uploader: Option<String>,
+  #[serde(default)]
+  pub uploader_id: Option<String>,
+  #[serde(default)]
+  pub thumbnail: Option<String>,
+  #[serde(default)]
+  pub description: Option<String>,
+  #[serde(default)]
+  pub extractor: Option<String>,
+  /// Current number of viewers.
+  #[serde(default)]
+  pub viewers: Option<u64>,
+  /// Unix timestamp of when the stream went live.
+  #[serde(default)]
+  pub started_at: Option<i64>,
+  /// Always `true`. `media_add` sends the media without its type, this tells the frontend that
+  /// downloading it records a stream.
+  #[serde(default)]
+  pub is_live: bool,
 }
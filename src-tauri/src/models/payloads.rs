@@ -1,5 +1,6 @@
-use crate::models::download::FormatOptions;
+use crate::models::download::{DownloadItem, FormatOptions};
 use crate::models::error::{DiagnosticEvent, DiagnosticLevel};
+use crate::models::PlaylistEntry;
 use serde::Serialize;
 
 #[derive(Debug, Clone, Serialize)]
@@ -92,6 +93,51 @@ impl MediaFatalPayload {
   }
 }
 
+/// A chunk of a playlist that is being expanded line by line.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistEntriesPayload {
+  pub group_id: String,
+  pub id: String,
+  pub entries: Vec<PlaylistEntry>,
+  /// Entries received so far, including ones skipped because they are archived.
+  pub received: usize,
+  /// Whether this is the last chunk, either because the listing ended or was cancelled.
+  pub done: bool,
+  /// Set on the last chunk when yt-dlp failed after listing some entries.
+  pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaControlPayload {
+  pub group_id: String,
+  pub id: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaRetryPayload {
+  pub group_id: String,
+  pub id: String,
+  pub attempt: u32,
+  pub max_retries: u32,
+  pub delay_ms: u64,
+  pub code: String,
+}
+
+/// Sent while a download waits for its premiere or stream to start.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaCountdownPayload {
+  pub group_id: String,
+  pub id: String,
+  /// Unix timestamp of the scheduled start, when known.
+  pub starts_at: Option<i64>,
+  /// Seconds until the next attempt to start the download.
+  pub seconds_left: u64,
+}
+
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NavigatePayload {
@@ -105,8 +151,164 @@ pub struct AppendLogPayload {
   pub line: String,
 }
 
+/// A group that is fetched without the frontend having created it, e.g. through the HTTP API.
+/// Sent before its `media_add`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaGroupAddPayload {
+  pub group_id: String,
+  pub id: String,
+  pub url: String,
+}
+
+/// A group queued for download without the frontend having fetched it first, e.g. by
+/// `open-video-downloader add --download` or the HTTP API.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadGroupPayload {
+  pub group_id: String,
+  pub title: Option<String>,
+  pub items: Vec<DownloadGroupItem>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadGroupItem {
+  pub id: String,
+  pub url: String,
+  pub title: Option<String>,
+}
+
+impl DownloadGroupPayload {
+  pub fn new(group_id: &str, items: &[DownloadItem]) -> Self {
+    Self {
+      group_id: group_id.to_string(),
+      title: None,
+      items: items
+        .iter()
+        .map(|item| DownloadGroupItem {
+          id: item.id.clone(),
+          url: item.url.clone(),
+          title: None,
+        })
+        .collect(),
+    }
+  }
+}
+
+/// URLs passed to `open-video-downloader add` for the frontend to fetch.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliAddPayload {
+  pub urls: Vec<String>,
+}
+
+/// A URL the background clipboard watcher picked up.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardUrlPayload {
+  pub url: String,
+}
+
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ShortcutPayload {
   pub action: &'static str,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GroupKind {
+  Fetch,
+  Download,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupFailure {
+  pub id: String,
+  pub code: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupCompletePayload {
+  pub group_id: String,
+  pub kind: GroupKind,
+  pub succeeded: usize,
+  pub failed: usize,
+  pub skipped: usize,
+  pub cancelled: usize,
+  pub total_bytes: u64,
+  pub elapsed_ms: u64,
+  pub failures: Vec<GroupFailure>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GroupRunState {
+  Queued,
+  Running,
+  Cancelled,
+  Finished,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueGroupStatus {
+  pub group_id: String,
+  pub state: GroupRunState,
+  pub fetch_pending: usize,
+  pub fetch_in_flight: usize,
+  /// Playlist entries that have not reported back, as counted by the fetch pipeline.
+  pub fetch_remaining: Option<usize>,
+  pub download_pending: usize,
+  pub download_in_flight: usize,
+  pub download_paused: usize,
+  /// Downloads that have not reported back, as counted by the download pipeline.
+  pub download_remaining: Option<usize>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueJobStatus {
+  pub group_id: String,
+  pub id: String,
+  pub kind: GroupKind,
+  pub age_ms: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LimiterStatus {
+  pub max_permits: usize,
+  pub available_permits: usize,
+  pub paused: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSnapshotPayload {
+  pub groups: Vec<QueueGroupStatus>,
+  pub jobs: Vec<QueueJobStatus>,
+  pub fetch_limiter: LimiterStatus,
+  pub download_limiter: LimiterStatus,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionSyncItem {
+  pub id: String,
+  pub url: String,
+  pub title: Option<String>,
+}
+
+/// Sent after a subscription was synced. `group_id` is set when new entries were queued.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionSyncPayload {
+  pub subscription_id: String,
+  pub group_id: Option<String>,
+  pub queued: Vec<SubscriptionSyncItem>,
+  pub error: Option<String>,
+}
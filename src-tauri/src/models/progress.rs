@@ -35,6 +35,32 @@ pub struct MediaProgressStage {
   pub stage: ProgressStage,
 }
 
+/// Progress of a livestream recording, which has no total to report a percentage against.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaLiveProgress {
+  pub id: String,
+  pub group_id: String,
+  /// Seconds since the recording started.
+  pub elapsed_secs: u64,
+  pub downloaded_bytes: Option<u64>,
+  pub speed_bps: Option<f64>,
+}
+
+/// Start of the next item of a job that downloads a whole playlist.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaPlaylistItem {
+  /// Synthetic id the item's events are reported under.
+  pub id: String,
+  pub group_id: String,
+  /// Id of the playlist download job.
+  pub parent_id: String,
+  /// 1-based position among the selected items.
+  pub index: u64,
+  pub count: u64,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MediaProgressComplete {
@@ -47,6 +73,10 @@ pub enum ProgressEvent {
   Destination(MediaDestination),
   Progress(MediaProgress),
   StageChange(MediaProgressStage),
+  PlaylistItem(MediaPlaylistItem),
+  Live(MediaLiveProgress),
+  /// Skipped because it is in the download archive.
+  AlreadyDownloaded(MediaProgressComplete),
 }
 
 #[derive(Debug, Serialize, Clone, PartialEq, Eq, Hash)]
@@ -23,8 +23,15 @@ pub struct YtdlpInfo {
   #[serde(rename = "type_")]
   pub type_: Option<String>,
   pub is_live: Option<bool>,
+  /// e.g. `is_live`, `is_upcoming`, `was_live` or `not_live`.
+  pub live_status: Option<String>,
+  /// When a livestream started or is scheduled to start, as a unix timestamp.
+  pub release_timestamp: Option<i64>,
+  pub concurrent_view_count: Option<i64>,
   pub entries: Option<Vec<YtdlpEntry>>,
   pub webpage_url: Option<String>,
+  /// The URL yt-dlp was given, used to match results of a multi-URL run.
+  pub original_url: Option<String>,
   pub filesize: Option<i64>,
   pub filesize_approx: Option<i64>,
   pub playlist_count: Option<i64>,
@@ -40,8 +47,19 @@ pub struct YtdlpThumbnail {
 
 #[derive(Debug, Deserialize)]
 pub struct YtdlpEntry {
+  pub id: Option<String>,
+  pub ie_key: Option<String>,
   pub url: Option<String>,
   pub webpage_url: Option<String>,
+  pub title: Option<String>,
+  pub duration: Option<f64>,
+  pub thumbnail: Option<String>,
+  pub thumbnails: Option<Vec<YtdlpThumbnail>>,
+  pub uploader: Option<String>,
+  pub channel: Option<String>,
+  pub upload_date: Option<String>,
+  pub timestamp: Option<i64>,
+  pub availability: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -58,6 +76,9 @@ pub struct YtdlpFormat {
   pub ext: Option<String>,
   pub vcodec: Option<String>,
   pub acodec: Option<String>,
+  pub tbr: Option<f64>,
+  pub filesize: Option<i64>,
+  pub filesize_approx: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
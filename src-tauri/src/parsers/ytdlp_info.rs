@@ -11,12 +11,29 @@ pub enum InfoType {
 
 pub fn parse_ytdlp_info(json: &str, id: String) -> Result<ParsedMedia, String> {
   let info: YtdlpInfo = serde_json::from_str(json).map_err(|e| format!("JSON parse error: {e}"))?;
+  Ok(parse_info(info, id))
+}
+
+/// Parses one line of a `-J` run with several URLs, along with the URL it belongs to. yt-dlp
+/// prints `null` for URLs it failed on when errors are ignored, those yield `None`.
+pub fn parse_ytdlp_info_line(json: &str) -> Result<Option<(String, ParsedMedia)>, String> {
+  let info: Option<YtdlpInfo> =
+    serde_json::from_str(json).map_err(|e| format!("JSON parse error: {e}"))?;
+  let Some(mut info) = info else {
+    return Ok(None);
+  };
+  let Some(url) = info.original_url.take() else {
+    return Err("Missing original_url".into());
+  };
+  Ok(Some((url, parse_info(info, String::new()))))
+}
 
-  Ok(match detect_info_type(&info) {
+fn parse_info(info: YtdlpInfo, id: String) -> ParsedMedia {
+  match detect_info_type(&info) {
     InfoType::Livestream => parse_livestream(info, id),
     InfoType::Playlist => parse_playlist(info, id),
     InfoType::Single => parse_single(info, id),
-  })
+  }
 }
 
 pub fn detect_info_type(info: &YtdlpInfo) -> InfoType {
@@ -30,3 +47,22 @@ pub fn detect_info_type(info: &YtdlpInfo) -> InfoType {
     InfoType::Single
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn batch_lines_carry_the_requested_url() {
+    let line = r#"{"id": "abc", "title": "Video", "webpage_url": "https://example.com/watch?v=abc", "original_url": "https://example.com/abc"}"#;
+    let (url, media) = parse_ytdlp_info_line(line).unwrap().unwrap();
+    assert_eq!(url, "https://example.com/abc");
+    assert!(matches!(media, ParsedMedia::Single(_)));
+  }
+
+  #[test]
+  fn failed_urls_are_skipped() {
+    assert!(parse_ytdlp_info_line("null").unwrap().is_none());
+    assert!(parse_ytdlp_info_line(r#"{"id": "abc"}"#).is_err());
+  }
+}
@@ -1,5 +1,6 @@
 use crate::models::parsed::ParsedLivestream;
 use crate::models::{ParsedMedia, YtdlpInfo};
+use crate::parsers::ytdlp_single::common::i64_to_u64;
 
 pub fn parse_livestream(info: YtdlpInfo, id: String) -> ParsedMedia {
   ParsedMedia::Livestream(ParsedLivestream {
@@ -7,5 +8,12 @@ pub fn parse_livestream(info: YtdlpInfo, id: String) -> ParsedMedia {
     url: info.webpage_url,
     title: info.title,
     uploader: info.uploader,
+    uploader_id: info.uploader_id,
+    thumbnail: info.thumbnail,
+    description: info.description,
+    extractor: info.extractor_key,
+    viewers: i64_to_u64(info.concurrent_view_count),
+    started_at: info.release_timestamp,
+    is_live: true,
   })
 }
@@ -1,36 +1,22 @@
+use crate::models::ytdlp::{YtdlpEntry, YtdlpThumbnail};
 use crate::models::{ParsedMedia, ParsedPlaylist, PlaylistEntry, YtdlpInfo};
 use crate::parsers::ytdlp_single::i64_to_u64;
+use crate::state::download_archive::archive_id;
+
+/// yt-dlp `--print` template that writes one flat playlist entry per line as JSON.
+pub const PLAYLIST_ENTRY_TEMPLATE: &str = "%(.{id,ie_key,url,webpage_url,title,duration,thumbnail,\
+thumbnails,uploader,channel,upload_date,timestamp,availability})j";
 
 pub fn parse_playlist(info: YtdlpInfo, id: String) -> ParsedMedia {
-  let mut entries = Vec::new();
-
-  if let Some(items) = info.entries {
-    for (idx, entry) in items.iter().enumerate() {
-      let url = if let Some(u) = &entry.url {
-        u.clone()
-      } else {
-        entry.webpage_url.clone().unwrap_or_default()
-      };
-
-      if !url.is_empty() {
-        entries.push(PlaylistEntry {
-          video_url: url,
-          index: idx,
-        });
-      }
-    }
-  }
+  let entries = info
+    .entries
+    .iter()
+    .flatten()
+    .enumerate()
+    .filter_map(|(index, entry)| playlist_entry(entry, index))
+    .collect();
 
-  let thumbnail = info.thumbnails.as_ref().and_then(|thumbs| {
-    thumbs
-      .iter()
-      .max_by_key(|thumb| {
-        let w = thumb.width.unwrap_or(0);
-        let h = thumb.height.unwrap_or(0);
-        w * h
-      })
-      .and_then(|thumb| thumb.url.clone())
-  });
+  let thumbnail = info.thumbnails.as_deref().and_then(largest_thumbnail);
 
   ParsedMedia::Playlist(ParsedPlaylist {
     id,
@@ -44,3 +30,93 @@ pub fn parse_playlist(info: YtdlpInfo, id: String) -> ParsedMedia {
     entries,
   })
 }
+
+/// Parses one line printed with `PLAYLIST_ENTRY_TEMPLATE`.
+pub fn parse_playlist_entry_line(line: &str, index: usize) -> Option<PlaylistEntry> {
+  let entry: YtdlpEntry = serde_json::from_str(line.trim()).ok()?;
+  playlist_entry(&entry, index)
+}
+
+fn playlist_entry(entry: &YtdlpEntry, index: usize) -> Option<PlaylistEntry> {
+  let url = entry
+    .url
+    .clone()
+    .or_else(|| entry.webpage_url.clone())
+    .filter(|url| !url.is_empty())?;
+  let archive_id = match (&entry.ie_key, &entry.id) {
+    (Some(ie_key), Some(id)) => Some(archive_id(ie_key, id)),
+    _ => None,
+  };
+  let upload_date = entry.upload_date.clone().or_else(|| {
+    let timestamp = entry.timestamp?;
+    let date = chrono::DateTime::from_timestamp(timestamp, 0)?;
+    Some(date.format("%Y%m%d").to_string())
+  });
+  Some(PlaylistEntry {
+    video_url: url,
+    index,
+    archive_id,
+    title: entry.title.clone(),
+    duration: entry.duration,
+    thumbnail: entry
+      .thumbnail
+      .clone()
+      .or_else(|| entry.thumbnails.as_deref().and_then(largest_thumbnail)),
+    uploader: entry.uploader.clone().or_else(|| entry.channel.clone()),
+    upload_date,
+    availability: entry.availability.clone(),
+  })
+}
+
+fn largest_thumbnail(thumbnails: &[YtdlpThumbnail]) -> Option<String> {
+  thumbnails
+    .iter()
+    .max_by_key(|thumb| {
+      let w = thumb.width.unwrap_or(0);
+      let h = thumb.height.unwrap_or(0);
+      w * h
+    })
+    .and_then(|thumb| thumb.url.clone())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_printed_entries() {
+    let line =
+      r#"{"id": "abc", "ie_key": "Youtube", "url": "https://youtu.be/abc", "webpage_url": null}"#;
+    let entry = parse_playlist_entry_line(line, 4).unwrap();
+    assert_eq!(entry.video_url, "https://youtu.be/abc");
+    assert_eq!(entry.index, 4);
+    assert_eq!(entry.archive_id.as_deref(), Some("youtube abc"));
+
+    let fallback =
+      r#"{"id": null, "ie_key": null, "url": null, "webpage_url": "https://example.com/v"}"#;
+    let entry = parse_playlist_entry_line(fallback, 0).unwrap();
+    assert_eq!(entry.video_url, "https://example.com/v");
+    assert_eq!(entry.archive_id, None);
+    assert_eq!(entry.title, None);
+
+    assert!(parse_playlist_entry_line("[download] Downloading item 1 of 3", 0).is_none());
+    assert!(parse_playlist_entry_line(r#"{"id": "x", "url": ""}"#, 0).is_none());
+  }
+
+  #[test]
+  fn parses_entry_details() {
+    let line = r#"{"id": "abc", "ie_key": "Youtube", "url": "https://youtu.be/abc",
+      "title": "A video", "duration": 63.0, "thumbnail": null,
+      "thumbnails": [{"url": "small.jpg", "width": 120, "height": 90},
+                     {"url": "large.jpg", "width": 1280, "height": 720}],
+      "uploader": null, "channel": "A channel", "upload_date": null,
+      "timestamp": 1700000000, "availability": "public"}"#;
+    let entry = parse_playlist_entry_line(line, 0).unwrap();
+    assert_eq!(entry.title.as_deref(), Some("A video"));
+    assert_eq!(entry.duration, Some(63.0));
+    assert_eq!(entry.thumbnail.as_deref(), Some("large.jpg"));
+    assert_eq!(entry.uploader.as_deref(), Some("A channel"));
+    assert_eq!(entry.upload_date.as_deref(), Some("20231114"));
+    assert_eq!(entry.availability.as_deref(), Some("public"));
+  }
+}
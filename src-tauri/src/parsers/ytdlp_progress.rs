@@ -1,16 +1,28 @@
 use crate::models::progress::MediaDestinationPath;
 use crate::models::{
-  MediaDestination, MediaProgress, MediaProgressStage, ProgressCategory, ProgressEvent,
-  ProgressStage, TrackType,
+  MediaDestination, MediaLiveProgress, MediaPlaylistItem, MediaProgress, MediaProgressComplete,
+  MediaProgressStage, ProgressCategory, ProgressEvent, ProgressStage, TrackType,
 };
 use std::path::Path;
+use std::time::Instant;
 
 pub struct YtdlpProgressParser {
   id: String,
   group_id: String,
+  initial_category: ProgressCategory,
   current_category: ProgressCategory,
   current_stage: ProgressStage,
   partial_download_duration_secs: Option<f64>,
+  /// Id of a job that downloads a whole playlist. Its items are reported under
+  /// `playlist_item_id` ids instead.
+  playlist_job_id: Option<String>,
+  /// Set for livestream recordings, which report elapsed time and bytes instead of a percentage.
+  live_started: Option<Instant>,
+}
+
+/// Synthetic id for the `index`th item downloaded by the playlist job `job_id`.
+pub fn playlist_item_id(job_id: &str, index: u64) -> String {
+  format!("{job_id}:{index}")
 }
 
 impl YtdlpProgressParser {
@@ -23,15 +35,51 @@ impl YtdlpProgressParser {
     Self {
       id: id.to_string(),
       group_id: group_id.to_string(),
+      initial_category: initial_category.clone(),
       current_category: initial_category,
       current_stage: ProgressStage::Initializing,
       partial_download_duration_secs,
+      playlist_job_id: None,
+      live_started: None,
     }
   }
 
+  /// Reports progress as `ProgressEvent::Live`, counting elapsed time from now.
+  pub fn with_live_recording(mut self) -> Self {
+    self.live_started = Some(Instant::now());
+    self
+  }
+
+  /// Reports every `Downloading item N of M` as a new item with its own id.
+  pub fn with_playlist_items(mut self) -> Self {
+    self.playlist_job_id = Some(self.id.clone());
+    self
+  }
+
+  /// Whether yt-dlp is still fetching data, as opposed to merging or post-processing files.
+  pub fn is_downloading(&self) -> bool {
+    matches!(
+      self.current_stage,
+      ProgressStage::Initializing | ProgressStage::Downloading
+    )
+  }
+
   pub fn parse_line(&mut self, line: &str) -> Vec<ProgressEvent> {
     let mut evts = Vec::new();
 
+    if let Some(evt) = self.try_playlist_item(line) {
+      evts.push(evt);
+      return evts;
+    }
+
+    if Self::is_archived(line) {
+      evts.push(ProgressEvent::AlreadyDownloaded(MediaProgressComplete {
+        id: self.id.clone(),
+        group_id: self.group_id.clone(),
+      }));
+      return evts;
+    }
+
     if let Some(evt) = self.try_destination(line) {
       evts.push(evt);
     }
@@ -46,6 +94,19 @@ impl YtdlpProgressParser {
       return evts;
     }
 
+    if let Some(progress) = self.try_live_progress(line) {
+      if self.current_stage == ProgressStage::Initializing {
+        self.current_stage = ProgressStage::Downloading;
+        evts.push(ProgressEvent::StageChange(MediaProgressStage {
+          id: self.id.clone(),
+          group_id: self.group_id.clone(),
+          stage: self.current_stage.clone(),
+        }));
+      }
+      evts.push(progress);
+      return evts;
+    }
+
     if let Some(evt) = self.try_progress_update(line) {
       evts.push(evt);
       return evts;
@@ -81,6 +142,27 @@ impl YtdlpProgressParser {
     evts
   }
 
+  fn try_playlist_item(&mut self, line: &str) -> Option<ProgressEvent> {
+    let job_id = self.playlist_job_id.as_deref()?;
+    let rest = line
+      .strip_prefix("[download] Downloading item ")
+      .or_else(|| line.strip_prefix("[download] Downloading video "))?;
+    let (index, count) = rest.trim().split_once(" of ")?;
+    let index: u64 = index.trim().parse().ok()?;
+    let count: u64 = count.trim().parse().ok()?;
+
+    self.id = playlist_item_id(job_id, index);
+    self.current_category = self.initial_category.clone();
+    self.current_stage = ProgressStage::Initializing;
+    Some(ProgressEvent::PlaylistItem(MediaPlaylistItem {
+      id: self.id.clone(),
+      group_id: self.group_id.clone(),
+      parent_id: job_id.to_string(),
+      index,
+      count,
+    }))
+  }
+
   fn try_merger_destination(&self, line: &str) -> Option<ProgressEvent> {
     const PREFIX: &str = "[Merger] Merging formats into";
     if let Some(rest) = line.strip_prefix(PREFIX) {
@@ -152,6 +234,11 @@ impl YtdlpProgressParser {
     }))
   }
 
+  /// yt-dlp skips media that is in the download archive without downloading anything.
+  fn is_archived(line: &str) -> bool {
+    line.starts_with("[download] ") && line.ends_with(" has already been recorded in the archive")
+  }
+
   fn extract_already_downloaded_path(line: &str) -> Option<String> {
     let rest = line.strip_prefix("[download] ")?;
     let (path, _) = rest.split_once(" has already been downloaded")?;
@@ -308,6 +395,31 @@ impl YtdlpProgressParser {
     }))
   }
 
+  /// Reads downloaded bytes and speed from `RAW|` lines, or the size from ffmpeg stats when
+  /// ffmpeg records the stream.
+  fn try_live_progress(&self, line: &str) -> Option<ProgressEvent> {
+    let started = self.live_started?;
+    let (downloaded_bytes, speed_bps) = match line.trim_end().strip_prefix("RAW|") {
+      Some(raw) => {
+        let parts: Vec<&str> = raw.split('|').collect();
+        let field = |index: usize| parts.get(index).map(|value| value.trim());
+        (
+          field(4).and_then(|value| value.parse().ok()),
+          field(2).and_then(|value| value.parse().ok()),
+        )
+      }
+      None => (Some(parse_ffmpeg_size_bytes(line)?), None),
+    };
+
+    Some(ProgressEvent::Live(MediaLiveProgress {
+      id: self.id.clone(),
+      group_id: self.group_id.clone(),
+      elapsed_secs: started.elapsed().as_secs(),
+      downloaded_bytes,
+      speed_bps,
+    }))
+  }
+
   fn try_ffmpeg_progress_update(&self, line: &str) -> Option<ProgressEvent> {
     let total_secs = self.partial_download_duration_secs?;
     if total_secs <= 0.0 {
@@ -402,6 +514,22 @@ fn parse_ffmpeg_progress_time_secs(line: &str) -> Option<f64> {
     .and_then(parse_clock_to_secs)
 }
 
+/// `size=   10240kB` from ffmpeg stats, where `kB` are KiB.
+fn parse_ffmpeg_size_bytes(line: &str) -> Option<u64> {
+  let sample = extract_latest_ffmpeg_progress_sample(line)?;
+  let rest = &sample[sample.find("size=")? + "size=".len()..];
+  let value = rest.split_whitespace().next()?;
+  let digits = value.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+  let multiplier = match value[digits.len()..].to_ascii_lowercase().as_str() {
+    "" | "b" => 1,
+    "kb" | "kib" => 1024,
+    "mb" | "mib" => 1024 * 1024,
+    "gb" | "gib" => 1024 * 1024 * 1024,
+    _ => return None,
+  };
+  digits.parse::<u64>().ok().map(|size| size * multiplier)
+}
+
 fn parse_ffmpeg_speed_factor(line: &str) -> Option<f64> {
   extract_latest_ffmpeg_progress_sample(line)
     .and_then(|sample| extract_token_value(sample, "speed="))
@@ -516,6 +644,56 @@ mod tests {
     )));
   }
 
+  #[test]
+  fn reports_playlist_items_under_synthetic_ids() {
+    let mut single = YtdlpProgressParser::new("job", "group", ProgressCategory::Video, None);
+    assert!(single
+      .parse_line("[download] Downloading item 1 of 3")
+      .is_empty());
+
+    let mut parser =
+      YtdlpProgressParser::new("job", "group", ProgressCategory::Video, None).with_playlist_items();
+    let events = parser.parse_line("[download] Downloading item 2 of 3");
+    let [ProgressEvent::PlaylistItem(item)] = events.as_slice() else {
+      panic!("expected a playlist item event");
+    };
+    assert_eq!(item.id, "job:2");
+    assert_eq!(item.parent_id, "job");
+    assert_eq!((item.index, item.count), (2, 3));
+
+    let events = parser.parse_line("[download] Destination: /tmp/second.mp4");
+    assert!(events.iter().all(|event| match event {
+      ProgressEvent::Destination(destination) => destination.id == "job:2",
+      ProgressEvent::StageChange(stage) => stage.id == "job:2",
+      _ => true,
+    }));
+  }
+
+  #[test]
+  fn reports_media_skipped_by_the_download_archive() {
+    let mut parser = YtdlpProgressParser::new("item", "group", ProgressCategory::Video, None);
+    let events =
+      parser.parse_line("[download] Some title has already been recorded in the archive");
+    let [ProgressEvent::AlreadyDownloaded(item)] = events.as_slice() else {
+      panic!("expected an already downloaded event, got {events:?}");
+    };
+    assert_eq!(item.id, "item");
+    assert!(!parser
+      .parse_line("[download] /tmp/out.mp4 has already been downloaded")
+      .iter()
+      .any(|event| matches!(event, ProgressEvent::AlreadyDownloaded(_))));
+  }
+
+  #[test]
+  fn leaves_the_download_phase_when_merging() {
+    let mut parser = YtdlpProgressParser::new("item", "group", ProgressCategory::Video, None);
+    assert!(parser.is_downloading());
+    parser.parse_line("[download] Destination: /tmp/video.f137.mp4");
+    assert!(parser.is_downloading());
+    parser.parse_line(r#"[Merger] Merging formats into "/tmp/video.mp4""#);
+    assert!(!parser.is_downloading());
+  }
+
   #[test]
   fn detects_remuxing_stage_from_video_remuxer_logs() {
     let mut parser = YtdlpProgressParser::new("item", "group", ProgressCategory::Video, None);
@@ -526,4 +704,31 @@ mod tests {
       ProgressEvent::StageChange(stage) if stage.stage == ProgressStage::Remuxing
     )));
   }
+
+  #[test]
+  fn reports_elapsed_time_and_bytes_for_live_recordings() {
+    let mut parser = YtdlpProgressParser::new("item", "group", ProgressCategory::Video, None)
+      .with_live_recording();
+
+    let events = parser.parse_line("RAW|||524288.5|NA|1048576|NA|NA|12|NA");
+    let Some(ProgressEvent::Live(progress)) = events.last() else {
+      panic!("expected live progress, got {events:?}");
+    };
+    assert_eq!(progress.downloaded_bytes, Some(1_048_576));
+    assert_eq!(progress.speed_bps, Some(524_288.5));
+    assert!(events
+      .iter()
+      .any(|event| matches!(event, ProgressEvent::StageChange(_))));
+
+    let events = parser.parse_line(
+      "frame= 1861 fps= 30 q=-1.0 size=    1433kB time=00:01:02.00 bitrate=405.1kbits/s speed=1x",
+    );
+    let [ProgressEvent::Live(progress)] = events.as_slice() else {
+      panic!("expected live progress, got {events:?}");
+    };
+    assert_eq!(progress.downloaded_bytes, Some(1433 * 1024));
+    assert!(!events
+      .iter()
+      .any(|event| matches!(event, ProgressEvent::Progress(_))));
+  }
 }
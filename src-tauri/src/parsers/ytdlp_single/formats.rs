@@ -4,7 +4,7 @@ use super::tracks::{
   auto_track, has_audio_stream, has_video_stream, is_real_video_codec, sort_tracks, to_track,
 };
 use crate::models::ytdlp::YtdlpFormat;
-use crate::models::{MediaCodec, MediaFormat, MediaTrack};
+use crate::models::{FormatStream, MediaCodec, MediaFormat, MediaTrack};
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
@@ -25,6 +25,7 @@ struct FormatAggregate {
   video_codecs: HashMap<String, MediaCodec>,
   audio_track_ids: HashSet<String>,
   video_track_ids: HashSet<String>,
+  streams: Vec<FormatStream>,
 }
 
 struct FormatGroupInfo {
@@ -83,7 +84,14 @@ pub(super) fn process_formats(formats: &[YtdlpFormat]) -> ProcessedFormats {
           video_codecs: HashMap::new(),
           audio_track_ids: HashSet::new(),
           video_track_ids: HashSet::new(),
+          streams: Vec::new(),
         });
+      entry.streams.push(to_stream(
+        fmt,
+        &group,
+        audio_track.is_some(),
+        video_track.is_some(),
+      ));
 
       if let Some(ac) = &fmt.acodec {
         if ac != "none" {
@@ -127,6 +135,8 @@ pub(super) fn process_formats(formats: &[YtdlpFormat]) -> ProcessedFormats {
       audio_track_ids.sort();
       let mut video_track_ids = agg.video_track_ids.into_iter().collect::<Vec<_>>();
       video_track_ids.sort();
+      let mut streams = agg.streams;
+      streams.sort_by(|a, b| a.format_id.cmp(&b.format_id));
 
       MediaFormat {
         id: agg.id,
@@ -137,6 +147,7 @@ pub(super) fn process_formats(formats: &[YtdlpFormat]) -> ProcessedFormats {
         video_codecs,
         audio_track_ids,
         video_track_ids,
+        streams,
       }
     })
     .collect();
@@ -166,8 +177,10 @@ fn detect_format_group(fmt: &YtdlpFormat) -> Option<FormatGroupInfo> {
     fps_norm = None;
   }
 
+  // Combined formats like YouTube's 18 report an abr as well and are grouped by their video,
+  // their audio bitrate is kept on the stream.
   let (format_id, height, abr, fps) = match (&fmt.format_id, fmt.height, abr_norm, fps_norm) {
-    (Some(format_id), Some(height), None, fps) => {
+    (Some(format_id), Some(height), _, fps) => {
       let fps_rounded = fps.map(|f| f.round() as u64);
       (format_id.clone(), Some(height), None, fps_rounded)
     }
@@ -195,6 +208,39 @@ fn detect_format_group(fmt: &YtdlpFormat) -> Option<FormatGroupInfo> {
   })
 }
 
+fn to_stream(
+  fmt: &YtdlpFormat,
+  group: &FormatGroupInfo,
+  has_audio: bool,
+  has_video: bool,
+) -> FormatStream {
+  let codec = |codec: &Option<String>| {
+    codec
+      .as_deref()
+      .map(str::trim)
+      .filter(|codec| is_real_video_codec(codec))
+      .map(str::to_string)
+  };
+  FormatStream {
+    format_id: group.id.clone(),
+    height: group.height,
+    fps: group.fps,
+    abr: fmt
+      .abr
+      .filter(|abr| *abr > 0.0)
+      .map(|abr| abr.round() as u64),
+    tbr: fmt.tbr,
+    language: fmt.language.clone(),
+    audio_channels: fmt.audio_channels.map(|channels| channels.round() as u64),
+    ext: fmt.ext.clone(),
+    vcodec: codec(&fmt.vcodec),
+    acodec: codec(&fmt.acodec),
+    has_video,
+    has_audio,
+    filesize: i64_to_u64(fmt.filesize.or(fmt.filesize_approx)),
+  }
+}
+
 fn sort_media_format(a: &MediaFormat, b: &MediaFormat) -> Ordering {
   match (a.height, b.height) {
     (Some(ah), Some(bh)) => bh
@@ -302,6 +348,37 @@ mod tests {
     );
   }
 
+  #[test]
+  fn keeps_combined_formats_with_an_audio_bitrate() {
+    let processed = process_formats(&[make_group_format(GroupFormatSpec {
+      format_id: "18",
+      language: Some("en"),
+      language_preference: Some(10),
+      format_note: Some("360p"),
+      format: Some("360p"),
+      audio_channels: Some(2.0),
+      height: Some(360),
+      fps: Some(30.0),
+      abr: Some(96.0),
+      vcodec: Some("avc1.42001E"),
+      acodec: Some("mp4a.40.2"),
+      ext: Some("mp4"),
+    })]);
+
+    let fmt360 = processed
+      .media_formats
+      .iter()
+      .find(|fmt| fmt.height == Some(360))
+      .expect("360p");
+    assert_eq!(fmt360.abr, None);
+    let [stream] = fmt360.streams.as_slice() else {
+      panic!("expected one stream");
+    };
+    assert_eq!(stream.format_id, "18");
+    assert!(stream.has_audio && stream.has_video);
+    assert_eq!(stream.abr, Some(96));
+  }
+
   #[test]
   fn groups_audio_only_formats_by_abr() {
     let processed = process_formats(&[
@@ -369,6 +446,9 @@ mod tests {
       ext: Some("mp4".into()),
       vcodec: Some("none".into()),
       acodec: Some("none".into()),
+      tbr: None,
+      filesize: None,
+      filesize_approx: None,
     }]);
 
     let fmt = processed.media_formats.first().expect("format");
@@ -407,6 +487,9 @@ mod tests {
       ext: Some("mhtml".into()),
       vcodec: Some("none".into()),
       acodec: Some("none".into()),
+      tbr: None,
+      filesize: None,
+      filesize_approx: None,
     }]);
 
     assert!(processed.media_formats.is_empty());
@@ -1,6 +1,6 @@
 mod chapters;
 mod codecs;
-mod common;
+pub(crate) mod common;
 mod formats;
 mod tracks;
 
@@ -77,6 +77,9 @@ pub fn parse_single(info: YtdlpInfo, id: String) -> ParsedMedia {
     formats: media_formats,
     subtitle_inventory,
     chapters: process_chapters(info.chapters.as_deref()),
+    scheduled_start: info
+      .release_timestamp
+      .filter(|_| info.live_status.as_deref() == Some("is_upcoming")),
   })
 }
 
@@ -22,8 +22,12 @@ pub(super) fn base_info(formats: Vec<YtdlpFormat>) -> YtdlpInfo {
     automatic_captions: None,
     type_: Some("video".into()),
     is_live: Some(false),
+    live_status: None,
+    release_timestamp: None,
+    concurrent_view_count: None,
     entries: None,
     webpage_url: Some("https://example.com".into()),
+    original_url: None,
     filesize: None,
     filesize_approx: None,
     playlist_count: None,
@@ -53,6 +57,9 @@ pub(super) fn make_format(
     ext: Some("mp4".into()),
     vcodec: vcodec.map(str::to_string),
     acodec: acodec.map(str::to_string),
+    tbr: None,
+    filesize: None,
+    filesize_approx: None,
   }
 }
 
@@ -85,5 +92,8 @@ pub(super) fn make_group_format(spec: GroupFormatSpec<'_>) -> YtdlpFormat {
     ext: spec.ext.map(str::to_string),
     vcodec: spec.vcodec.map(str::to_string),
     acodec: spec.acodec.map(str::to_string),
+    tbr: None,
+    filesize: None,
+    filesize_approx: None,
   }
 }
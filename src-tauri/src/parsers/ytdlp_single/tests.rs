@@ -84,8 +84,12 @@ fn parse_single_without_formats_adds_auto_tracks() {
     automatic_captions: None,
     type_: Some("video".into()),
     is_live: Some(false),
+    live_status: None,
+    release_timestamp: None,
+    concurrent_view_count: None,
     entries: None,
     webpage_url: Some("https://example.com".into()),
+    original_url: None,
     filesize: None,
     filesize_approx: None,
     playlist_count: None,
@@ -153,3 +157,22 @@ fn parse_single_collects_subtitle_inventory() {
     vec!["en-orig", "fr"]
   );
 }
+
+#[test]
+fn parse_single_keeps_the_start_of_upcoming_premieres() {
+  let mut info = base_info(Vec::new());
+  info.release_timestamp = Some(1_800_000_000);
+  info.live_status = Some("is_upcoming".into());
+  let ParsedMedia::Single(single) = parse_single(info, "id1".into()) else {
+    panic!("expected single");
+  };
+  assert_eq!(single.scheduled_start, Some(1_800_000_000));
+
+  let mut info = base_info(Vec::new());
+  info.release_timestamp = Some(1_700_000_000);
+  info.live_status = Some("was_live".into());
+  let ParsedMedia::Single(single) = parse_single(info, "id1".into()) else {
+    panic!("expected single");
+  };
+  assert_eq!(single.scheduled_start, None);
+}
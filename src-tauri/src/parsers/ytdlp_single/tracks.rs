@@ -158,6 +158,9 @@ mod tests {
       ext: Some("mhtml".into()),
       vcodec: Some("none".into()),
       acodec: Some("none".into()),
+      tbr: None,
+      filesize: None,
+      filesize_approx: None,
     };
 
     assert!(!has_video_stream(&fmt));
@@ -1,7 +1,10 @@
 pub mod override_resolver;
+pub mod post_download_hook;
 pub mod template_context;
 pub mod ytdlp_args;
 pub mod ytdlp_download;
 pub mod ytdlp_info;
+pub mod ytdlp_info_batch;
+pub mod ytdlp_playlist_stream;
 pub mod ytdlp_process;
 pub mod ytdlp_runner;
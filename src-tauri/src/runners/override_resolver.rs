@@ -117,6 +117,8 @@ impl ApplyPatch<SponsorBlockSettings> for SponsorBlockOverrides {
 impl ApplyPatch<InputSettings> for InputOverrides {
   fn apply_to(&self, target: &mut InputSettings) {
     apply_copy_patch!(self, target, prefer_video_in_mixed_links);
+    apply_copy_patch!(self, target, use_download_archive);
+    apply_copy_patch!(self, target, wait_for_scheduled);
   }
 }
 
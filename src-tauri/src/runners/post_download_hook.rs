@@ -0,0 +1,321 @@
+use crate::logging::LogStoreState;
+use crate::models::download::{HookFailurePolicy, PostDownloadHookOverrides};
+use crate::scheduling::download_pipeline::DownloadEntry;
+use crate::state::config_models::{HookProfile, PostDownloadHookSettings};
+use crate::SharedConfig;
+use std::fmt;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::process::Command;
+
+/// Diagnostic code reported for downloads failed by their hook.
+pub const HOOK_FAILED_CODE: &str = "postDownloadHookFailed";
+
+const ENV_PREFIX: &str = "OVD_";
+
+#[derive(Debug)]
+pub enum PostDownloadHookError {
+  SpawnFailed(String),
+  TimedOut(u64),
+  NonZeroExit(Option<i32>),
+}
+
+impl fmt::Display for PostDownloadHookError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::SpawnFailed(e) => write!(f, "Failed to start post-download hook: {e}"),
+      Self::TimedOut(secs) => write!(f, "Post-download hook timed out after {secs}s"),
+      Self::NonZeroExit(Some(code)) => write!(f, "Post-download hook exited with code {code}"),
+      Self::NonZeroExit(None) => write!(f, "Post-download hook was terminated"),
+    }
+  }
+}
+
+impl std::error::Error for PostDownloadHookError {}
+
+/// Runs the configured post-download hook for `entry`, if any.
+///
+/// `exit_status` is the yt-dlp exit code, `0` for successful downloads. Hook failures are logged
+/// to the group log and only returned when the failure policy fails the download.
+pub async fn run_post_download_hook(
+  app: &AppHandle,
+  entry: &DownloadEntry,
+  destination: Option<&Path>,
+  exit_status: i32,
+) -> Result<(), PostDownloadHookError> {
+  let hook = match resolve_hook(
+    &app.state::<SharedConfig>().load().post_download_hook,
+    entry
+      .overrides
+      .as_ref()
+      .and_then(|value| value.post_download_hook.as_ref()),
+  ) {
+    Ok(Some(hook)) => hook,
+    Ok(None) => return Ok(()),
+    Err(name) => {
+      tracing::warn!(download_id = %entry.id, profile = %name, "Unknown post-download hook");
+      append_log(
+        app,
+        &entry.group_id,
+        &format!("[hook] Unknown hook profile {name}"),
+      );
+      return Ok(());
+    }
+  };
+  if exit_status != 0 && !hook.run_on_failure {
+    return Ok(());
+  }
+  let program = hook.command.trim();
+  if program.is_empty() {
+    return Ok(());
+  }
+
+  let result = run_hook(app, entry, &hook, program, destination, exit_status).await;
+  let Err(e) = result else {
+    return Ok(());
+  };
+  tracing::warn!(download_id = %entry.id, error = %e, "Post-download hook failed");
+  append_log(app, &entry.group_id, &format!("[hook] {e}"));
+  match hook.failure_policy {
+    HookFailurePolicy::Ignore => Ok(()),
+    HookFailurePolicy::FailDownload => Err(e),
+  }
+}
+
+/// The hook a download runs, if any: the named profile it picked or the default hook. Fails with
+/// the name of a profile that is not configured.
+fn resolve_hook(
+  settings: &PostDownloadHookSettings,
+  overrides: Option<&PostDownloadHookOverrides>,
+) -> Result<Option<HookProfile>, String> {
+  let profile = overrides.and_then(|value| value.profile.as_deref());
+  let enabled = overrides
+    .and_then(|value| value.enabled)
+    .unwrap_or(settings.enabled || profile.is_some());
+  if !enabled {
+    return Ok(None);
+  }
+  match profile {
+    Some(name) => match settings.profiles.get(name) {
+      Some(profile) => Ok(Some(profile.clone())),
+      None => Err(name.to_string()),
+    },
+    None => Ok(Some(settings.default_profile())),
+  }
+}
+
+async fn run_hook(
+  app: &AppHandle,
+  entry: &DownloadEntry,
+  hook: &HookProfile,
+  program: &str,
+  destination: Option<&Path>,
+  exit_status: i32,
+) -> Result<(), PostDownloadHookError> {
+  let mut command = Command::new(program);
+  command
+    .args(hook_args(hook, entry))
+    .envs(hook_env(entry, destination, exit_status))
+    .stdin(Stdio::null())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .kill_on_drop(true);
+  #[cfg(windows)]
+  command.creation_flags(windows_sys::Win32::System::Threading::CREATE_NO_WINDOW);
+
+  tracing::info!(download_id = %entry.id, program = %program, "Running post-download hook");
+  let child = command
+    .spawn()
+    .map_err(|e| PostDownloadHookError::SpawnFailed(e.to_string()))?;
+  let timeout = Duration::from_secs(hook.timeout_secs.max(1));
+  // Dropping the timed out future kills the child.
+  let output = tokio::time::timeout(timeout, child.wait_with_output())
+    .await
+    .map_err(|_| PostDownloadHookError::TimedOut(hook.timeout_secs))?
+    .map_err(|e| PostDownloadHookError::SpawnFailed(e.to_string()))?;
+
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  for line in stdout.lines().chain(stderr.lines()) {
+    if !line.is_empty() {
+      append_log(app, &entry.group_id, &format!("[hook] {line}"));
+    }
+  }
+
+  if output.status.success() {
+    Ok(())
+  } else {
+    Err(PostDownloadHookError::NonZeroExit(output.status.code()))
+  }
+}
+
+/// The hook's arguments with the download's template values filled in.
+fn hook_args(hook: &HookProfile, entry: &DownloadEntry) -> Vec<String> {
+  hook
+    .args
+    .iter()
+    .map(|arg| entry.template_context.render_template(arg))
+    .collect()
+}
+
+fn append_log(app: &AppHandle, group_id: &str, line: &str) {
+  let log_state = app.state::<LogStoreState>();
+  let mut store = log_state.write();
+  store.append_line(app, group_id, line);
+}
+
+/// Environment passed to the hook: fixed `OVD_*` variables plus every template value,
+/// e.g. `title` as `OVD_TITLE`.
+fn hook_env(
+  entry: &DownloadEntry,
+  destination: Option<&Path>,
+  exit_status: i32,
+) -> Vec<(String, String)> {
+  let mut env: Vec<(String, String)> = entry
+    .template_context
+    .values
+    .iter()
+    .filter_map(|(key, value)| Some((env_key(key)?, value.clone())))
+    .collect();
+  env.sort();
+  let file_path = destination
+    .map(|path| path.to_string_lossy().into_owned())
+    .unwrap_or_default();
+  env.extend([
+    (format!("{ENV_PREFIX}FILE_PATH"), file_path),
+    (format!("{ENV_PREFIX}GROUP_ID"), entry.group_id.clone()),
+    (format!("{ENV_PREFIX}DOWNLOAD_ID"), entry.id.clone()),
+    (format!("{ENV_PREFIX}URL"), entry.url.clone()),
+    (format!("{ENV_PREFIX}EXIT_STATUS"), exit_status.to_string()),
+  ]);
+  env
+}
+
+fn env_key(key: &str) -> Option<String> {
+  let name: String = key
+    .chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() {
+        c.to_ascii_uppercase()
+      } else {
+        '_'
+      }
+    })
+    .collect();
+  let name = name.trim_matches('_');
+  (!name.is_empty()).then(|| format!("{ENV_PREFIX}{name}"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::download::{FormatOptions, QueuePriority};
+  use crate::models::TrackType;
+  use crate::runners::template_context::TemplateContext;
+  use std::collections::HashMap;
+
+  fn entry() -> DownloadEntry {
+    DownloadEntry {
+      group_id: "group".into(),
+      id: "item".into(),
+      url: "https://example.com/watch".into(),
+      format: FormatOptions {
+        track_type: TrackType::Both,
+        abr: None,
+        height: None,
+        fps: None,
+        audio_encoding: None,
+        video_encoding: None,
+        audio_track: None,
+        video_track: None,
+      },
+      subtitle_inventory: None,
+      overrides: None,
+      template_context: TemplateContext {
+        values: HashMap::from([
+          ("title".to_string(), "A video".to_string()),
+          ("playlist_id".to_string(), "PL1".to_string()),
+          ("weird key!".to_string(), "x".to_string()),
+        ]),
+      },
+      priority: QueuePriority::Normal,
+      numbered: false,
+      retries: 0,
+      direct_playlist: false,
+      live: None,
+    }
+  }
+
+  #[test]
+  fn exposes_template_values_and_download_details() {
+    let env: HashMap<String, String> = hook_env(&entry(), Some(Path::new("/tmp/a.mp4")), 0)
+      .into_iter()
+      .collect();
+    assert_eq!(env["OVD_TITLE"], "A video");
+    assert_eq!(env["OVD_PLAYLIST_ID"], "PL1");
+    assert_eq!(env["OVD_WEIRD_KEY"], "x");
+    assert_eq!(env["OVD_FILE_PATH"], "/tmp/a.mp4");
+    assert_eq!(env["OVD_GROUP_ID"], "group");
+    assert_eq!(env["OVD_EXIT_STATUS"], "0");
+  }
+
+  #[test]
+  fn downloads_pick_configured_hooks_by_name() {
+    let mut settings = PostDownloadHookSettings {
+      enabled: true,
+      command: Some("notify".into()),
+      ..PostDownloadHookSettings::default()
+    };
+    settings.profiles.insert(
+      "upload".into(),
+      HookProfile {
+        command: "rclone".into(),
+        ..HookProfile::default()
+      },
+    );
+    let pick = |enabled, profile: Option<&str>| PostDownloadHookOverrides {
+      enabled,
+      profile: profile.map(String::from),
+    };
+
+    let command =
+      |hook: Result<Option<HookProfile>, String>| hook.map(|hook| hook.map(|h| h.command));
+    assert_eq!(
+      command(resolve_hook(&settings, None)),
+      Ok(Some("notify".into()))
+    );
+    assert_eq!(
+      command(resolve_hook(&settings, Some(&pick(None, Some("upload"))))),
+      Ok(Some("rclone".into()))
+    );
+    assert_eq!(
+      command(resolve_hook(&settings, Some(&pick(None, Some("missing"))))),
+      Err("missing".into())
+    );
+    assert_eq!(
+      command(resolve_hook(
+        &settings,
+        Some(&pick(Some(false), Some("upload")))
+      )),
+      Ok(None)
+    );
+  }
+
+  #[test]
+  fn renders_hook_args_from_template_values() {
+    let hook = HookProfile {
+      command: "notify-send".into(),
+      args: vec!["Downloaded".into(), "%(title)s".into()],
+      ..HookProfile::default()
+    };
+    assert_eq!(hook_args(&hook, &entry()), vec!["Downloaded", "A video"]);
+  }
+
+  #[test]
+  fn env_keys_are_sanitized() {
+    assert_eq!(env_key("uploader_id").as_deref(), Some("OVD_UPLOADER_ID"));
+    assert_eq!(env_key("__"), None);
+  }
+}
@@ -1,9 +1,11 @@
 mod format_args;
+mod format_size;
 mod input_filter_args;
 mod location_args;
 mod output_args;
 
 pub use format_args::build_format_args;
+pub use format_size::resolve_format_size;
 pub use input_filter_args::build_input_filter_args;
 pub use location_args::build_location_args;
 pub use output_args::build_output_args;
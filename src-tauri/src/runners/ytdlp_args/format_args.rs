@@ -277,13 +277,13 @@ fn video_sort_fields(
 }
 
 #[derive(Debug, Default)]
-struct TrackPreference {
-  language: Option<String>,
-  channels: Option<u32>,
+pub(super) struct TrackPreference {
+  pub(super) language: Option<String>,
+  pub(super) channels: Option<u32>,
 }
 
 impl TrackPreference {
-  fn parse(track: Option<&String>) -> Self {
+  pub(super) fn parse(track: Option<&String>) -> Self {
     let Some(raw) = track else {
       return Self::default();
     };
@@ -402,7 +402,7 @@ impl FormatSort {
   }
 }
 
-fn language_candidates(language: &str) -> Vec<String> {
+pub(super) fn language_candidates(language: &str) -> Vec<String> {
   let lang = language.trim();
   if lang.is_empty() {
     return Vec::new();
@@ -416,6 +416,6 @@ fn language_candidates(language: &str) -> Vec<String> {
   out
 }
 
-fn non_empty(value: Option<&str>) -> Option<&str> {
+pub(super) fn non_empty(value: Option<&str>) -> Option<&str> {
   value.map(str::trim).filter(|value| !value.is_empty())
 }
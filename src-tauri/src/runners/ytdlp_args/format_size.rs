@@ -0,0 +1,371 @@
+use super::format_args::{language_candidates, non_empty, TrackPreference};
+use crate::models::download::{AudioFormat, FormatOptions, VideoContainer};
+use crate::models::{FormatStream, MediaFormat, TrackType};
+use crate::state::config_models::OutputSettings;
+
+/// Estimates the download size for `format_options` from already fetched formats, picking the
+/// streams the `build_format_args` selector would pick. Returns `None` when nothing matches or a
+/// picked stream has no known size, so the caller can fall back to asking yt-dlp.
+pub fn resolve_format_size(
+  formats: &[MediaFormat],
+  format_options: &FormatOptions,
+  output_settings: &OutputSettings,
+) -> Option<u64> {
+  let streams: Vec<&FormatStream> = formats.iter().flat_map(|format| &format.streams).collect();
+  let prefs = StreamPreferences::new(format_options, output_settings);
+  let selected = match format_options.track_type {
+    TrackType::Audio => select_audio(&streams, &prefs),
+    TrackType::Video => select_video_only(&streams, &prefs),
+    TrackType::Both => select_combined(&streams, &prefs),
+  }?;
+  selected.iter().map(|stream| stream.filesize).sum()
+}
+
+/// The parts of the format selector and `-S` sort order that decide which streams win.
+struct StreamPreferences {
+  audio_languages: Vec<String>,
+  video_languages: Vec<String>,
+  channels: Option<u32>,
+  abr: Option<u32>,
+  height: Option<u32>,
+  fps: Option<u32>,
+  vcodec: Option<String>,
+  acodec: Option<String>,
+  vext: Option<&'static str>,
+  aext: Option<&'static str>,
+}
+
+impl StreamPreferences {
+  fn new(format_options: &FormatOptions, output_settings: &OutputSettings) -> Self {
+    let audio_track = TrackPreference::parse(format_options.audio_track.as_ref());
+    let video_track = TrackPreference::parse(format_options.video_track.as_ref());
+    let languages = |pref: &TrackPreference| {
+      pref
+        .language
+        .as_deref()
+        .map(language_candidates)
+        .unwrap_or_default()
+    };
+    let mp4 = matches!(output_settings.video.container, VideoContainer::Mp4);
+    let audio_only = matches!(format_options.track_type, TrackType::Audio);
+
+    let (acodec, aext) = match non_empty(format_options.audio_encoding.as_deref()) {
+      Some(encoding) => (Some(encoding.to_string()), None),
+      None if audio_only => match output_settings.audio.format {
+        AudioFormat::M4a | AudioFormat::Aac => (Some("aac".into()), Some("m4a")),
+        AudioFormat::Opus | AudioFormat::Flac | AudioFormat::Wav => (Some("opus".into()), None),
+        AudioFormat::Ogg => (Some("vorbis".into()), Some("ogg")),
+        AudioFormat::Mp3 => (None, Some("mp3")),
+      },
+      None if mp4 => (Some("aac".into()), Some("m4a")),
+      None => (None, None),
+    };
+    let vcodec = non_empty(format_options.video_encoding.as_deref())
+      .map(str::to_string)
+      .or_else(|| mp4.then(|| "avc1".into()));
+
+    let audio_languages = languages(&audio_track);
+    let mut video_languages = languages(&video_track);
+    if video_languages.is_empty() {
+      video_languages = audio_languages.clone();
+    }
+
+    Self {
+      audio_languages,
+      video_languages,
+      channels: audio_track.channels,
+      abr: if audio_only { format_options.abr } else { None },
+      height: format_options.height,
+      fps: format_options.fps,
+      vcodec,
+      acodec,
+      vext: mp4.then_some("mp4"),
+      aext,
+    }
+  }
+
+  /// Height and fps bounds in the order the selector relaxes them.
+  fn bounds(&self) -> Vec<(Option<u32>, Option<u32>)> {
+    let mut bounds = Vec::new();
+    if self.height.is_some() || self.fps.is_some() {
+      bounds.push((self.height, self.fps));
+    }
+    if self.height.is_some() && self.fps.is_some() {
+      bounds.push((self.height, None));
+      bounds.push((None, self.fps));
+    }
+    bounds.push((None, None));
+    bounds
+  }
+
+  fn video_rank(&self, stream: &FormatStream) -> (bool, u64, u64, bool, bool, u64) {
+    (
+      matches_language(stream, &self.video_languages),
+      stream.height.unwrap_or(0),
+      stream.fps.unwrap_or(0),
+      matches_codec(stream.vcodec.as_deref(), self.vcodec.as_deref()),
+      self.vext.is_some() && stream.ext.as_deref() == self.vext,
+      bitrate(stream),
+    )
+  }
+
+  fn audio_rank(&self, stream: &FormatStream) -> (bool, bool, u64, bool, bool, u64, u64) {
+    let abr_distance = match (self.abr, stream.abr) {
+      (Some(target), Some(abr)) => u64::from(target).abs_diff(abr),
+      (Some(_), None) => u64::MAX,
+      (None, _) => 0,
+    };
+    (
+      matches_language(stream, &self.audio_languages),
+      self
+        .channels
+        .is_some_and(|channels| stream.audio_channels == Some(u64::from(channels))),
+      u64::MAX - abr_distance,
+      matches_codec(stream.acodec.as_deref(), self.acodec.as_deref()),
+      self.aext.is_some() && stream.ext.as_deref() == self.aext,
+      stream.abr.unwrap_or(0),
+      bitrate(stream),
+    )
+  }
+}
+
+/// `ba/best`
+fn select_audio<'a>(
+  streams: &[&'a FormatStream],
+  prefs: &StreamPreferences,
+) -> Option<Vec<&'a FormatStream>> {
+  best_by(
+    streams,
+    |s| s.has_audio && !s.has_video,
+    |s| prefs.audio_rank(s),
+  )
+  .or_else(|| {
+    best_by(
+      streams,
+      |s| s.has_audio && s.has_video,
+      |s| prefs.audio_rank(s),
+    )
+  })
+  .map(|stream| vec![stream])
+}
+
+/// `bv/b` with the height and fps bounds relaxed one at a time.
+fn select_video_only<'a>(
+  streams: &[&'a FormatStream],
+  prefs: &StreamPreferences,
+) -> Option<Vec<&'a FormatStream>> {
+  let bounds = prefs.bounds();
+  let pick = |audio: bool| {
+    bounds.iter().find_map(|&(height, fps)| {
+      best_by(
+        streams,
+        |s| s.has_video && s.has_audio == audio && within(s, height, fps),
+        |s| prefs.video_rank(s),
+      )
+    })
+  };
+  pick(false)
+    .or_else(|| pick(true))
+    .map(|stream| vec![stream])
+}
+
+/// `bv*+ba/b`, relaxing the height and fps bounds one at a time.
+fn select_combined<'a>(
+  streams: &[&'a FormatStream],
+  prefs: &StreamPreferences,
+) -> Option<Vec<&'a FormatStream>> {
+  let audio = best_by(
+    streams,
+    |s| s.has_audio && !s.has_video,
+    |s| prefs.audio_rank(s),
+  );
+  prefs.bounds().into_iter().find_map(|(height, fps)| {
+    if let Some(audio) = audio {
+      let video = best_by(
+        streams,
+        |s| s.has_video && within(s, height, fps),
+        |s| prefs.video_rank(s),
+      );
+      if let Some(video) = video {
+        return Some(vec![video, audio]);
+      }
+    }
+    best_by(
+      streams,
+      |s| s.has_video && s.has_audio && within(s, height, fps),
+      |s| prefs.video_rank(s),
+    )
+    .map(|stream| vec![stream])
+  })
+}
+
+fn best_by<'a, K: Ord>(
+  streams: &[&'a FormatStream],
+  filter: impl Fn(&FormatStream) -> bool,
+  rank: impl Fn(&FormatStream) -> K,
+) -> Option<&'a FormatStream> {
+  streams
+    .iter()
+    .copied()
+    .filter(|stream| filter(stream))
+    .max_by_key(|stream| rank(stream))
+}
+
+/// yt-dlp drops formats with an unknown value from `[height<=N]` style filters.
+fn within(stream: &FormatStream, height: Option<u32>, fps: Option<u32>) -> bool {
+  let fits = |bound: Option<u32>, value: Option<u64>| {
+    bound.is_none_or(|bound| value.is_some_and(|value| value <= u64::from(bound)))
+  };
+  fits(height, stream.height) && fits(fps, stream.fps)
+}
+
+fn matches_language(stream: &FormatStream, languages: &[String]) -> bool {
+  stream.language.as_deref().is_some_and(|language| {
+    languages
+      .iter()
+      .any(|candidate| candidate.eq_ignore_ascii_case(language))
+  })
+}
+
+fn matches_codec(codec: Option<&str>, wanted: Option<&str>) -> bool {
+  let (Some(codec), Some(wanted)) = (codec, wanted) else {
+    return false;
+  };
+  let codec = codec.to_lowercase();
+  let wanted = wanted.to_lowercase();
+  match wanted.as_str() {
+    "aac" => codec.starts_with("mp4a") || codec.starts_with("aac"),
+    "avc1" | "h264" => codec.starts_with("avc") || codec.starts_with("h264"),
+    _ => codec.starts_with(&wanted),
+  }
+}
+
+fn bitrate(stream: &FormatStream) -> u64 {
+  stream.tbr.map_or(0, |tbr| (tbr * 1000.0) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn stream(format_id: &str, height: Option<u64>, abr: Option<u64>, filesize: u64) -> FormatStream {
+    FormatStream {
+      format_id: format_id.into(),
+      height,
+      fps: height.map(|_| 30),
+      abr,
+      tbr: None,
+      language: None,
+      audio_channels: None,
+      ext: Some(if height.is_some() { "mp4" } else { "m4a" }.into()),
+      vcodec: height.map(|_| "avc1.64001F".into()),
+      acodec: abr.map(|_| "mp4a.40.2".into()),
+      has_video: height.is_some(),
+      has_audio: abr.is_some(),
+      filesize: Some(filesize),
+    }
+  }
+
+  fn format_group(streams: Vec<FormatStream>) -> Vec<MediaFormat> {
+    vec![MediaFormat {
+      id: "group".into(),
+      abr: None,
+      height: None,
+      fps: None,
+      audio_codecs: Vec::new(),
+      video_codecs: Vec::new(),
+      audio_track_ids: Vec::new(),
+      video_track_ids: Vec::new(),
+      streams,
+    }]
+  }
+
+  fn options(track_type: TrackType, height: Option<u32>, abr: Option<u32>) -> FormatOptions {
+    FormatOptions {
+      track_type,
+      abr,
+      height,
+      fps: None,
+      audio_encoding: None,
+      video_encoding: None,
+      audio_track: None,
+      video_track: None,
+    }
+  }
+
+  fn sample() -> Vec<MediaFormat> {
+    format_group(vec![
+      stream("1080", Some(1080), None, 400),
+      stream("720", Some(720), None, 200),
+      stream("18", Some(360), Some(96), 50),
+      stream("low", None, Some(48), 10),
+      stream("high", None, Some(128), 30),
+    ])
+  }
+
+  #[test]
+  fn merges_best_video_with_best_audio() {
+    let settings = OutputSettings::default();
+    let formats = sample();
+    assert_eq!(
+      resolve_format_size(&formats, &options(TrackType::Both, None, None), &settings),
+      Some(430)
+    );
+    assert_eq!(
+      resolve_format_size(
+        &formats,
+        &options(TrackType::Both, Some(720), None),
+        &settings
+      ),
+      Some(230)
+    );
+    assert_eq!(
+      resolve_format_size(
+        &formats,
+        &options(TrackType::Video, Some(720), None),
+        &settings
+      ),
+      Some(200)
+    );
+  }
+
+  #[test]
+  fn picks_audio_closest_to_the_requested_bitrate() {
+    let settings = OutputSettings::default();
+    let formats = sample();
+    assert_eq!(
+      resolve_format_size(
+        &formats,
+        &options(TrackType::Audio, None, Some(50)),
+        &settings
+      ),
+      Some(10)
+    );
+    assert_eq!(
+      resolve_format_size(&formats, &options(TrackType::Audio, None, None), &settings),
+      Some(30)
+    );
+  }
+
+  #[test]
+  fn unknown_sizes_are_not_guessed() {
+    let mut formats = sample();
+    formats[0].streams[4].filesize = None;
+    assert_eq!(
+      resolve_format_size(
+        &formats,
+        &options(TrackType::Both, None, None),
+        &OutputSettings::default()
+      ),
+      None
+    );
+    assert_eq!(
+      resolve_format_size(
+        &format_group(Vec::new()),
+        &options(TrackType::Both, None, None),
+        &OutputSettings::default()
+      ),
+      None
+    );
+  }
+}
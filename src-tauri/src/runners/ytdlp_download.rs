@@ -1,16 +1,43 @@
 use crate::logging::LogStoreState;
 use crate::models::download::DownloadSection;
+use crate::models::error::{DiagnosticEvent, DiagnosticLevel};
+use crate::models::progress::MediaDestinationPath;
 use crate::models::{
   MediaDiagnosticPayload, MediaFatalPayload, MediaProgressComplete, ProgressEvent,
 };
 use crate::parsers::ytdlp_error::{DiagnosticMatcher, YtdlpErrorParser};
 use crate::parsers::ytdlp_progress::{progress_category_for_track_type, YtdlpProgressParser};
-use crate::runners::ytdlp_runner::{YtdlpCommandEvent, YtdlpRunner};
+use crate::runners::post_download_hook::run_post_download_hook;
+use crate::runners::ytdlp_runner::{
+  uses_download_archive, waits_for_scheduled, YtdlpChild, YtdlpCommandEvent, YtdlpRunner,
+};
+use crate::scheduling::dispatcher::DispatchEntry;
 use crate::scheduling::download_pipeline::DownloadEntry;
+use crate::scheduling::fetch_pipeline::scheduled_start;
 use crate::scheduling::group_state::subscribe_group;
+use crate::scheduling::host_limiter::THROTTLING_CODES;
+use crate::scheduling::item_state::{subscribe_item, ItemControl};
+use crate::scheduling::retry::retry_delay;
+use crate::state::download_archive::DownloadArchive;
+use crate::{DownloadHostLimiter, SharedConfig};
 use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::watch;
+use tokio::time::{sleep_until, Instant};
+
+/// Diagnostic codes of premieres and streams that have not started yet.
+pub const SCHEDULED_CODES: [&str; 2] = ["notPremieredYet", "livestreamNotStarted"];
+
+/// How often to check media that is scheduled at an unknown time.
+const SCHEDULED_POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Shortest wait for scheduled media, used once its start time has passed.
+const SCHEDULED_MIN_WAIT: Duration = Duration::from_secs(60);
+
+/// How long a stopped recording may take to finish its file before it is killed.
+const RECORDING_STOP_GRACE: Duration = Duration::from_secs(30);
 
 #[derive(Debug)]
 pub enum YtdlpDownloadError {
@@ -18,7 +45,8 @@ pub enum YtdlpDownloadError {
   InvalidDiagnosticRules(String),
   SpawnFailed(String),
   RunnerError(String),
-  NonZeroExit(i32),
+  /// Exit code and the diagnostic code of the last error yt-dlp reported.
+  NonZeroExit(i32, Option<String>),
   EventStreamEnded,
 }
 
@@ -29,7 +57,7 @@ impl fmt::Display for YtdlpDownloadError {
       Self::InvalidDiagnosticRules(e) => write!(f, "Invalid diagnostic rules: {e}"),
       Self::SpawnFailed(e) => write!(f, "Failed to spawn yt-dlp: {e}"),
       Self::RunnerError(e) => write!(f, "yt-dlp runner error: {e}"),
-      Self::NonZeroExit(code) => write!(f, "yt-dlp exited with code {code}"),
+      Self::NonZeroExit(code, _) => write!(f, "yt-dlp exited with code {code}"),
       Self::EventStreamEnded => write!(f, "yt-dlp event stream ended unexpectedly"),
     }
   }
@@ -37,25 +65,77 @@ impl fmt::Display for YtdlpDownloadError {
 
 impl std::error::Error for YtdlpDownloadError {}
 
+impl YtdlpDownloadError {
+  pub fn diagnostic_code(&self) -> Option<&str> {
+    match self {
+      Self::NonZeroExit(_, code) => code.as_deref(),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadOutcome {
+  /// yt-dlp finished successfully, with the most likely path of the final file.
+  /// `media_complete` is left to the caller, which may still run a post-download hook.
+  Completed {
+    destination: Option<PathBuf>,
+  },
+  /// yt-dlp skipped the media because it is in the download archive.
+  AlreadyDownloaded,
+  Paused,
+  Cancelled,
+  /// Failed with a transient diagnostic code and should be retried after `delay`.
+  Retry {
+    code: String,
+    delay: Duration,
+  },
+  /// Not premiered or live yet, should run again after `delay`.
+  Wait {
+    starts_at: Option<i64>,
+    delay: Duration,
+  },
+}
+
+/// Runs yt-dlp for `entry`, starting it again whenever its bandwidth share changes while it is
+/// still downloading.
 pub async fn run_ytdlp_download(
   app: AppHandle,
   entry: DownloadEntry,
-) -> Result<(), YtdlpDownloadError> {
-  let runner = YtdlpRunner::new(&app)
+  rate_rx: &mut watch::Receiver<Option<u64>>,
+) -> Result<DownloadOutcome, YtdlpDownloadError> {
+  loop {
+    if let Some(outcome) = run_ytdlp_process(&app, &entry, rate_rx).await? {
+      return Ok(outcome);
+    }
+  }
+}
+
+/// One yt-dlp run. `None` means it was stopped to pick up a new rate limit.
+async fn run_ytdlp_process(
+  app: &AppHandle,
+  entry: &DownloadEntry,
+  rate_rx: &mut watch::Receiver<Option<u64>>,
+) -> Result<Option<DownloadOutcome>, YtdlpDownloadError> {
+  let rate_share = *rate_rx.borrow_and_update();
+  let runner = YtdlpRunner::new(app)
     .with_progress_args()
     .with_network_args(entry.overrides.as_ref())
+    .with_rate_limit_args(rate_share, entry.overrides.as_ref())
     .with_auth_args(entry.overrides.as_ref())
     .with_subtitle_args(entry.overrides.as_ref(), entry.subtitle_inventory.as_ref())
     .with_sponsorblock_args(entry.overrides.as_ref())
     .with_format_args(&entry.format, entry.overrides.as_ref())
     .with_input_filter_args(entry.overrides.as_ref())
-    .with_input_args(entry.overrides.as_ref());
+    .with_download_archive_args(entry.overrides.as_ref())
+    .with_input_args(entry.overrides.as_ref())
+    .with_live_args(entry.live.as_ref());
   let output_args = match runner.output_args(&entry.format, entry.overrides.as_ref()) {
     Ok(args) => args,
     Err(err) => {
       emit_internal_fatal(
-        &app,
-        &entry,
+        app,
+        entry,
         format!("Invalid download configuration: {err}"),
         Some(err.clone()),
       );
@@ -76,8 +156,8 @@ pub async fn run_ytdlp_download(
     Ok(matcher) => matcher,
     Err(err) => {
       emit_internal_fatal(
-        &app,
-        &entry,
+        app,
+        entry,
         format!("Invalid diagnostic rules: {err}"),
         Some(err.to_string()),
       );
@@ -100,7 +180,20 @@ pub async fn run_ytdlp_download(
     progress_category_for_track_type(&entry.format.track_type),
     partial_download_duration_secs,
   );
+  if entry.direct_playlist {
+    progress_parser = progress_parser.with_playlist_items();
+  }
+  if entry.live.is_some() {
+    progress_parser = progress_parser.with_live_recording();
+  }
 
+  // Held until this run ends so the archive isn't rewritten while yt-dlp may append to it.
+  let archive = app.state::<DownloadArchive>();
+  let _archive_lease = uses_download_archive(
+    &app.state::<SharedConfig>().load(),
+    entry.overrides.as_ref(),
+  )
+  .then(|| archive.lease());
   let (mut rx, child) = match runner.spawn() {
     Ok(result) => result,
     Err(err) => {
@@ -113,6 +206,21 @@ pub async fn run_ytdlp_download(
     }
   };
   let mut cancel_rx = subscribe_group(&entry.group_id);
+  let mut item_rx = subscribe_item(&entry.id);
+  let mut last_error_code: Option<String> = None;
+  let mut destination: Option<MediaDestinationPath> = None;
+  let mut items = PlaylistItems::default();
+  let live = entry.live.is_some();
+  // Restarting would cut a recording in two or start a playlist job over, so those keep the
+  // limit they started with and a new share only applies to their next run.
+  let restartable = !live && !entry.direct_playlist;
+  let live_deadline = entry
+    .live
+    .as_ref()
+    .and_then(|live| live.max_duration_secs)
+    .map(|secs| Instant::now() + Duration::from_secs(secs));
+  let mut recording_stopped = false;
+  let mut kill_deadline: Option<Instant> = None;
 
   loop {
     tokio::select! {
@@ -124,7 +232,12 @@ pub async fn run_ytdlp_download(
         if is_cancelled_now(&cancel_rx) {
           tracing::info!("Cancelled processing for group_id {}", entry.group_id);
           let _ = child.kill_tree();
-          return Ok(());
+          return Ok(Some(DownloadOutcome::Cancelled));
+        }
+        if let Some(outcome) = stopped_outcome(&item_rx, live) {
+          tracing::info!("Stopped download id={} outcome={:?}", entry.id, outcome);
+          let _ = child.kill_tree();
+          return Ok(Some(outcome));
         }
 
         let log_state = app.state::<LogStoreState>();
@@ -132,26 +245,63 @@ pub async fn run_ytdlp_download(
         match event {
           YtdlpCommandEvent::Stdout(line) => {
             let line_str = String::from_utf8_lossy(&line);
-            store_log_line(&line_str, &entry, log_state, &app);
-            parse_progress_line(&line_str, &mut progress_parser, &app);
-            parse_error_line(&line_str, &error_parser, &app);
+            store_log_line(&line_str, entry, log_state, app);
+            parse_progress_line(
+              &line_str,
+              &mut progress_parser,
+              app,
+              &mut destination,
+              &mut items,
+            );
+            if let Some(event) = parse_error_line(&line_str, &error_parser, app, &items) {
+              track_diagnostic(app, entry, &event, &mut last_error_code, &mut items);
+            }
+            report_finished_items(app, entry, &mut items).await;
           }
           YtdlpCommandEvent::Stderr(line) => {
             let line_str = String::from_utf8_lossy(&line);
-            store_log_line(&line_str, &entry, log_state, &app);
-            parse_progress_line(&line_str, &mut progress_parser, &app);
-            parse_error_line(&line_str, &error_parser, &app);
+            store_log_line(&line_str, entry, log_state, app);
+            parse_progress_line(
+              &line_str,
+              &mut progress_parser,
+              app,
+              &mut destination,
+              &mut items,
+            );
+            if let Some(event) = parse_error_line(&line_str, &error_parser, app, &items) {
+              track_diagnostic(app, entry, &event, &mut last_error_code, &mut items);
+            }
+            report_finished_items(app, entry, &mut items).await;
           }
           YtdlpCommandEvent::Terminated(term) => {
+            // yt-dlp exits with an error when interrupted, but the recording is complete.
+            if recording_stopped {
+              finalize_recording(destination.as_ref());
+              return Ok(Some(DownloadOutcome::Completed {
+                destination: destination.map(|d| PathBuf::from(d.path)),
+              }));
+            }
+            // After a failed exit the last item only counts when its file made it to disk.
+            let confirmed = term.code == Some(0)
+              || destination
+                .as_ref()
+                .is_some_and(|d| Path::new(&d.path).exists());
+            items.finish(destination.as_ref(), confirmed);
+            report_finished_items(app, entry, &mut items).await;
+            if term.code == Some(0) && items.archived {
+              return Ok(Some(DownloadOutcome::AlreadyDownloaded));
+            }
             if term.code == Some(0) {
-              let _ = app.emit(
-                "media_complete",
-                MediaProgressComplete {
-                  id: entry.id.clone(),
-                  group_id: entry.group_id.clone(),
-                },
-              );
-              return Ok(());
+              return Ok(Some(DownloadOutcome::Completed {
+                destination: destination.map(|d| PathBuf::from(d.path)),
+              }));
+            }
+
+            if let Some(outcome) = wait_outcome(app, entry, last_error_code.as_deref()) {
+              return Ok(Some(outcome));
+            }
+            if let Some(outcome) = retry_outcome(app, entry, last_error_code.as_deref()) {
+              return Ok(Some(outcome));
             }
 
             let exit = term.code.unwrap_or(1);
@@ -164,11 +314,11 @@ pub async fn run_ytdlp_download(
                 format!("Download failed for group {}", entry.group_id),
               ),
             );
-            return Err(YtdlpDownloadError::NonZeroExit(exit));
+            return Err(YtdlpDownloadError::NonZeroExit(exit, last_error_code));
           }
           YtdlpCommandEvent::Error(err) => {
             let msg = format!("Download failed for group {}: {}", entry.group_id, err);
-            emit_internal_fatal(&app, &entry, msg.clone(), Some(err.clone()));
+            emit_internal_fatal(app, entry, msg.clone(), Some(err.clone()));
 
             return Err(YtdlpDownloadError::RunnerError(err));
           }
@@ -178,15 +328,51 @@ pub async fn run_ytdlp_download(
         if is_cancelled_now(&cancel_rx) {
           tracing::info!("Cancelled processing for group_id {}", entry.group_id);
           let _ = child.kill_tree();
-          return Ok(());
+          return Ok(Some(DownloadOutcome::Cancelled));
+        }
+      }
+      _ = item_rx.changed() => {
+        if live && *item_rx.borrow() == ItemControl::Stop && !recording_stopped {
+          tracing::info!("Stopping recording id={}", entry.id);
+          recording_stopped = true;
+          kill_deadline = Some(stop_recording(&child));
+          continue;
+        }
+        if let Some(outcome) = stopped_outcome(&item_rx, live) {
+          tracing::info!("Stopped download id={} outcome={:?}", entry.id, outcome);
+          // Killing yt-dlp leaves the .part file behind, so a resumed run continues from it.
+          let _ = child.kill_tree();
+          return Ok(Some(outcome));
         }
       }
+      _ = sleep_until(live_deadline.unwrap_or_else(Instant::now)),
+        if live_deadline.is_some() && !recording_stopped => {
+        tracing::info!("Recording id={} reached its maximum duration", entry.id);
+        recording_stopped = true;
+        kill_deadline = Some(stop_recording(&child));
+      }
+      _ = sleep_until(kill_deadline.unwrap_or_else(Instant::now)), if kill_deadline.is_some() => {
+        tracing::warn!("Recording id={} did not stop in time, killing it", entry.id);
+        let _ = child.kill_tree();
+        kill_deadline = None;
+      }
+      Ok(()) = rate_rx.changed(), if restartable => {
+        // Killing a merge or post-processor would throw away finished work, and the rate
+        // no longer matters once the data is downloaded.
+        if !progress_parser.is_downloading() {
+          tracing::debug!("Keeping the rate limit of download id={} past its download", entry.id);
+          continue;
+        }
+        tracing::info!("Restarting download id={} with a new rate limit", entry.id);
+        let _ = child.kill_tree();
+        return Ok(None);
+      }
     }
   }
 
   emit_internal_fatal(
-    &app,
-    &entry,
+    app,
+    entry,
     "yt-dlp event stream ended unexpectedly".to_string(),
     None,
   );
@@ -209,6 +395,38 @@ fn is_cancelled_now(cancel_rx: &watch::Receiver<bool>) -> bool {
   !*cancel_rx.borrow()
 }
 
+/// Outcome of a pause or cancel request. Stopping only applies to recordings, which end
+/// gracefully instead of being killed.
+fn stopped_outcome(item_rx: &watch::Receiver<ItemControl>, live: bool) -> Option<DownloadOutcome> {
+  match *item_rx.borrow() {
+    ItemControl::Run => None,
+    ItemControl::Stop if live => None,
+    ItemControl::Pause => Some(DownloadOutcome::Paused),
+    ItemControl::Cancel | ItemControl::Stop => Some(DownloadOutcome::Cancelled),
+  }
+}
+
+/// Interrupts a recording and returns when it should be killed if it has not exited by then.
+fn stop_recording(child: &YtdlpChild) -> Instant {
+  child.interrupt();
+  Instant::now() + RECORDING_STOP_GRACE
+}
+
+/// yt-dlp leaves an interrupted recording behind as `.part`, which is playable MPEG-TS.
+fn finalize_recording(destination: Option<&MediaDestinationPath>) {
+  let Some(destination) = destination else {
+    return;
+  };
+  let path = PathBuf::from(&destination.path);
+  let part = PathBuf::from(format!("{}.part", destination.path));
+  if path.exists() || !part.exists() {
+    return;
+  }
+  if let Err(e) = std::fs::rename(&part, &path) {
+    tracing::warn!(path = %part.display(), error = %e, "Failed to finalize recording");
+  }
+}
+
 fn store_log_line(
   line: &str,
   entry: &DownloadEntry,
@@ -222,12 +440,138 @@ fn store_log_line(
   store.append_line(app, &entry.group_id, line);
 }
 
-fn parse_progress_line(line: &str, progress_parser: &mut YtdlpProgressParser, app: &AppHandle) {
+/// The item a playlist job is downloading, see `DownloadEntry::direct_playlist`.
+#[derive(Default)]
+struct PlaylistItems {
+  current: Option<String>,
+  failed: bool,
+  /// The current item, or a download without items, was skipped by the download archive.
+  archived: bool,
+  /// Items yt-dlp is done with that were not reported yet, see `report_finished_items`.
+  finished: Vec<FinishedItem>,
+}
+
+struct FinishedItem {
+  id: String,
+  destination: Option<PathBuf>,
+  end: ItemEnd,
+}
+
+enum ItemEnd {
+  Completed,
+  AlreadyDownloaded,
+  Failed,
+}
+
+impl PlaylistItems {
+  /// yt-dlp only moves on to the next item once it is done with the current one.
+  fn start(&mut self, id: String, destination: Option<&MediaDestinationPath>) {
+    self.finish(destination, true);
+    self.current = Some(id);
+  }
+
+  fn archive(&mut self) {
+    self.archived = true;
+  }
+
+  fn fail(&mut self) {
+    if self.current.is_some() {
+      self.failed = true;
+    }
+  }
+
+  /// Ends the current item. It only counts as complete when yt-dlp `confirmed` it and reported no
+  /// error for it.
+  fn finish(&mut self, destination: Option<&MediaDestinationPath>, confirmed: bool) {
+    let Some(id) = self.current.take() else {
+      return;
+    };
+    let archived = std::mem::take(&mut self.archived);
+    let end = if std::mem::take(&mut self.failed) || !confirmed {
+      ItemEnd::Failed
+    } else if archived {
+      ItemEnd::AlreadyDownloaded
+    } else {
+      ItemEnd::Completed
+    };
+    self.finished.push(FinishedItem {
+      id,
+      destination: destination.map(|d| PathBuf::from(&d.path)),
+      end,
+    });
+  }
+}
+
+/// Reports the items a playlist job is done with, running the post-download hook for each of
+/// them with the item's own file.
+async fn report_finished_items(app: &AppHandle, entry: &DownloadEntry, items: &mut PlaylistItems) {
+  for item in std::mem::take(&mut items.finished) {
+    let item_entry = DownloadEntry {
+      id: item.id.clone(),
+      ..entry.clone()
+    };
+    let complete = MediaProgressComplete {
+      id: item.id.clone(),
+      group_id: entry.group_id.clone(),
+    };
+    match item.end {
+      ItemEnd::AlreadyDownloaded => {
+        let _ = app.emit("media_already_downloaded", complete);
+      }
+      ItemEnd::Failed => {
+        // The item already failed, so the hook's failure policy doesn't matter here.
+        let _ = run_post_download_hook(app, &item_entry, None, 1).await;
+        let _ = app.emit(
+          "media_fatal",
+          MediaFatalPayload::with_exit(
+            entry.group_id.clone(),
+            item.id,
+            1,
+            "Playlist item failed".to_string(),
+          ),
+        );
+      }
+      ItemEnd::Completed => {
+        match run_post_download_hook(app, &item_entry, item.destination.as_deref(), 0).await {
+          Ok(()) => {
+            let _ = app.emit("media_complete", complete);
+          }
+          Err(e) => {
+            let _ = app.emit(
+              "media_fatal",
+              MediaFatalPayload::internal(entry.group_id.clone(), item.id, e.to_string(), None),
+            );
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Emits progress events and keeps the most confident destination seen so far.
+fn parse_progress_line(
+  line: &str,
+  progress_parser: &mut YtdlpProgressParser,
+  app: &AppHandle,
+  best_destination: &mut Option<MediaDestinationPath>,
+  items: &mut PlaylistItems,
+) {
   let progress_events = progress_parser.parse_line(line);
 
   for progress_event in progress_events {
     match progress_event {
+      ProgressEvent::PlaylistItem(item) => {
+        items.start(item.id.clone(), best_destination.as_ref());
+        *best_destination = None;
+        app.emit("media_playlist_item", item).ok();
+      }
       ProgressEvent::Destination(destination) => {
+        let is_better = best_destination
+          .as_ref()
+          .is_none_or(|best| destination.destination.confidence >= best.confidence);
+        if is_better {
+          *best_destination = Some(destination.destination.clone());
+        }
         app.emit("media_destination", destination).ok();
       }
       ProgressEvent::Progress(progress) => {
@@ -236,19 +580,96 @@ fn parse_progress_line(line: &str, progress_parser: &mut YtdlpProgressParser, ap
       ProgressEvent::StageChange(progress) => {
         app.emit("media_progress_stage", progress).ok();
       }
+      ProgressEvent::Live(progress) => {
+        app.emit("media_live_progress", progress).ok();
+      }
+      ProgressEvent::AlreadyDownloaded(_) => items.archive(),
+    }
+  }
+}
+
+fn parse_error_line(
+  line: &str,
+  error_parser: &YtdlpErrorParser,
+  app: &AppHandle,
+  items: &PlaylistItems,
+) -> Option<DiagnosticEvent> {
+  let event = error_parser.parse_line(line)?;
+  let mut payload = MediaDiagnosticPayload::from_diagnostic_event(event.clone());
+  if let Some(id) = &items.current {
+    payload.id = id.clone();
+  }
+  app.emit("media_diagnostic", payload).ok();
+  Some(event)
+}
+
+/// Remembers the last error code for the retry decision and slows down the site on throttling.
+fn track_diagnostic(
+  app: &AppHandle,
+  entry: &DownloadEntry,
+  event: &DiagnosticEvent,
+  last_error_code: &mut Option<String>,
+  items: &mut PlaylistItems,
+) {
+  if THROTTLING_CODES.contains(&event.code.as_str()) {
+    if let (Some(key), Some(limiter)) = (entry.limit_key(), app.try_state::<DownloadHostLimiter>())
+    {
+      limiter.0.throttle(&key);
     }
   }
+  if matches!(event.level, DiagnosticLevel::Error) {
+    *last_error_code = Some(event.code.clone());
+    items.fail();
+  }
 }
 
-fn parse_error_line(line: &str, error_parser: &YtdlpErrorParser, app: &AppHandle) {
-  if let Some(event) = error_parser.parse_line(line) {
-    app
-      .emit(
-        "media_diagnostic",
-        MediaDiagnosticPayload::from_diagnostic_event(event),
-      )
-      .ok();
+fn retry_outcome(
+  app: &AppHandle,
+  entry: &DownloadEntry,
+  error_code: Option<&str>,
+) -> Option<DownloadOutcome> {
+  let code = error_code?;
+  let config = app.state::<SharedConfig>().load();
+  let delay = retry_delay(&config.retry, code, entry.retries)?;
+  Some(DownloadOutcome::Retry {
+    code: code.to_string(),
+    delay,
+  })
+}
+
+/// Keeps premieres and streams that have not started queued when waiting for them is enabled.
+fn wait_outcome(
+  app: &AppHandle,
+  entry: &DownloadEntry,
+  error_code: Option<&str>,
+) -> Option<DownloadOutcome> {
+  let code = error_code?;
+  if !SCHEDULED_CODES.contains(&code) {
+    return None;
+  }
+  let config = app.state::<SharedConfig>().load();
+  if !waits_for_scheduled(&config, entry.overrides.as_ref()) {
+    return None;
   }
+  let starts_at = scheduled_start(&entry.url, entry.overrides.as_ref());
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs() as i64)
+    .unwrap_or(0);
+  Some(DownloadOutcome::Wait {
+    starts_at,
+    delay: scheduled_wait(starts_at, now),
+  })
+}
+
+/// Waits until the scheduled start, then checks every minute since streams often start late.
+fn scheduled_wait(starts_at: Option<i64>, now: i64) -> Duration {
+  let wait = match starts_at {
+    Some(starts_at) if starts_at > now => Duration::from_secs((starts_at - now) as u64),
+    Some(_) => SCHEDULED_MIN_WAIT,
+    None => SCHEDULED_POLL_INTERVAL,
+  };
+  wait.max(SCHEDULED_MIN_WAIT)
 }
 
 fn download_section_duration_secs(section: &DownloadSection) -> Option<f64> {
@@ -277,3 +698,45 @@ fn parse_clock_secs(value: &str) -> Option<f64> {
 
   Some(total)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn ends(items: &PlaylistItems) -> Vec<(&str, &'static str)> {
+    items
+      .finished
+      .iter()
+      .map(|item| {
+        let end = match item.end {
+          ItemEnd::Completed => "completed",
+          ItemEnd::AlreadyDownloaded => "archived",
+          ItemEnd::Failed => "failed",
+        };
+        (item.id.as_str(), end)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn playlist_items_complete_only_when_confirmed() {
+    let mut items = PlaylistItems::default();
+    items.start("job:1".into(), None);
+    items.start("job:2".into(), None);
+    items.fail();
+    items.start("job:3".into(), None);
+    items.archive();
+    items.start("job:4".into(), None);
+    items.finish(None, false);
+
+    assert_eq!(
+      ends(&items),
+      vec![
+        ("job:1", "completed"),
+        ("job:2", "failed"),
+        ("job:3", "archived"),
+        ("job:4", "failed"),
+      ]
+    );
+  }
+}
@@ -1,18 +1,33 @@
 use crate::logging::LogStoreState;
 use crate::models::download::{DownloadOverrides, FormatOptions};
-use crate::models::{MediaDiagnosticPayload, MediaFatalPayload, ParsedMedia, TrackType};
+use crate::models::error::DiagnosticLevel;
+use crate::models::{
+  MediaDiagnosticPayload, MediaFatalPayload, ParsedMedia, PlaylistEntry, TrackType,
+};
 use crate::parsers::ytdlp_error::{DiagnosticMatcher, YtdlpErrorParser};
 use crate::parsers::ytdlp_info::parse_ytdlp_info;
-use crate::runners::ytdlp_runner::YtdlpRunner;
+use crate::runners::ytdlp_runner::{uses_download_archive, YtdlpRunner};
+use crate::state::download_archive::DownloadArchive;
+use crate::state::metadata_cache::{cache_key, MetadataCache};
+use crate::SharedConfig;
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::fmt;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 
 #[derive(Debug)]
 pub enum YtdlpInfoFetchError {
   InvalidDiagnosticRules(String),
   RunnerFailed(String),
-  NonZeroExit(i32),
+  /// Exit code and the diagnostic code of the last error yt-dlp reported.
+  NonZeroExit(i32, Option<String>),
+  /// A listing that failed after `received` entries, with the exit and diagnostic code.
+  Incomplete {
+    received: usize,
+    exit: i32,
+    code: Option<String>,
+  },
   ParseFailed(String),
 }
 
@@ -21,7 +36,11 @@ impl fmt::Display for YtdlpInfoFetchError {
     match self {
       Self::InvalidDiagnosticRules(e) => write!(f, "Invalid diagnostic rules: {e}"),
       Self::RunnerFailed(e) => write!(f, "yt-dlp invocation failed: {e}"),
-      Self::NonZeroExit(code) => write!(f, "yt-dlp exited with code {code}"),
+      Self::NonZeroExit(code, _) => write!(f, "yt-dlp exited with code {code}"),
+      Self::Incomplete { received, exit, .. } => write!(
+        f,
+        "Playlist listing stopped after {received} entries, yt-dlp exited with code {exit}"
+      ),
       Self::ParseFailed(e) => write!(f, "Failed to parse yt-dlp output: {e}"),
     }
   }
@@ -29,6 +48,15 @@ impl fmt::Display for YtdlpInfoFetchError {
 
 impl std::error::Error for YtdlpInfoFetchError {}
 
+impl YtdlpInfoFetchError {
+  pub fn diagnostic_code(&self) -> Option<&str> {
+    match self {
+      Self::NonZeroExit(_, code) | Self::Incomplete { code, .. } => code.as_deref(),
+      _ => None,
+    }
+  }
+}
+
 pub async fn run_ytdlp_info_fetch(
   app: &AppHandle,
   id: String,
@@ -39,24 +67,21 @@ pub async fn run_ytdlp_info_fetch(
 ) -> Result<Option<ParsedMedia>, YtdlpInfoFetchError> {
   static RULES_JSON: &str = include_str!("../diagnostic_rules.json");
 
+  let cache_slot = InfoCacheSlot::new(app, url, format.as_ref(), overrides.as_ref());
+  if let Some(mut media) = cache_slot.as_ref().and_then(|slot| slot.get(app)) {
+    tracing::debug!(url = %url, "Serving media info from the metadata cache");
+    media.set_id(id);
+    skip_archived_entries(app, &mut media, overrides.as_ref());
+    return Ok(Some(media));
+  }
+
   let runner = YtdlpRunner::new(app)
-    .with_format_args(
-      &format.unwrap_or(FormatOptions {
-        track_type: TrackType::Both,
-        abr: None,
-        height: None,
-        fps: None,
-        audio_encoding: None,
-        video_encoding: None,
-        audio_track: None,
-        video_track: None,
-      }),
-      None,
-    )
+    .with_format_args(&info_format_options(format), None)
     .with_input_args(overrides.as_ref())
     .with_input_filter_args(overrides.as_ref())
     .with_auth_args(overrides.as_ref())
     .with_network_args(overrides.as_ref())
+    .with_scheduled_args(overrides.as_ref())
     .with_args(["-J", "--flat-playlist"])
     .with_url(url);
 
@@ -153,11 +178,25 @@ pub async fn run_ytdlp_info_fetch(
         stderr_snippet.into_owned(),
       ),
     );
-    return Err(YtdlpInfoFetchError::NonZeroExit(status_code));
+    let last_error_code = parsed_events
+      .iter()
+      .rev()
+      .find(|event| matches!(event.level, DiagnosticLevel::Error))
+      .map(|event| event.code.clone());
+    return Err(YtdlpInfoFetchError::NonZeroExit(
+      status_code,
+      last_error_code,
+    ));
   }
 
   match parse_ytdlp_info(&stdout_text, id.clone()) {
-    Ok(media) => Ok(Some(media)),
+    Ok(mut media) => {
+      if let Some(slot) = &cache_slot {
+        slot.insert(app, &media);
+      }
+      skip_archived_entries(app, &mut media, overrides.as_ref());
+      Ok(Some(media))
+    }
     Err(e) => {
       let _ = app.emit(
         "media_fatal",
@@ -172,3 +211,147 @@ pub async fn run_ytdlp_info_fetch(
     }
   }
 }
+
+/// Lists `url` without going through the metadata cache or emitting media events, for
+/// background syncs that need the current entries. yt-dlp's output is logged under `log_id`.
+pub async fn run_ytdlp_listing(
+  app: &AppHandle,
+  log_id: &str,
+  url: &str,
+  overrides: Option<&DownloadOverrides>,
+) -> Result<ParsedMedia, YtdlpInfoFetchError> {
+  let output = YtdlpRunner::new(app)
+    .with_format_args(&info_format_options(None), None)
+    .with_input_args(overrides)
+    .with_input_filter_args(overrides)
+    .with_auth_args(overrides)
+    .with_network_args(overrides)
+    .with_args(["-J", "--flat-playlist"])
+    .with_url(url)
+    .output()
+    .await
+    .map_err(YtdlpInfoFetchError::RunnerFailed)?;
+
+  let stdout_text = String::from_utf8_lossy(&output.stdout);
+  let stderr_text = String::from_utf8_lossy(&output.stderr);
+  {
+    let log_state = app.state::<LogStoreState>();
+    let mut store = log_state.write();
+    store.append_lines(app, log_id, stderr_text.lines());
+  }
+
+  let status_code = output.status.code().unwrap_or(1);
+  if status_code != 0 {
+    return Err(YtdlpInfoFetchError::NonZeroExit(status_code, None));
+  }
+  parse_ytdlp_info(&stdout_text, log_id.to_string()).map_err(YtdlpInfoFetchError::ParseFailed)
+}
+
+/// The names of yt-dlp's extractors, one per line.
+pub async fn run_ytdlp_list_extractors(app: &AppHandle) -> Result<String, String> {
+  let output = YtdlpRunner::new(app)
+    .with_args(["--list-extractors"])
+    .output()
+    .await?;
+  if !output.status.success() {
+    return Err(format!(
+      "yt-dlp --list-extractors exited with {}",
+      output.status.code().unwrap_or(1)
+    ));
+  }
+  Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Format options info fetches run with, which only matter for the reported file size.
+pub fn info_format_options(format: Option<FormatOptions>) -> FormatOptions {
+  format.unwrap_or(FormatOptions {
+    track_type: TrackType::Both,
+    abr: None,
+    height: None,
+    fps: None,
+    audio_encoding: None,
+    video_encoding: None,
+    audio_track: None,
+    video_track: None,
+  })
+}
+
+/// Where an info fetch is stored in the metadata cache.
+pub struct InfoCacheSlot {
+  key: String,
+  ttl: Duration,
+  max_bytes: u64,
+}
+
+impl InfoCacheSlot {
+  /// Returns `None` when the metadata cache is disabled.
+  pub fn new(
+    app: &AppHandle,
+    url: &str,
+    format: Option<&FormatOptions>,
+    overrides: Option<&DownloadOverrides>,
+  ) -> Option<Self> {
+    let config = app.state::<SharedConfig>().load();
+    let settings = &config.metadata_cache;
+    settings.enabled.then(|| Self {
+      key: cache_key(url, format, &config, overrides),
+      ttl: Duration::from_secs(settings.ttl_secs),
+      max_bytes: settings.max_size_mb.saturating_mul(1024 * 1024),
+    })
+  }
+
+  pub fn get(&self, app: &AppHandle) -> Option<ParsedMedia> {
+    app.state::<MetadataCache>().get(&self.key, self.ttl)
+  }
+
+  pub fn insert(&self, app: &AppHandle, media: &ParsedMedia) {
+    if let Err(e) = app
+      .state::<MetadataCache>()
+      .insert(&self.key, media, self.max_bytes)
+    {
+      tracing::warn!(error = %e, "Failed to write metadata cache entry");
+    }
+  }
+}
+
+/// Drops playlist entries that are already in the download archive, so re-adding a channel only
+/// lists its new uploads.
+pub fn skip_archived_entries(
+  app: &AppHandle,
+  media: &mut ParsedMedia,
+  overrides: Option<&DownloadOverrides>,
+) {
+  let ParsedMedia::Playlist(playlist) = media else {
+    return;
+  };
+  let Some(archived) = archived_ids(app, overrides) else {
+    return;
+  };
+  playlist
+    .entries
+    .retain(|entry| !is_archived(entry, &archived));
+}
+
+/// Archive ids to leave out of playlist listings, or `None` when the archive is not in use.
+pub fn archived_ids(
+  app: &AppHandle,
+  overrides: Option<&DownloadOverrides>,
+) -> Option<HashSet<String>> {
+  if !uses_download_archive(&app.state::<SharedConfig>().load(), overrides) {
+    return None;
+  }
+  match app.state::<DownloadArchive>().archive_ids() {
+    Ok(archived) => Some(archived),
+    Err(e) => {
+      tracing::warn!(error = %e, "Failed to read download archive");
+      None
+    }
+  }
+}
+
+pub fn is_archived(entry: &PlaylistEntry, archived: &HashSet<String>) -> bool {
+  entry
+    .archive_id
+    .as_ref()
+    .is_some_and(|id| archived.contains(id))
+}
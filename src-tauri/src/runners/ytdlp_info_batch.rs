@@ -0,0 +1,114 @@
+use crate::logging::LogStoreState;
+use crate::models::download::DownloadOverrides;
+use crate::models::ParsedMedia;
+use crate::parsers::ytdlp_info::parse_ytdlp_info_line;
+use crate::runners::ytdlp_info::{
+  info_format_options, skip_archived_entries, InfoCacheSlot, YtdlpInfoFetchError,
+};
+use crate::runners::ytdlp_runner::{YtdlpCommandEvent, YtdlpRunner};
+use crate::scheduling::group_state::subscribe_group;
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager};
+
+pub struct InfoBatchItem {
+  pub id: String,
+  pub url: String,
+}
+
+/// Fetches the info of several URLs of a group that share `overrides` with a single `yt-dlp -J`
+/// run, so they pay the yt-dlp startup cost once. Returns the parsed media by item id. Items
+/// yt-dlp failed on are left out, the caller fetches those on their own to report their errors.
+/// Stops early when the group is cancelled, returning only what was fetched until then.
+pub async fn run_ytdlp_info_batch(
+  app: &AppHandle,
+  group_id: &str,
+  items: &[InfoBatchItem],
+  overrides: Option<&DownloadOverrides>,
+) -> Result<HashMap<String, ParsedMedia>, YtdlpInfoFetchError> {
+  let mut fetched = HashMap::new();
+  let mut pending = Vec::new();
+  for item in items {
+    let slot = InfoCacheSlot::new(app, &item.url, None, overrides);
+    match slot.as_ref().and_then(|slot| slot.get(app)) {
+      Some(media) => {
+        tracing::debug!(url = %item.url, "Serving media info from the metadata cache");
+        fetched.insert(item.id.clone(), media);
+      }
+      None => pending.push((item, slot)),
+    }
+  }
+
+  if !pending.is_empty() {
+    let mut runner = YtdlpRunner::new(app)
+      .with_format_args(&info_format_options(None), None)
+      .with_input_args(overrides)
+      .with_input_filter_args(overrides)
+      .with_auth_args(overrides)
+      .with_network_args(overrides)
+      .with_scheduled_args(overrides)
+      .with_args(["-J", "--flat-playlist", "--ignore-errors"]);
+    for (item, _) in &pending {
+      runner = runner.with_url(&item.url);
+    }
+
+    let (mut rx, child) = runner.spawn().map_err(YtdlpInfoFetchError::RunnerFailed)?;
+    let mut cancel_rx = subscribe_group(group_id);
+    let mut stdout_lines = Vec::new();
+    loop {
+      tokio::select! {
+        event = rx.recv() => match event {
+          Some(YtdlpCommandEvent::Stdout(line)) => {
+            stdout_lines.push(String::from_utf8_lossy(&line).into_owned());
+          }
+          Some(YtdlpCommandEvent::Stderr(line)) => {
+            let line = String::from_utf8_lossy(&line);
+            if !line.is_empty() {
+              let log_state = app.state::<LogStoreState>();
+              let mut store = log_state.write();
+              store.append_line(app, group_id, &line);
+            }
+          }
+          Some(YtdlpCommandEvent::Error(e)) => {
+            let _ = child.kill_tree();
+            return Err(YtdlpInfoFetchError::RunnerFailed(e));
+          }
+          Some(YtdlpCommandEvent::Terminated(_)) | None => break,
+        },
+        Ok(()) = cancel_rx.changed() => {
+          if !*cancel_rx.borrow() {
+            tracing::info!("Cancelled batched info fetch for group_id {group_id}");
+            let _ = child.kill_tree();
+            stdout_lines.clear();
+            break;
+          }
+        }
+      }
+    }
+
+    for line in stdout_lines.iter().filter(|line| !line.trim().is_empty()) {
+      let (url, media) = match parse_ytdlp_info_line(line) {
+        Ok(Some(parsed)) => parsed,
+        Ok(None) => continue,
+        Err(e) => {
+          tracing::warn!(error = %e, "Failed to parse batched yt-dlp output");
+          continue;
+        }
+      };
+      // The same URL may have been added twice, every item gets its own copy.
+      for (item, slot) in pending.iter().filter(|(item, _)| item.url == url) {
+        if let Some(slot) = slot {
+          slot.insert(app, &media);
+        }
+        fetched.insert(item.id.clone(), media.clone());
+      }
+    }
+  }
+
+  for item in items {
+    if let Some(media) = fetched.get_mut(&item.id) {
+      media.set_id(item.id.clone());
+      skip_archived_entries(app, media, overrides);
+    }
+  }
+  Ok(fetched)
+}
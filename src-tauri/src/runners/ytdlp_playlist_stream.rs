@@ -0,0 +1,227 @@
+use crate::logging::LogStoreState;
+use crate::models::download::DownloadOverrides;
+use crate::models::error::DiagnosticLevel;
+use crate::models::payloads::PlaylistEntriesPayload;
+use crate::models::{MediaDiagnosticPayload, MediaFatalPayload, PlaylistEntry};
+use crate::parsers::ytdlp_error::{DiagnosticMatcher, YtdlpErrorParser};
+use crate::parsers::ytdlp_playlist::{parse_playlist_entry_line, PLAYLIST_ENTRY_TEMPLATE};
+use crate::runners::ytdlp_info::{archived_ids, is_archived, YtdlpInfoFetchError};
+use crate::runners::ytdlp_runner::{YtdlpCommandEvent, YtdlpRunner};
+use crate::scheduling::group_state::subscribe_group;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Entries per `playlist_entries` event.
+const CHUNK_SIZE: usize = 200;
+
+/// How long received entries may wait before they are sent in a smaller chunk.
+const FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Lists a playlist with `--flat-playlist --print`, emitting `playlist_entries` chunks while
+/// yt-dlp prints them instead of waiting for the whole JSON. Stops early when the group is
+/// cancelled. Returns the number of entries received, or `Incomplete` when yt-dlp failed after
+/// listing some of them.
+pub async fn run_ytdlp_playlist_stream(
+  app: &AppHandle,
+  id: &str,
+  group_id: &str,
+  url: &str,
+  overrides: Option<&DownloadOverrides>,
+) -> Result<usize, YtdlpInfoFetchError> {
+  static RULES_JSON: &str = include_str!("../diagnostic_rules.json");
+
+  let matcher = match DiagnosticMatcher::from_json(RULES_JSON) {
+    Ok(matcher) => matcher,
+    Err(e) => {
+      let _ = app.emit(
+        "media_fatal",
+        MediaFatalPayload::internal(
+          group_id.to_string(),
+          id.to_string(),
+          format!("Invalid diagnostic rules: {e}"),
+          None,
+        ),
+      );
+      return Err(YtdlpInfoFetchError::InvalidDiagnosticRules(e.to_string()));
+    }
+  };
+  let error_parser = YtdlpErrorParser::new(id, group_id, matcher);
+
+  let runner = YtdlpRunner::new(app)
+    .with_input_args(overrides)
+    .with_input_filter_args(overrides)
+    .with_auth_args(overrides)
+    .with_network_args(overrides)
+    .with_args(["--flat-playlist", "--print", PLAYLIST_ENTRY_TEMPLATE])
+    .with_url(url);
+
+  let (mut rx, child) = match runner.spawn() {
+    Ok(result) => result,
+    Err(e) => {
+      let _ = app.emit(
+        "media_fatal",
+        MediaFatalPayload::with_exit(
+          group_id.to_string(),
+          id.to_string(),
+          1,
+          format!("Failed to spawn yt-dlp: {e}"),
+        ),
+      );
+      return Err(YtdlpInfoFetchError::RunnerFailed(e));
+    }
+  };
+
+  let mut cancel_rx = subscribe_group(group_id);
+  let archived = archived_ids(app, overrides).unwrap_or_default();
+  let mut chunks = ChunkEmitter::new(app, group_id, id);
+  let mut last_error_code: Option<String> = None;
+  let mut flush = tokio::time::interval(FLUSH_INTERVAL);
+
+  loop {
+    tokio::select! {
+      event = rx.recv() => {
+        let Some(event) = event else {
+          let _ = child.kill_tree();
+          chunks.finish(None);
+          return Err(YtdlpInfoFetchError::RunnerFailed(
+            "yt-dlp event stream ended unexpectedly".into(),
+          ));
+        };
+        match event {
+          YtdlpCommandEvent::Stdout(line) => {
+            let line = String::from_utf8_lossy(&line);
+            if let Some(entry) = parse_playlist_entry_line(&line, chunks.received) {
+              let skip = is_archived(&entry, &archived);
+              chunks.push(entry, skip);
+            }
+          }
+          YtdlpCommandEvent::Stderr(line) => {
+            let line = String::from_utf8_lossy(&line);
+            if line.is_empty() {
+              continue;
+            }
+            {
+              let log_state = app.state::<LogStoreState>();
+              let mut store = log_state.write();
+              store.append_line(app, group_id, &line);
+            }
+            if let Some(event) = error_parser.parse_line(&line) {
+              if matches!(event.level, DiagnosticLevel::Error) {
+                last_error_code = Some(event.code.clone());
+              }
+              let _ = app.emit(
+                "media_diagnostic",
+                MediaDiagnosticPayload::from_diagnostic_event(event),
+              );
+            }
+          }
+          YtdlpCommandEvent::Terminated(term) => {
+            let received = chunks.received;
+            if term.code == Some(0) {
+              chunks.finish(None);
+              return Ok(received);
+            }
+            let exit = term.code.unwrap_or(1);
+            // The entries listed so far stay usable, the last chunk says why the rest is missing.
+            if received > 0 {
+              let error = YtdlpInfoFetchError::Incomplete {
+                received,
+                exit,
+                code: last_error_code,
+              };
+              chunks.finish(Some(error.to_string()));
+              return Err(error);
+            }
+            chunks.finish(None);
+            let _ = app.emit(
+              "media_fatal",
+              MediaFatalPayload::with_exit(
+                group_id.to_string(),
+                id.to_string(),
+                exit,
+                format!("yt-dlp exited with code {exit}"),
+              ),
+            );
+            return Err(YtdlpInfoFetchError::NonZeroExit(exit, last_error_code));
+          }
+          YtdlpCommandEvent::Error(e) => {
+            chunks.finish(None);
+            let _ = app.emit(
+              "media_fatal",
+              MediaFatalPayload::internal(
+                group_id.to_string(),
+                id.to_string(),
+                format!("Playlist listing failed: {e}"),
+                Some(e.clone()),
+              ),
+            );
+            return Err(YtdlpInfoFetchError::RunnerFailed(e));
+          }
+        }
+      }
+      _ = flush.tick() => chunks.flush(),
+      Ok(()) = cancel_rx.changed() => {
+        if !*cancel_rx.borrow() {
+          tracing::info!("Cancelled playlist listing for group_id {group_id}");
+          let _ = child.kill_tree();
+          let received = chunks.received;
+          chunks.finish(None);
+          return Ok(received);
+        }
+      }
+    }
+  }
+}
+
+/// Buffers listed entries and emits them as `playlist_entries` chunks.
+struct ChunkEmitter<'a> {
+  app: &'a AppHandle,
+  group_id: &'a str,
+  id: &'a str,
+  pending: Vec<PlaylistEntry>,
+  received: usize,
+}
+
+impl<'a> ChunkEmitter<'a> {
+  fn new(app: &'a AppHandle, group_id: &'a str, id: &'a str) -> Self {
+    Self {
+      app,
+      group_id,
+      id,
+      pending: Vec::with_capacity(CHUNK_SIZE),
+      received: 0,
+    }
+  }
+
+  fn push(&mut self, entry: PlaylistEntry, skip: bool) {
+    self.received += 1;
+    if !skip {
+      self.pending.push(entry);
+    }
+    if self.pending.len() >= CHUNK_SIZE {
+      self.emit(false, None);
+    }
+  }
+
+  fn flush(&mut self) {
+    if !self.pending.is_empty() {
+      self.emit(false, None);
+    }
+  }
+
+  fn finish(mut self, error: Option<String>) {
+    self.emit(true, error);
+  }
+
+  fn emit(&mut self, done: bool, error: Option<String>) {
+    let payload = PlaylistEntriesPayload {
+      group_id: self.group_id.to_string(),
+      id: self.id.to_string(),
+      entries: std::mem::take(&mut self.pending),
+      received: self.received,
+      done,
+      error,
+    };
+    let _ = self.app.emit("playlist_entries", payload);
+  }
+}
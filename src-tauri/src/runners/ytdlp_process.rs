@@ -121,6 +121,24 @@ pub fn kill_platform_process(platform: &PlatformProcess) {
   }
 }
 
+/// Asks the process tree to stop like Ctrl+C would, so ffmpeg finishes the file it writes.
+/// Windows has no such signal for windowless processes and ends the tree instead.
+pub fn interrupt_platform_process(platform: &PlatformProcess) {
+  #[cfg(unix)]
+  {
+    if let Some(pgid) = platform.pgid {
+      unsafe {
+        libc::killpg(pgid, libc::SIGINT);
+      }
+    }
+  }
+
+  #[cfg(windows)]
+  {
+    kill_platform_process(platform);
+  }
+}
+
 #[cfg(windows)]
 fn create_job_for_child(child: &std::process::Child) -> io::Result<JobHandle> {
   let handle = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null::<u16>()) };
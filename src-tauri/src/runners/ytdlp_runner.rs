@@ -1,4 +1,4 @@
-use crate::models::download::{DownloadOverrides, FormatOptions, PlaylistMode};
+use crate::models::download::{DownloadOverrides, FormatOptions, LiveRecording, PlaylistMode};
 use crate::models::SubtitleInventory;
 use crate::models::TrackType;
 use crate::paths::PathsManager;
@@ -8,9 +8,12 @@ use crate::runners::ytdlp_args::{
   build_format_args, build_input_filter_args, build_location_args, build_output_args,
 };
 use crate::runners::ytdlp_process::{
-  configure_command, kill_platform_process, platform_process_from_child, PlatformProcess,
+  configure_command, interrupt_platform_process, kill_platform_process,
+  platform_process_from_child, PlatformProcess,
 };
+use crate::scheduling::bandwidth::pinned_rate;
 use crate::state::config_models::{AuthSettings, Config, SponsorBlockSettings, SubtitleSettings};
+use crate::state::download_archive::DownloadArchive;
 use crate::state::preferences_models::Preferences;
 use crate::stronghold::stronghold_state::{AuthSecrets, StrongholdState};
 use crate::{SharedConfig, SharedPreferences};
@@ -118,6 +121,24 @@ impl<'a> YtdlpRunner<'a> {
     self
   }
 
+  /// Adds `--limit-rate`, preferring the per-download override over `share` (bytes per second).
+  pub fn with_rate_limit_args(
+    mut self,
+    share: Option<u64>,
+    overrides: Option<&DownloadOverrides>,
+  ) -> Self {
+    let limit = match pinned_rate(overrides) {
+      Some(rate) => rate.to_string(),
+      None => match share {
+        Some(share) => share.to_string(),
+        None => return self,
+      },
+    };
+    self.args.push("--limit-rate".into());
+    self.args.push(limit);
+    self
+  }
+
   pub fn with_auth_args(mut self, overrides: Option<&DownloadOverrides>) -> Self {
     let auth_overrides = overrides.and_then(|value| value.auth.as_ref());
     let auth_settings: AuthSettings = resolve_with_patch(&self.cfg.auth, auth_overrides);
@@ -229,6 +250,43 @@ impl<'a> YtdlpRunner<'a> {
     self
   }
 
+  pub fn with_live_args(mut self, live: Option<&LiveRecording>) -> Self {
+    let Some(live) = live else {
+      return self;
+    };
+    self.args.push(
+      if live.from_start {
+        "--live-from-start"
+      } else {
+        "--no-live-from-start"
+      }
+      .into(),
+    );
+    // MPEG-TS stays playable when the recording is cut off.
+    self.args.push("--hls-use-mpegts".into());
+    self
+  }
+
+  /// Lets info fetches of premieres and streams that have not started return their details,
+  /// including when they are scheduled, instead of failing.
+  pub fn with_scheduled_args(mut self, overrides: Option<&DownloadOverrides>) -> Self {
+    if waits_for_scheduled(&self.cfg, overrides) {
+      self.args.push("--ignore-no-formats-error".into());
+    }
+    self
+  }
+
+  pub fn with_download_archive_args(mut self, overrides: Option<&DownloadOverrides>) -> Self {
+    if uses_download_archive(&self.cfg, overrides) {
+      let archive = self.app.state::<DownloadArchive>();
+      self.args.push("--download-archive".into());
+      self
+        .args
+        .push(archive.path().to_string_lossy().into_owned());
+    }
+    self
+  }
+
   pub fn with_input_filter_args(mut self, overrides: Option<&DownloadOverrides>) -> Self {
     self.args.extend(build_input_filter_args(
       overrides.and_then(|value| value.input_filters.as_ref()),
@@ -399,6 +457,18 @@ struct RunLogSummary {
   has_auth: bool,
 }
 
+/// Whether downloads with `overrides` are recorded in and filtered by the download archive.
+pub fn uses_download_archive(cfg: &Config, overrides: Option<&DownloadOverrides>) -> bool {
+  resolve_with_patch(&cfg.input, overrides.and_then(|value| value.input.as_ref()))
+    .use_download_archive
+}
+
+/// Whether downloads with `overrides` wait for scheduled premieres and streams to start.
+pub fn waits_for_scheduled(cfg: &Config, overrides: Option<&DownloadOverrides>) -> bool {
+  resolve_with_patch(&cfg.input, overrides.and_then(|value| value.input.as_ref()))
+    .wait_for_scheduled
+}
+
 fn summarize_args_for_log(args: &[String]) -> RunLogSummary {
   RunLogSummary {
     arg_count: args.len(),
@@ -440,6 +510,10 @@ impl YtdlpChild {
     kill_platform_process(&self.platform);
     Ok(())
   }
+
+  pub fn interrupt(&self) {
+    interrupt_platform_process(&self.platform);
+  }
 }
 
 fn spawn_reader<R: Read + Send + 'static>(
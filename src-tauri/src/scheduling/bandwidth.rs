@@ -0,0 +1,192 @@
+use crate::models::download::DownloadOverrides;
+use crate::scheduling::schedule::{local_minute_of_day, window_active};
+use crate::state::config_models::PerformanceSettings;
+use crate::SharedConfig;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::watch;
+
+/// How often budgets are recomputed so the alternate window takes effect on time.
+pub const REBALANCE_TICK: Duration = Duration::from_secs(30);
+
+/// yt-dlp has to be restarted to pick up a new rate, so raising a share waits this long after
+/// the previous change.
+const RAISE_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Lowering a share waits this long after the previous change, so a batch of downloads starting
+/// one after another restarts the running ones once instead of for every new download. The total
+/// can exceed the budget until then, and downloads that can't be restarted keep their old rate
+/// until their next run.
+const LOWER_COOLDOWN: Duration = Duration::from_secs(10);
+
+/// Smallest share handed out, so a large queue doesn't slow every download to a crawl.
+const MIN_SHARE: u64 = 16 * 1024;
+
+struct Share {
+  sender: watch::Sender<Option<u64>>,
+  changed_at: Option<Instant>,
+}
+
+/// Active downloads that take part in the shared budget, keyed by download id.
+static SHARES: LazyLock<Mutex<HashMap<String, Share>>> =
+  LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The per-download `--limit-rate` override. Downloads with one don't take part in the split.
+pub fn pinned_rate(overrides: Option<&DownloadOverrides>) -> Option<&str> {
+  overrides
+    .and_then(|value| value.network.as_ref())
+    .and_then(|network| network.limit_rate.as_deref())
+    .map(str::trim)
+    .filter(|rate| !rate.is_empty())
+}
+
+/// Adds a starting download to the shared budget and returns its share in bytes per second.
+/// The receiver changes whenever the share changes. Whether the running process is restarted
+/// to apply it is up to the download. The shares of running downloads are lowered once their
+/// cooldown has passed.
+pub fn register(
+  app: &AppHandle,
+  id: &str,
+  overrides: Option<&DownloadOverrides>,
+) -> watch::Receiver<Option<u64>> {
+  if pinned_rate(overrides).is_some() {
+    return watch::channel(None).1;
+  }
+  let (sender, receiver) = watch::channel(None);
+  SHARES.lock().unwrap().insert(
+    id.to_string(),
+    Share {
+      sender,
+      changed_at: None,
+    },
+  );
+  rebalance(app);
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    tokio::time::sleep(LOWER_COOLDOWN).await;
+    rebalance(&app);
+  });
+  receiver
+}
+
+pub fn unregister(app: &AppHandle, id: &str) {
+  if SHARES.lock().unwrap().remove(id).is_some() {
+    rebalance(app);
+  }
+}
+
+pub fn rebalance(app: &AppHandle) {
+  if let Some(config) = app.try_state::<SharedConfig>() {
+    rebalance_with(&config.load().performance);
+  }
+}
+
+/// Splits the current budget evenly across the registered downloads.
+pub fn rebalance_with(settings: &PerformanceSettings) {
+  let budget = current_budget(settings, local_minute_of_day());
+  let now = Instant::now();
+  let mut shares = SHARES.lock().unwrap();
+  let next = split_budget(budget, shares.len());
+  for share in shares.values_mut() {
+    let since_change = share.changed_at.map(|at| now.duration_since(at));
+    let changed = share.sender.send_if_modified(|current| {
+      if !should_apply(*current, next, since_change) {
+        return false;
+      }
+      *current = next;
+      true
+    });
+    if changed {
+      share.changed_at = Some(now);
+    }
+  }
+}
+
+fn current_budget(settings: &PerformanceSettings, minute_of_day: u32) -> Option<u64> {
+  let alternate = settings
+    .alternate_bandwidth_window
+    .as_ref()
+    .is_some_and(|window| window_active(window, minute_of_day));
+  let kib = if alternate {
+    settings.alternate_bandwidth_limit_kib
+  } else {
+    settings.bandwidth_limit_kib
+  };
+  kib.filter(|kib| *kib > 0).map(|kib| kib * 1024)
+}
+
+fn split_budget(budget: Option<u64>, downloads: usize) -> Option<u64> {
+  budget.map(|budget| (budget / downloads.max(1) as u64).max(MIN_SHARE))
+}
+
+/// Whether a running download should be restarted to move from `current` to `next`.
+/// `None` means unlimited and `since_change` is `None` for downloads that never got a share.
+fn should_apply(current: Option<u64>, next: Option<u64>, since_change: Option<Duration>) -> bool {
+  let Some(since_change) = since_change else {
+    return true;
+  };
+  match (current, next) {
+    (Some(current), Some(next)) if next < current => {
+      since_change >= LOWER_COOLDOWN && current - next > current / 4
+    }
+    (Some(current), Some(next)) => since_change >= RAISE_COOLDOWN && next - current > current / 4,
+    (None, Some(_)) => true,
+    (Some(_), None) => since_change >= RAISE_COOLDOWN,
+    (None, None) => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::state::config_models::DownloadWindow;
+
+  #[test]
+  fn uses_the_alternate_budget_inside_its_window() {
+    let settings = PerformanceSettings {
+      bandwidth_limit_kib: Some(1_000),
+      alternate_bandwidth_limit_kib: None,
+      alternate_bandwidth_window: Some(DownloadWindow {
+        start: "23:00".into(),
+        end: "07:00".into(),
+      }),
+      ..PerformanceSettings::default()
+    };
+    assert_eq!(current_budget(&settings, 12 * 60), Some(1_024_000));
+    assert_eq!(current_budget(&settings, 2 * 60), None);
+  }
+
+  #[test]
+  fn splits_the_budget_evenly() {
+    assert_eq!(split_budget(Some(3_000_000), 3), Some(1_000_000));
+    assert_eq!(split_budget(Some(3_000_000), 0), Some(3_000_000));
+    assert_eq!(split_budget(Some(100_000), 50), Some(MIN_SHARE));
+    assert_eq!(split_budget(None, 4), None);
+  }
+
+  #[test]
+  fn lowers_and_raises_after_a_cooldown() {
+    let fresh = Some(Duration::ZERO);
+    assert!(should_apply(None, Some(1_000), None));
+    assert!(should_apply(None, Some(1_000), fresh));
+
+    // A batch of new downloads doesn't restart the running ones for each of them.
+    assert!(!should_apply(Some(1_000), Some(500), fresh));
+    assert!(should_apply(Some(1_000), Some(500), Some(LOWER_COOLDOWN)));
+
+    // Small changes are not worth a restart.
+    assert!(!should_apply(Some(1_000), Some(900), Some(LOWER_COOLDOWN)));
+    assert!(!should_apply(
+      Some(1_000),
+      Some(1_100),
+      Some(RAISE_COOLDOWN)
+    ));
+
+    assert!(!should_apply(Some(500), Some(1_000), fresh));
+    assert!(should_apply(Some(500), Some(1_000), Some(RAISE_COOLDOWN)));
+    assert!(!should_apply(Some(500), None, fresh));
+    assert!(should_apply(Some(500), None, Some(RAISE_COOLDOWN)));
+  }
+}
@@ -1,11 +1,12 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+use tokio::sync::{watch, Mutex, OwnedSemaphorePermit, Semaphore};
 
 pub struct DynamicSemaphore {
   semaphore: Arc<Semaphore>,
   max: AtomicUsize,
   held: Mutex<Vec<OwnedSemaphorePermit>>,
+  paused: watch::Sender<bool>,
 }
 
 impl DynamicSemaphore {
@@ -15,20 +16,50 @@ impl DynamicSemaphore {
       semaphore: Arc::new(Semaphore::new(max)),
       max: AtomicUsize::new(max),
       held: Mutex::new(Vec::new()),
+      paused: watch::channel(false).0,
     }
   }
 
+  pub fn max(&self) -> usize {
+    self.max.load(Ordering::SeqCst)
+  }
+
   pub fn available_permits(&self) -> usize {
     self.semaphore.available_permits()
   }
 
+  /// Stops handing out permits until `resume` is called. Permits that are
+  /// already held are not affected.
+  pub fn pause(&self) {
+    self.paused.send_replace(true);
+  }
+
+  pub fn resume(&self) {
+    self.paused.send_replace(false);
+  }
+
+  pub fn is_paused(&self) -> bool {
+    *self.paused.borrow()
+  }
+
   pub async fn acquire_owned(self: &Arc<Self>) -> OwnedSemaphorePermit {
-    self
-      .semaphore
-      .clone()
-      .acquire_owned()
-      .await
-      .expect("Semaphore closed")
+    loop {
+      let mut paused_rx = self.paused.subscribe();
+      let _ = paused_rx.wait_for(|paused| !*paused).await;
+
+      let permit = self
+        .semaphore
+        .clone()
+        .acquire_owned()
+        .await
+        .expect("Semaphore closed");
+
+      // The limiter may have been paused while we were waiting for a permit.
+      if !self.is_paused() {
+        return permit;
+      }
+      drop(permit);
+    }
   }
 
   pub async fn resize(&self, new_max: usize) {
@@ -0,0 +1,288 @@
+use crate::models::download::QueuePriority;
+use crate::scheduling::dispatcher::DispatchEntry;
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+
+type GroupQueue<Entry> = (String, VecDeque<Entry>);
+
+/// Pending dispatcher entries, grouped per group id.
+///
+/// Groups are served round-robin within the highest priority level that has pending work.
+/// A group that was just served waits in `requeued` until every group in `current` had its
+/// turn, so newly added groups are not starved by long running ones.
+pub struct DispatchQueue<Entry> {
+  current: VecDeque<GroupQueue<Entry>>,
+  requeued: VecDeque<GroupQueue<Entry>>,
+}
+
+impl<Entry: DispatchEntry> Default for DispatchQueue<Entry> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<Entry: DispatchEntry> DispatchQueue<Entry> {
+  pub fn new() -> Self {
+    Self {
+      current: VecDeque::new(),
+      requeued: VecDeque::new(),
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.current.is_empty() && self.requeued.is_empty()
+  }
+
+  /// Number of pending entries per group id.
+  pub fn pending_counts(&self) -> HashMap<String, usize> {
+    self
+      .current
+      .iter()
+      .chain(self.requeued.iter())
+      .map(|(gid, queue)| (gid.clone(), queue.len()))
+      .collect()
+  }
+
+  pub fn push(&mut self, group_id: String, entries: Vec<Entry>) {
+    if entries.is_empty() {
+      return;
+    }
+    if let Some((_, queue)) = self.group_mut(&group_id) {
+      queue.extend(entries);
+    } else {
+      self
+        .current
+        .push_back((group_id, entries.into_iter().collect()));
+    }
+  }
+
+  pub fn remove_group(&mut self, group_id: &str) {
+    self.retain_groups(|gid| gid != group_id);
+  }
+
+  pub fn retain_groups(&mut self, keep: impl Fn(&str) -> bool) {
+    self.current.retain(|(gid, _)| keep(gid));
+    self.requeued.retain(|(gid, _)| keep(gid));
+  }
+
+  /// Moves a pending entry to `index` within its group, clamped to the queue length.
+  pub fn move_entry(&mut self, group_id: &str, id: &str, index: usize) -> bool {
+    let Some((_, queue)) = self.group_mut(group_id) else {
+      return false;
+    };
+    let Some(position) = queue.iter().position(|entry| entry.id() == id) else {
+      return false;
+    };
+    let entry = queue.remove(position).unwrap();
+    let index = index.min(queue.len());
+    queue.insert(index, entry);
+    true
+  }
+
+  pub fn prioritize_entry(&mut self, group_id: &str, id: &str, priority: QueuePriority) -> bool {
+    let Some((_, queue)) = self.group_mut(group_id) else {
+      return false;
+    };
+    match queue.iter_mut().find(|entry| entry.id() == id) {
+      Some(entry) => {
+        entry.set_priority(priority);
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Takes the next entry to run, skipping entries for which `can_run` is false.
+  ///
+  /// The group is chosen by `group_priority`, ties keep the round-robin order. Within the group
+  /// the first entry with the highest priority wins, so equal priorities keep queue order.
+  pub fn pop_next(
+    &mut self,
+    group_priority: impl Fn(&str) -> QueuePriority,
+    can_run: impl Fn(&Entry) -> bool,
+  ) -> Option<Entry> {
+    if self.current.is_empty() {
+      self.current.append(&mut self.requeued);
+    }
+
+    let next_in = |queue: &VecDeque<Entry>| {
+      queue
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| can_run(entry))
+        .max_by_key(|(index, entry)| (entry.priority(), Reverse(*index)))
+        .map(|(index, _)| index)
+    };
+
+    let (group_index, entry_index) = self
+      .current
+      .iter()
+      .chain(self.requeued.iter())
+      .enumerate()
+      .filter_map(|(index, (gid, queue))| next_in(queue).map(|entry| (index, gid, entry)))
+      .max_by_key(|(index, gid, _)| (group_priority(gid), Reverse(*index)))
+      .map(|(index, _, entry)| (index, entry))?;
+
+    let (group_id, mut queue) = if group_index < self.current.len() {
+      self.current.remove(group_index)?
+    } else {
+      self.requeued.remove(group_index - self.current.len())?
+    };
+    let entry = queue.remove(entry_index)?;
+
+    tracing::trace!(
+      group_id = %group_id,
+      remaining_in_group = queue.len(),
+      "Dispatcher: scheduling entry"
+    );
+
+    if !queue.is_empty() {
+      self.requeued.push_back((group_id, queue));
+    }
+    Some(entry)
+  }
+
+  fn group_mut(&mut self, group_id: &str) -> Option<&mut GroupQueue<Entry>> {
+    self
+      .current
+      .iter_mut()
+      .chain(self.requeued.iter_mut())
+      .find(|(gid, _)| gid == group_id)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Clone)]
+  struct TestEntry {
+    group_id: String,
+    id: String,
+    priority: QueuePriority,
+  }
+
+  impl DispatchEntry for TestEntry {
+    fn group_id(&self) -> &String {
+      &self.group_id
+    }
+    fn id(&self) -> &String {
+      &self.id
+    }
+    fn group_key(&self) -> Option<&String> {
+      None
+    }
+    fn set_numbering(&mut self, _autonumber: u64, _group_autonumber: Option<u64>) {}
+    fn priority(&self) -> QueuePriority {
+      self.priority
+    }
+    fn set_priority(&mut self, priority: QueuePriority) {
+      self.priority = priority;
+    }
+  }
+
+  fn entries(group_id: &str, count: usize) -> Vec<TestEntry> {
+    (0..count)
+      .map(|index| TestEntry {
+        group_id: group_id.to_string(),
+        id: format!("{group_id}:{index}"),
+        priority: QueuePriority::Normal,
+      })
+      .collect()
+  }
+
+  fn drain(
+    queue: &mut DispatchQueue<TestEntry>,
+    group_priority: impl Fn(&str) -> QueuePriority,
+  ) -> Vec<String> {
+    let mut order = Vec::new();
+    while let Some(entry) = queue.pop_next(&group_priority, |_| true) {
+      order.push(entry.id);
+    }
+    order
+  }
+
+  #[test]
+  fn round_robins_groups_of_equal_priority() {
+    let mut queue = DispatchQueue::new();
+    queue.push("a".into(), entries("a", 2));
+    queue.push("b".into(), entries("b", 2));
+
+    let order = drain(&mut queue, |_| QueuePriority::Normal);
+    assert_eq!(order, vec!["a:0", "b:0", "a:1", "b:1"]);
+  }
+
+  #[test]
+  fn serves_higher_priority_groups_first() {
+    let mut queue = DispatchQueue::new();
+    queue.push("a".into(), entries("a", 2));
+    queue.push("b".into(), entries("b", 2));
+    queue.push("c".into(), entries("c", 1));
+
+    let order = drain(&mut queue, |gid| match gid {
+      "a" => QueuePriority::Low,
+      _ => QueuePriority::High,
+    });
+    assert_eq!(order, vec!["b:0", "c:0", "b:1", "a:0", "a:1"]);
+  }
+
+  #[test]
+  fn prioritized_entry_jumps_ahead_within_group() {
+    let mut queue = DispatchQueue::new();
+    queue.push("a".into(), entries("a", 3));
+
+    assert!(queue.prioritize_entry("a", "a:2", QueuePriority::Urgent));
+    let order = drain(&mut queue, |_| QueuePriority::Normal);
+    assert_eq!(order, vec!["a:2", "a:0", "a:1"]);
+  }
+
+  #[test]
+  fn move_entry_reorders_and_clamps_index() {
+    let mut queue = DispatchQueue::new();
+    queue.push("a".into(), entries("a", 3));
+
+    assert!(queue.move_entry("a", "a:0", 10));
+    assert!(queue.move_entry("a", "a:2", 0));
+    assert!(!queue.move_entry("a", "missing", 0));
+    let order = drain(&mut queue, |_| QueuePriority::Normal);
+    assert_eq!(order, vec!["a:2", "a:1", "a:0"]);
+  }
+
+  #[test]
+  fn skips_entries_that_cannot_run() {
+    let mut queue = DispatchQueue::new();
+    queue.push("a".into(), entries("a", 2));
+    queue.push("b".into(), entries("b", 1));
+
+    let next = queue.pop_next(|_| QueuePriority::Normal, |entry| entry.group_id != "a");
+    assert_eq!(next.unwrap().id, "b:0");
+    assert!(queue
+      .pop_next(|_| QueuePriority::Normal, |entry| entry.group_id != "a")
+      .is_none());
+    assert!(!queue.is_empty());
+  }
+
+  #[test]
+  fn push_merges_into_requeued_group() {
+    let mut queue = DispatchQueue::new();
+    queue.push("a".into(), entries("a", 2));
+    assert_eq!(
+      queue
+        .pop_next(|_| QueuePriority::Normal, |_| true)
+        .unwrap()
+        .id,
+      "a:0"
+    );
+
+    queue.push(
+      "a".into(),
+      vec![TestEntry {
+        group_id: "a".into(),
+        id: "a:extra".into(),
+        priority: QueuePriority::Normal,
+      }],
+    );
+    let order = drain(&mut queue, |_| QueuePriority::Normal);
+    assert_eq!(order, vec!["a:1", "a:extra"]);
+  }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+
+/// What one dispatcher is doing right now, published by its loop for introspection.
+#[derive(Default)]
+pub struct DispatchStats {
+  inner: StdMutex<StatsInner>,
+}
+
+#[derive(Default)]
+struct StatsInner {
+  pending: HashMap<String, usize>,
+  running: HashMap<u64, RunningJob>,
+  next_token: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunningJob {
+  pub group_id: String,
+  pub id: String,
+  pub started_at: Instant,
+}
+
+impl RunningJob {
+  pub fn age(&self) -> Duration {
+    self.started_at.elapsed()
+  }
+}
+
+impl DispatchStats {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn set_pending(&self, pending: HashMap<String, usize>) {
+    self.inner.lock().unwrap().pending = pending;
+  }
+
+  /// Records a dispatched job and returns the token to pass to `job_finished`.
+  pub fn job_started(&self, group_id: &str, id: &str) -> u64 {
+    let mut inner = self.inner.lock().unwrap();
+    let token = inner.next_token;
+    inner.next_token += 1;
+    inner.running.insert(
+      token,
+      RunningJob {
+        group_id: group_id.to_string(),
+        id: id.to_string(),
+        started_at: Instant::now(),
+      },
+    );
+    token
+  }
+
+  pub fn job_finished(&self, token: u64) {
+    self.inner.lock().unwrap().running.remove(&token);
+  }
+
+  /// Pending entries per group id.
+  pub fn pending(&self) -> HashMap<String, usize> {
+    self.inner.lock().unwrap().pending.clone()
+  }
+
+  /// Jobs that currently hold a permit, longest running first.
+  pub fn running(&self) -> Vec<RunningJob> {
+    let mut running: Vec<RunningJob> = self
+      .inner
+      .lock()
+      .unwrap()
+      .running
+      .values()
+      .cloned()
+      .collect();
+    running.sort_by_key(|job| job.started_at);
+    running
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn tracks_running_jobs_by_token() {
+    let stats = DispatchStats::new();
+    let first = stats.job_started("group", "same-id");
+    let second = stats.job_started("group", "same-id");
+    assert_eq!(stats.running().len(), 2);
+
+    stats.job_finished(first);
+    let running = stats.running();
+    assert_eq!(running.len(), 1);
+    assert_eq!(running[0].group_id, "group");
+
+    stats.job_finished(second);
+    assert!(stats.running().is_empty());
+  }
+}
@@ -1,37 +1,118 @@
+use crate::models::download::QueuePriority;
 use crate::scheduling::concurrency::DynamicSemaphore;
-use crate::scheduling::group_state::{is_group_running, remove_group};
+use crate::scheduling::dispatch_queue::DispatchQueue;
+use crate::scheduling::dispatch_stats::DispatchStats;
+use crate::scheduling::group_state::{group_priority, is_group_running, remove_group};
+use crate::scheduling::host_limiter::HostLimiter;
 use crate::scheduling::numbering::NumberingManager;
 use futures::Future;
-use std::{collections::VecDeque, sync::Arc};
+use std::sync::Arc;
+use std::time::Duration;
 use tauri::{AppHandle, Runtime};
 use tokio::sync::mpsc;
 
 pub trait DispatchEntry: Clone + Send + Sync + 'static {
   fn group_id(&self) -> &String;
+  fn id(&self) -> &String;
   fn group_key(&self) -> Option<&String>;
   fn set_numbering(&mut self, autonumber: u64, group_autonumber: Option<u64>);
+  /// Entries that are requeued (e.g. resumed downloads) keep their original numbering.
+  fn is_numbered(&self) -> bool {
+    false
+  }
+  fn priority(&self) -> QueuePriority {
+    QueuePriority::Normal
+  }
+  fn set_priority(&mut self, _priority: QueuePriority) {}
+  /// Key (extractor or host) used to cap parallel entries of the same site.
+  fn limit_key(&self) -> Option<String> {
+    None
+  }
 }
 
 #[derive(Clone)]
 pub enum DispatchRequest<Req> {
   Pipeline(Req),
-  Cleanup { group_id: String },
+  Cleanup {
+    group_id: String,
+  },
+  /// Moves a pending entry to another position within its group.
+  Move {
+    group_id: String,
+    id: String,
+    index: usize,
+  },
+  Prioritize {
+    group_id: String,
+    id: String,
+    priority: QueuePriority,
+  },
+}
+
+/// How often entries that are held back by `DispatchOptions::group_gate` are checked again.
+const GATE_RECHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+type GroupGate = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Extra limits on top of the dispatcher semaphore.
+#[derive(Clone)]
+pub struct DispatchOptions {
+  pub host_limiter: Arc<HostLimiter>,
+  /// Whether entries of a group may start now, e.g. outside a scheduled time window.
+  pub group_gate: GroupGate,
+}
+
+impl Default for DispatchOptions {
+  fn default() -> Self {
+    Self {
+      host_limiter: Arc::new(HostLimiter::new(0)),
+      group_gate: Arc::new(|_| true),
+    }
+  }
 }
 
 pub struct GenericDispatcher<Req> {
   sender: mpsc::UnboundedSender<DispatchRequest<Req>>,
+  stats: Arc<DispatchStats>,
 }
 
 impl<Req> GenericDispatcher<Req>
 where
   Req: Send + Sync + Clone + 'static,
 {
+  #[cfg(test)]
   pub fn start<Entry, MakeEntries, RunJob, Fut, R>(
     app_handle: AppHandle<R>,
     semaphore: Arc<DynamicSemaphore>,
     make_entries: MakeEntries,
     run_job: RunJob,
   ) -> Self
+  where
+    R: Runtime,
+    Entry: DispatchEntry,
+    MakeEntries: Fn(Req) -> Vec<Entry> + Send + Sync + 'static,
+    RunJob: Fn(mpsc::UnboundedSender<DispatchRequest<Req>>, AppHandle<R>, Entry) -> Fut
+      + Send
+      + Sync
+      + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+  {
+    Self::start_with_options(
+      app_handle,
+      semaphore,
+      DispatchOptions::default(),
+      make_entries,
+      run_job,
+    )
+  }
+
+  pub fn start_with_options<Entry, MakeEntries, RunJob, Fut, R>(
+    app_handle: AppHandle<R>,
+    semaphore: Arc<DynamicSemaphore>,
+    options: DispatchOptions,
+    make_entries: MakeEntries,
+    run_job: RunJob,
+  ) -> Self
   where
     R: Runtime,
     Entry: DispatchEntry,
@@ -48,45 +129,26 @@ where
     let app_main = app_handle;
     let make_entries = Arc::new(make_entries);
     let run_job = Arc::new(run_job);
+    let DispatchOptions {
+      host_limiter,
+      group_gate,
+    } = options;
+    let stats = Arc::new(DispatchStats::new());
+    let stats_loop = stats.clone();
 
     tauri::async_runtime::spawn(async move {
       let mut numbering = NumberingManager::new();
-      let mut queues: VecDeque<(String, VecDeque<Entry>)> = VecDeque::new();
-      let mut pending_requeue: VecDeque<(String, VecDeque<Entry>)> = VecDeque::new();
+      let mut queue: DispatchQueue<Entry> = DispatchQueue::new();
       loop {
         while let Ok(req) = rx.try_recv() {
-          match req {
-            DispatchRequest::Cleanup { group_id } => {
-              queues.retain(|(gid, _)| gid != &group_id);
-              remove_group(&group_id);
-            }
-            DispatchRequest::Pipeline(inner) => {
-              let mut entries = make_entries(inner.clone());
-              if !entries.is_empty() {
-                let gid = entries[0].group_id().clone();
-                if is_group_running(&gid) {
-                  for entry in entries.iter_mut() {
-                    let group_key = entry.group_key();
-                    let (autonumber, group_autonumber) = numbering.assign_for(group_key);
-                    entry.set_numbering(autonumber, group_autonumber);
-                  }
-
-                  let q: VecDeque<Entry> = entries.into_iter().collect();
-                  queues.push_back((gid, q));
-                }
-              }
-            }
-          }
+          handle_request(req, make_entries.as_ref(), &mut queue, &mut numbering);
         }
 
-        queues.retain(|(gid, _)| is_group_running(gid));
-
-        if !pending_requeue.is_empty() {
-          queues.append(&mut pending_requeue);
-        }
+        queue.retain_groups(is_group_running);
+        stats_loop.set_pending(queue.pending_counts());
 
         // If no work, block until the next request.
-        if queues.is_empty() {
+        if queue.is_empty() {
           if let Some(req) = rx.recv().await {
             let _ = tx_loop.send(req);
             continue;
@@ -94,69 +156,132 @@ where
           break;
         }
 
-        while !queues.is_empty() {
-          // Grab one permit
-          let permit = sem.clone().acquire_owned().await;
+        // Grab one permit
+        let permit = sem.clone().acquire_owned().await;
 
-          if queues.is_empty() {
-            drop(permit);
-            continue;
-          }
-
-          // Pop the next group and its next entry
-          let (group_id, mut q) = queues.pop_front().unwrap();
-
-          // Skip cancelled groups.
-          let still_running = is_group_running(&group_id);
-          if !still_running {
-            drop(permit);
-            continue;
+        // Requests that arrived while waiting may change what runs next.
+        while let Ok(req) = rx.try_recv() {
+          handle_request(req, make_entries.as_ref(), &mut queue, &mut numbering);
+        }
+        queue.retain_groups(is_group_running);
+
+        // Grab the entry that is going to be run.
+        let next = queue.pop_next(group_priority, |entry| {
+          group_gate(entry.group_id())
+            && entry
+              .limit_key()
+              .is_none_or(|key| host_limiter.has_capacity(&key))
+        });
+        let Some(entry) = next else {
+          drop(permit);
+          if !queue.is_empty() {
+            // Everything pending is held back, wait for a slot, a new request or the gate.
+            tokio::select! {
+              _ = host_limiter.released() => {}
+              _ = tokio::time::sleep(GATE_RECHECK_INTERVAL) => {}
+              req = rx.recv() => match req {
+                Some(req) => {
+                  let _ = tx_loop.send(req);
+                }
+                None => break,
+              },
+            }
           }
-
-          // Grab the entry that is going to be run.
-          let entry = q.pop_front().unwrap();
-
+          continue;
+        };
+        stats_loop.set_pending(queue.pending_counts());
+
+        let entry_group_id = entry.group_id().clone();
+        let host_permit = entry.limit_key().map(|key| host_limiter.acquire(key));
+        let job_token = stats_loop.job_started(&entry_group_id, entry.id());
+        let stats_job = stats_loop.clone();
+
+        // Dispatch the entry with the permit.
+        let tx_job = tx_loop.clone();
+        let app2 = app_main.clone();
+        let run_job = run_job.clone();
+        tauri::async_runtime::spawn(async move {
           tracing::trace!(
-            group_id = %group_id,
-            remaining_in_group = q.len(),
-            "Dispatcher: scheduling entry"
+            group_id = %entry_group_id,
+            "Dispatcher: starting entry"
           );
-
-          let entry_group_id = entry.group_id().clone();
-
-          // Dispatch the entry with the permit.
-          {
-            let tx_job = tx_loop.clone();
-            let app2 = app_main.clone();
-            let run_job = run_job.clone();
-            tauri::async_runtime::spawn(async move {
-              tracing::trace!(
-                group_id = %entry_group_id,
-                "Dispatcher: starting entry"
-              );
-              run_job(tx_job, app2, entry.clone()).await;
-              tracing::trace!(
-                group_id = %entry_group_id,
-                "Dispatcher: finished entry, releasing permit"
-              );
-              drop(permit);
-            });
-          }
-
-          // Requeue if more groups are left.
-          if !q.is_empty() {
-            pending_requeue.push_back((group_id, q));
-          }
-        }
+          run_job(tx_job, app2, entry.clone()).await;
+          tracing::trace!(
+            group_id = %entry_group_id,
+            "Dispatcher: finished entry, releasing permit"
+          );
+          stats_job.job_finished(job_token);
+          drop(host_permit);
+          drop(permit);
+        });
       }
     });
 
-    Self { sender: tx }
+    Self { sender: tx, stats }
   }
 
   pub fn sender(&self) -> mpsc::UnboundedSender<DispatchRequest<Req>> {
     self.sender.clone()
   }
+
+  pub fn stats(&self) -> Arc<DispatchStats> {
+    self.stats.clone()
+  }
+}
+
+fn handle_request<Req, Entry, MakeEntries>(
+  req: DispatchRequest<Req>,
+  make_entries: &MakeEntries,
+  queue: &mut DispatchQueue<Entry>,
+  numbering: &mut NumberingManager,
+) where
+  Entry: DispatchEntry,
+  MakeEntries: Fn(Req) -> Vec<Entry>,
+{
+  match req {
+    DispatchRequest::Cleanup { group_id } => {
+      queue.remove_group(&group_id);
+      remove_group(&group_id);
+    }
+    DispatchRequest::Pipeline(inner) => {
+      let mut entries = make_entries(inner);
+      if entries.is_empty() {
+        return;
+      }
+      let gid = entries[0].group_id().clone();
+      if !is_group_running(&gid) {
+        return;
+      }
+      for entry in entries.iter_mut().filter(|entry| !entry.is_numbered()) {
+        let group_key = entry.group_key();
+        let (autonumber, group_autonumber) = numbering.assign_for(group_key);
+        entry.set_numbering(autonumber, group_autonumber);
+      }
+      queue.push(gid, entries);
+    }
+    DispatchRequest::Move {
+      group_id,
+      id,
+      index,
+    } => {
+      if !queue.move_entry(&group_id, &id, index) {
+        tracing::debug!(group_id = %group_id, id = %id, "Dispatcher: no pending entry to move");
+      }
+    }
+    DispatchRequest::Prioritize {
+      group_id,
+      id,
+      priority,
+    } => {
+      if !queue.prioritize_entry(&group_id, &id, priority) {
+        tracing::debug!(
+          group_id = %group_id,
+          id = %id,
+          "Dispatcher: no pending entry to prioritize"
+        );
+      }
+    }
+  }
 }
 
 #[cfg(test)]
@@ -180,6 +305,9 @@ mod tests {
     fn group_id(&self) -> &String {
       &self.group_id
     }
+    fn id(&self) -> &String {
+      &self.group_id
+    }
     fn group_key(&self) -> Option<&String> {
       None
     }
@@ -446,4 +574,59 @@ mod tests {
       "expected new group to run before the last queued entry of existing group"
     );
   }
+
+  #[tokio::test]
+  async fn dispatcher_hands_out_no_permits_while_paused() {
+    let app = mock_app();
+    let group_id = "paused-group".to_string();
+    ensure_group_running(&group_id);
+
+    let sem = Arc::new(DynamicSemaphore::new(2));
+    sem.pause();
+    let started = Arc::new(AtomicUsize::new(0));
+    let done = Arc::new(Notify::new());
+    let total = 2usize;
+
+    let started_job = started.clone();
+    let done_job = done.clone();
+
+    let dispatcher = GenericDispatcher::start(
+      app.handle().clone(),
+      sem.clone(),
+      {
+        let group_id = group_id.clone();
+        move |count: usize| {
+          (0..count)
+            .map(|index| TestEntry {
+              group_id: group_id.clone(),
+              index,
+            })
+            .collect()
+        }
+      },
+      move |_tx, _app, _entry: TestEntry| {
+        let started_job = started_job.clone();
+        let done_job = done_job.clone();
+        async move {
+          if started_job.fetch_add(1, Ordering::SeqCst) + 1 == total {
+            done_job.notify_one();
+          }
+        }
+      },
+    );
+
+    dispatcher
+      .sender()
+      .send(DispatchRequest::Pipeline(total))
+      .unwrap();
+
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(started.load(Ordering::SeqCst), 0);
+
+    sem.resume();
+    timeout(Duration::from_secs(5), done.notified())
+      .await
+      .expect("dispatcher did not resume within timeout");
+    assert_eq!(started.load(Ordering::SeqCst), total);
+  }
 }
@@ -1,14 +1,30 @@
-use crate::models::download::{DownloadOverrides, FormatOptions};
-use crate::models::DownloadItem;
+use crate::models::download::{DownloadOverrides, FormatOptions, LiveRecording, QueuePriority};
+use crate::models::payloads::{GroupKind, MediaCountdownPayload, MediaRetryPayload};
 use crate::models::SubtitleInventory;
+use crate::models::{DownloadItem, MediaFatalPayload, MediaProgressComplete};
+use crate::runners::post_download_hook::{run_post_download_hook, HOOK_FAILED_CODE};
 use crate::runners::template_context::TemplateContext;
-use crate::runners::ytdlp_download::{run_ytdlp_download, YtdlpDownloadError};
+use crate::runners::ytdlp_download::{run_ytdlp_download, DownloadOutcome, YtdlpDownloadError};
+use crate::scheduling::bandwidth::{self, REBALANCE_TICK};
 use crate::scheduling::concurrency::DynamicSemaphore;
-use crate::scheduling::dispatcher::{DispatchEntry, DispatchRequest, GenericDispatcher};
+use crate::scheduling::dispatcher::{
+  DispatchEntry, DispatchOptions, DispatchRequest, GenericDispatcher,
+};
+use crate::scheduling::fetch_pipeline::is_fetched_livestream;
+use crate::scheduling::group_state::{ensure_group_running, is_group_running};
+use crate::scheduling::group_summary::{GroupSummaries, ItemResult};
+use crate::scheduling::host_limiter::{host_key, HostLimiter, RECOVERY_TICK};
+use crate::scheduling::item_state::{item_control, remove_item, set_item_control, ItemControl};
+use crate::scheduling::schedule::{download_allowed_now, set_group_start};
+use crate::scheduling::subscriptions::record_subscription_download;
+use crate::state::download_queue_models::QueuedDownload;
+use crate::{SharedConfig, SharedDownloadQueue};
+use indexmap::IndexMap;
 use std::collections::HashMap;
 use std::sync::LazyLock;
 use std::sync::{Arc, Mutex};
-use tauri::AppHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::mpsc::UnboundedSender;
 
 #[derive(Clone)]
@@ -20,6 +36,14 @@ pub enum DownloadRequest {
     group_id: String,
     items: Vec<DownloadItem>,
   },
+  Resume {
+    entries: Vec<DownloadEntry>,
+  },
+  /// Journaled entries from the previous session, queued again on startup.
+  Restore {
+    group_id: String,
+    entries: Vec<DownloadEntry>,
+  },
 }
 
 #[derive(Clone)]
@@ -31,10 +55,22 @@ pub struct DownloadEntry {
   pub subtitle_inventory: Option<SubtitleInventory>,
   pub overrides: Option<DownloadOverrides>,
   pub template_context: TemplateContext,
+  pub priority: QueuePriority,
+  pub numbered: bool,
+  /// Retries that already happened after transient failures.
+  pub retries: u32,
+  /// A whole playlist downloaded by one yt-dlp run, see `DownloadItem::direct_playlist`.
+  pub direct_playlist: bool,
+  pub live: Option<LiveRecording>,
 }
 
 impl From<(DownloadItem, String)> for DownloadEntry {
+  /// Items of media that was fetched as a livestream are recorded, even when the caller, e.g. the
+  /// CLI or a subscription, did not ask for it.
   fn from(item: (DownloadItem, String)) -> Self {
+    let live = item.0.live.or_else(|| {
+      is_fetched_livestream(&item.0.url, item.0.overrides.as_ref()).then(LiveRecording::default)
+    });
     Self {
       group_id: item.1,
       id: item.0.id,
@@ -43,6 +79,52 @@ impl From<(DownloadItem, String)> for DownloadEntry {
       subtitle_inventory: item.0.subtitle_inventory,
       overrides: item.0.overrides,
       template_context: item.0.template_context,
+      priority: item.0.priority,
+      numbered: false,
+      retries: 0,
+      direct_playlist: item.0.direct_playlist,
+      live,
+    }
+  }
+}
+
+impl From<QueuedDownload> for DownloadEntry {
+  fn from(queued: QueuedDownload) -> Self {
+    Self {
+      group_id: queued.group_id,
+      id: queued.id,
+      url: queued.url,
+      format: queued.format,
+      subtitle_inventory: queued.subtitle_inventory,
+      overrides: queued.overrides,
+      template_context: queued.template_context,
+      priority: queued.priority,
+      numbered: false,
+      retries: 0,
+      direct_playlist: queued.direct_playlist,
+      live: queued.live,
+    }
+  }
+}
+
+impl From<&DownloadEntry> for QueuedDownload {
+  fn from(entry: &DownloadEntry) -> Self {
+    Self {
+      group_id: entry.group_id.clone(),
+      id: entry.id.clone(),
+      url: entry.url.clone(),
+      format: entry.format.clone(),
+      subtitle_inventory: entry.subtitle_inventory.clone(),
+      overrides: entry.overrides.clone(),
+      template_context: entry.template_context.clone(),
+      priority: entry.priority,
+      queued_at: SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0),
+      restored: false,
+      direct_playlist: entry.direct_playlist,
+      live: entry.live.clone(),
     }
   }
 }
@@ -51,6 +133,9 @@ impl DispatchEntry for DownloadEntry {
   fn group_id(&self) -> &String {
     &self.group_id
   }
+  fn id(&self) -> &String {
+    &self.id
+  }
   fn group_key(&self) -> Option<&String> {
     self.template_context.values.get("playlist_id")
   }
@@ -65,61 +150,558 @@ impl DispatchEntry for DownloadEntry {
         group_autonumber.to_string(),
       );
     }
+    self.numbered = true;
+  }
+  fn is_numbered(&self) -> bool {
+    self.numbered
+  }
+  fn priority(&self) -> QueuePriority {
+    self.priority
+  }
+  fn set_priority(&mut self, priority: QueuePriority) {
+    self.priority = priority;
+  }
+  fn limit_key(&self) -> Option<String> {
+    self
+      .template_context
+      .values
+      .get("extractor_key")
+      .filter(|key| !key.is_empty())
+      .map(|key| key.to_lowercase())
+      .or_else(|| host_key(&self.url))
   }
 }
 
 static DOWNLOAD_COUNTERS: LazyLock<Mutex<HashMap<String, usize>>> =
   LazyLock::new(|| Mutex::new(HashMap::new()));
 
+static DOWNLOAD_SUMMARIES: LazyLock<GroupSummaries> =
+  LazyLock::new(|| GroupSummaries::new(GroupKind::Download));
+
+/// Downloads that currently hold a permit, keyed by id with their group id as value.
+static ACTIVE_DOWNLOADS: LazyLock<Mutex<HashMap<String, String>>> =
+  LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Paused downloads waiting to be resumed. They keep counting towards their group.
+static PAUSED_DOWNLOADS: LazyLock<Mutex<HashMap<String, DownloadEntry>>> =
+  LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Finished downloads waiting to be dropped from the journal. Saving the journal rewrites the
+/// whole store, so removals are flushed together instead of once per item.
+static UNJOURNALED: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// How often `media_countdown` is sent while waiting for scheduled media.
+const COUNTDOWN_INTERVAL: Duration = Duration::from_secs(60);
+
+const JOURNAL_FLUSH_DELAY: Duration = Duration::from_secs(2);
+
 pub fn setup_download_dispatcher(
   app: &AppHandle,
   sem: Arc<DynamicSemaphore>,
+  host_limiter: Arc<HostLimiter>,
 ) -> GenericDispatcher<DownloadRequest> {
-  GenericDispatcher::start(
+  let recovery_limiter = host_limiter.clone();
+  tauri::async_runtime::spawn(async move {
+    let mut ticker = tokio::time::interval(RECOVERY_TICK);
+    loop {
+      ticker.tick().await;
+      recovery_limiter.recover();
+    }
+  });
+
+  let bandwidth_app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    let mut ticker = tokio::time::interval(REBALANCE_TICK);
+    loop {
+      ticker.tick().await;
+      bandwidth::rebalance(&bandwidth_app);
+    }
+  });
+
+  let gate_app = app.clone();
+  let options = DispatchOptions {
+    host_limiter,
+    group_gate: Arc::new(move |group_id| download_allowed_now(&gate_app, group_id)),
+  };
+
+  let journal_app = app.clone();
+  GenericDispatcher::start_with_options(
     app.clone(),
     sem,
-    |req: DownloadRequest| match req {
+    options,
+    move |req: DownloadRequest| match req {
       DownloadRequest::Batch { group_id, items } => {
-        let total = items.len();
-        DOWNLOAD_COUNTERS
-          .lock()
-          .unwrap()
-          .insert(group_id.clone(), total);
-        items
+        track_downloads(&group_id, items.len());
+        let entries: Vec<DownloadEntry> = items
           .into_iter()
           .map(|item| DownloadEntry::from((item, group_id.clone())))
-          .collect()
+          .collect();
+        if is_group_running(&group_id) {
+          journal_entries(&journal_app, &entries);
+        }
+        entries
+      }
+      DownloadRequest::Resume { entries } => entries,
+      DownloadRequest::Restore { group_id, entries } => {
+        track_downloads(&group_id, entries.len());
+        entries
       }
     },
     |tx, app: AppHandle, entry: DownloadEntry| async move {
-      tracing::info!("starting download id={} url={}", entry.id, entry.url);
-
-      if let Err(e) = run_ytdlp_download(app.clone(), entry.clone()).await {
-        tracing::warn!(
-          download_id = %entry.id,
-          group_id = %entry.group_id,
-          error = %e,
-          "Failed to run ytdlp download",
-        );
-        if should_report_to_sentry(&e) {
-          sentry::capture_error(&e);
+      match item_control(&entry.id) {
+        ItemControl::Run => {}
+        ItemControl::Pause => {
+          park_download(&tx, &app, entry);
+          return;
+        }
+        ItemControl::Cancel | ItemControl::Stop => {
+          finish_download(&tx, &app, &entry, ItemResult::Cancelled);
+          return;
         }
       }
 
-      let mut counters = DOWNLOAD_COUNTERS.lock().unwrap();
-      if let Some(cnt) = counters.get_mut(&entry.group_id) {
-        *cnt -= 1;
-        if *cnt == 0 {
-          counters.remove(&entry.group_id);
-          let _ = tx.send(DispatchRequest::Cleanup {
-            group_id: entry.group_id.clone(),
-          });
+      tracing::info!("starting download id={} url={}", entry.id, entry.url);
+      ACTIVE_DOWNLOADS
+        .lock()
+        .unwrap()
+        .insert(entry.id.clone(), entry.group_id.clone());
+
+      let mut rate_rx = bandwidth::register(&app, &entry.id, entry.overrides.as_ref());
+      let result = run_ytdlp_download(app.clone(), entry.clone(), &mut rate_rx).await;
+      bandwidth::unregister(&app, &entry.id);
+      ACTIVE_DOWNLOADS.lock().unwrap().remove(&entry.id);
+
+      let item_result = match result {
+        Ok(DownloadOutcome::Paused) => {
+          park_download(&tx, &app, entry);
+          return;
         }
-      }
+        Ok(DownloadOutcome::Retry { code, delay }) => {
+          schedule_retry(&tx, &app, entry, code, delay);
+          return;
+        }
+        Ok(DownloadOutcome::Wait { starts_at, delay }) => {
+          schedule_wait(&tx, &app, entry, starts_at, delay);
+          return;
+        }
+        Ok(DownloadOutcome::Completed { destination }) => {
+          record_subscription_download(&app, &entry.id, destination.as_deref());
+          // Playlist jobs already ran the hook for each of their items as they finished.
+          let hook = if entry.direct_playlist {
+            Ok(())
+          } else {
+            run_post_download_hook(&app, &entry, destination.as_deref(), 0).await
+          };
+          match hook {
+            Ok(()) => {
+              let _ = app.emit(
+                "media_complete",
+                MediaProgressComplete {
+                  id: entry.id.clone(),
+                  group_id: entry.group_id.clone(),
+                },
+              );
+              ItemResult::Succeeded {
+                bytes: destination
+                  .and_then(|path| std::fs::metadata(path).ok())
+                  .map(|meta| meta.len())
+                  .unwrap_or(0),
+              }
+            }
+            Err(e) => {
+              let _ = app.emit(
+                "media_fatal",
+                MediaFatalPayload::internal(
+                  entry.group_id.clone(),
+                  entry.id.clone(),
+                  e.to_string(),
+                  None,
+                ),
+              );
+              ItemResult::Failed {
+                id: entry.id.clone(),
+                code: Some(HOOK_FAILED_CODE.to_string()),
+              }
+            }
+          }
+        }
+        Ok(DownloadOutcome::AlreadyDownloaded) => {
+          let _ = app.emit(
+            "media_already_downloaded",
+            MediaProgressComplete {
+              id: entry.id.clone(),
+              group_id: entry.group_id.clone(),
+            },
+          );
+          ItemResult::Skipped
+        }
+        Ok(DownloadOutcome::Cancelled) => ItemResult::Cancelled,
+        Err(e) => {
+          tracing::warn!(
+            download_id = %entry.id,
+            group_id = %entry.group_id,
+            error = %e,
+            "Failed to run ytdlp download",
+          );
+          if should_report_to_sentry(&e) {
+            sentry::capture_error(&e);
+          }
+          if let YtdlpDownloadError::NonZeroExit(exit, _) = &e {
+            // The download already failed, so the hook's failure policy doesn't matter here.
+            if !entry.direct_playlist {
+              let _ = run_post_download_hook(&app, &entry, None, *exit).await;
+            }
+          }
+          ItemResult::Failed {
+            id: entry.id.clone(),
+            code: e.diagnostic_code().map(str::to_string),
+          }
+        }
+      };
+
+      finish_download(&tx, &app, &entry, item_result);
     },
   )
 }
 
+/// Queues the downloads journaled by the previous session again. They stay flagged as restored
+/// so the frontend can list them once it has loaded.
+pub fn restore_downloads(app: &AppHandle) {
+  let restored = app.state::<SharedDownloadQueue>().load().restored();
+  let mut groups: IndexMap<String, Vec<DownloadEntry>> = IndexMap::new();
+  for queued in restored {
+    groups
+      .entry(queued.group_id.clone())
+      .or_default()
+      .push(DownloadEntry::from(queued));
+  }
+
+  let sender = app.state::<DownloadSender>();
+  for (group_id, entries) in groups {
+    tracing::info!(group_id = %group_id, count = entries.len(), "Restoring journaled downloads");
+    ensure_group_running(&group_id);
+    let _ = sender
+      .0
+      .send(DispatchRequest::Pipeline(DownloadRequest::Restore {
+        group_id,
+        entries,
+      }));
+  }
+}
+
+pub fn pause_download(id: &str) {
+  set_item_control(id, ItemControl::Pause);
+}
+
+/// Stops a livestream recording gracefully. Recordings that did not start yet are cancelled.
+pub fn stop_download(
+  tx: &UnboundedSender<DispatchRequest<DownloadRequest>>,
+  app: &AppHandle,
+  id: &str,
+) {
+  set_item_control(id, ItemControl::Stop);
+  let parked = PAUSED_DOWNLOADS.lock().unwrap().remove(id);
+  if let Some(entry) = parked {
+    finish_download(tx, app, &entry, ItemResult::Cancelled);
+  }
+}
+
+/// Returns `true` when a paused download was handed back to the dispatcher.
+/// Downloads that were paused before they started simply run when their turn comes.
+pub fn resume_download(tx: &UnboundedSender<DispatchRequest<DownloadRequest>>, id: &str) -> bool {
+  set_item_control(id, ItemControl::Run);
+  let Some(entry) = PAUSED_DOWNLOADS.lock().unwrap().remove(id) else {
+    return false;
+  };
+  let _ = tx.send(DispatchRequest::Pipeline(DownloadRequest::Resume {
+    entries: vec![entry],
+  }));
+  true
+}
+
+pub fn cancel_download(
+  tx: &UnboundedSender<DispatchRequest<DownloadRequest>>,
+  app: &AppHandle,
+  id: &str,
+) {
+  set_item_control(id, ItemControl::Cancel);
+  let parked = PAUSED_DOWNLOADS.lock().unwrap().remove(id);
+  if let Some(entry) = parked {
+    finish_download(tx, app, &entry, ItemResult::Cancelled);
+  }
+}
+
+pub fn cancel_paused_group(
+  tx: &UnboundedSender<DispatchRequest<DownloadRequest>>,
+  app: &AppHandle,
+  group_id: &str,
+) {
+  let ids: Vec<String> = PAUSED_DOWNLOADS
+    .lock()
+    .unwrap()
+    .values()
+    .filter(|entry| entry.group_id == group_id)
+    .map(|entry| entry.id.clone())
+    .collect();
+  for id in ids {
+    cancel_download(tx, app, &id);
+  }
+}
+
+/// Closes the limiter and pauses every running download.
+/// Returns the `(group_id, id)` pairs that were paused.
+pub fn pause_all_downloads(limiter: &DynamicSemaphore) -> Vec<(String, String)> {
+  limiter.pause();
+  let active: Vec<(String, String)> = ACTIVE_DOWNLOADS
+    .lock()
+    .unwrap()
+    .iter()
+    .map(|(id, group_id)| (group_id.clone(), id.clone()))
+    .collect();
+  for (_, id) in &active {
+    pause_download(id);
+  }
+  active
+}
+
+/// Reopens the limiter and requeues every paused download.
+/// Returns the `(group_id, id)` pairs that were resumed.
+pub fn resume_all_downloads(
+  tx: &UnboundedSender<DispatchRequest<DownloadRequest>>,
+  limiter: &DynamicSemaphore,
+) -> Vec<(String, String)> {
+  limiter.resume();
+  let paused: Vec<(String, String)> = PAUSED_DOWNLOADS
+    .lock()
+    .unwrap()
+    .values()
+    .map(|entry| (entry.group_id.clone(), entry.id.clone()))
+    .collect();
+  for (_, id) in &paused {
+    resume_download(tx, id);
+  }
+  paused
+}
+
+fn park_download(
+  tx: &UnboundedSender<DispatchRequest<DownloadRequest>>,
+  app: &AppHandle,
+  entry: DownloadEntry,
+) {
+  let id = entry.id.clone();
+  PAUSED_DOWNLOADS.lock().unwrap().insert(id.clone(), entry);
+
+  // A resume or cancel may have arrived while the download was shutting down.
+  match item_control(&id) {
+    ItemControl::Pause => {}
+    ItemControl::Run => {
+      resume_download(tx, &id);
+    }
+    ItemControl::Cancel | ItemControl::Stop => cancel_download(tx, app, &id),
+  }
+}
+
+/// Requeues a download after `delay`. The permit is released while waiting, and a pause or
+/// cancel that arrives in the meantime is picked up once the entry is dispatched again.
+fn schedule_retry(
+  tx: &UnboundedSender<DispatchRequest<DownloadRequest>>,
+  app: &AppHandle,
+  mut entry: DownloadEntry,
+  code: String,
+  delay: Duration,
+) {
+  entry.retries += 1;
+  tracing::info!(
+    download_id = %entry.id,
+    group_id = %entry.group_id,
+    code = %code,
+    attempt = entry.retries,
+    delay_ms = delay.as_millis() as u64,
+    "Retrying download after transient failure",
+  );
+  let max_retries = app.state::<SharedConfig>().load().retry.max_retries;
+  let _ = app.emit(
+    "media_retry",
+    MediaRetryPayload {
+      group_id: entry.group_id.clone(),
+      id: entry.id.clone(),
+      attempt: entry.retries,
+      max_retries,
+      delay_ms: delay.as_millis() as u64,
+      code,
+    },
+  );
+
+  let tx = tx.clone();
+  tauri::async_runtime::spawn(async move {
+    tokio::time::sleep(delay).await;
+    let _ = tx.send(DispatchRequest::Pipeline(DownloadRequest::Resume {
+      entries: vec![entry],
+    }));
+  });
+}
+
+/// Requeues a download that waits for its premiere or stream after `delay`, emitting
+/// `media_countdown` every minute in the meantime. Unlike retries, waiting has no limit.
+fn schedule_wait(
+  tx: &UnboundedSender<DispatchRequest<DownloadRequest>>,
+  app: &AppHandle,
+  entry: DownloadEntry,
+  starts_at: Option<i64>,
+  delay: Duration,
+) {
+  tracing::info!(
+    download_id = %entry.id,
+    group_id = %entry.group_id,
+    starts_at = ?starts_at,
+    delay_secs = delay.as_secs(),
+    "Waiting for scheduled media to start",
+  );
+
+  let tx = tx.clone();
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    let deadline = tokio::time::Instant::now() + delay;
+    loop {
+      let left = deadline.saturating_duration_since(tokio::time::Instant::now());
+      let _ = app.emit(
+        "media_countdown",
+        MediaCountdownPayload {
+          group_id: entry.group_id.clone(),
+          id: entry.id.clone(),
+          starts_at,
+          seconds_left: left.as_secs(),
+        },
+      );
+      // A cancel is picked up by the dispatcher, no need to wait for the start to see it.
+      let stopped = !matches!(
+        item_control(&entry.id),
+        ItemControl::Run | ItemControl::Pause
+      );
+      if left.is_zero() || stopped || !is_group_running(&entry.group_id) {
+        break;
+      }
+      tokio::time::sleep(left.min(COUNTDOWN_INTERVAL)).await;
+    }
+    let _ = tx.send(DispatchRequest::Pipeline(DownloadRequest::Resume {
+      entries: vec![entry],
+    }));
+  });
+}
+
+/// Counts `count` more items towards the group. Batches added to a group that is still
+/// downloading add to its counter, so `group_complete` waits for all of them.
+fn track_downloads(group_id: &str, count: usize) {
+  *DOWNLOAD_COUNTERS
+    .lock()
+    .unwrap()
+    .entry(group_id.to_string())
+    .or_default() += count;
+  DOWNLOAD_SUMMARIES.start(group_id);
+}
+
+/// Items per group that have not reported back yet, including paused and retrying ones.
+pub fn download_counters() -> HashMap<String, usize> {
+  DOWNLOAD_COUNTERS.lock().unwrap().clone()
+}
+
+pub fn paused_download_counts() -> HashMap<String, usize> {
+  let mut counts = HashMap::new();
+  for entry in PAUSED_DOWNLOADS.lock().unwrap().values() {
+    *counts.entry(entry.group_id.clone()).or_insert(0) += 1;
+  }
+  counts
+}
+
+fn finish_download(
+  tx: &UnboundedSender<DispatchRequest<DownloadRequest>>,
+  app: &AppHandle,
+  entry: &DownloadEntry,
+  result: ItemResult,
+) {
+  remove_item(&entry.id);
+  unjournal_entry(app, &entry.id);
+  DOWNLOAD_SUMMARIES.record(&entry.group_id, result);
+
+  let mut counters = DOWNLOAD_COUNTERS.lock().unwrap();
+  if let Some(cnt) = counters.get_mut(&entry.group_id) {
+    *cnt -= 1;
+    if *cnt == 0 {
+      counters.remove(&entry.group_id);
+      let _ = tx.send(DispatchRequest::Cleanup {
+        group_id: entry.group_id.clone(),
+      });
+      emit_group_complete(app, &entry.group_id);
+    }
+  }
+}
+
+/// Completes a cancelled group right away. Entries that were still queued are dropped by the
+/// dispatcher and never report back, so they are counted as cancelled here.
+pub fn complete_cancelled_downloads(app: &AppHandle, group_id: &str) {
+  let Some(remaining) = DOWNLOAD_COUNTERS.lock().unwrap().remove(group_id) else {
+    return;
+  };
+  for _ in 0..remaining {
+    DOWNLOAD_SUMMARIES.record(group_id, ItemResult::Cancelled);
+  }
+  emit_group_complete(app, group_id);
+}
+
+fn emit_group_complete(app: &AppHandle, group_id: &str) {
+  if let Err(e) = set_group_start(app, group_id, None) {
+    tracing::warn!(group_id = %group_id, error = %e, "Failed to clear scheduled group start");
+  }
+  if let Some(summary) = DOWNLOAD_SUMMARIES.finish(group_id) {
+    let _ = app.emit("group_complete", summary);
+  }
+}
+
+fn journal_entries(app: &AppHandle, entries: &[DownloadEntry]) {
+  let Some(queue) = app.try_state::<SharedDownloadQueue>() else {
+    return;
+  };
+  // Pending removals go first, so an id that is queued again right after finishing stays.
+  let removed = std::mem::take(&mut *UNJOURNALED.lock().unwrap());
+  let result = queue.update(app, |value| {
+    value.remove_many(&removed);
+    for entry in entries {
+      value.insert(QueuedDownload::from(entry));
+    }
+  });
+  if let Err(e) = result {
+    tracing::warn!(error = %e, "Failed to journal queued downloads");
+  }
+}
+
+fn unjournal_entry(app: &AppHandle, id: &str) {
+  let mut pending = UNJOURNALED.lock().unwrap();
+  pending.push(id.to_string());
+  if pending.len() > 1 {
+    // A flush is already scheduled.
+    return;
+  }
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    tokio::time::sleep(JOURNAL_FLUSH_DELAY).await;
+    flush_journal(&app);
+  });
+}
+
+/// Drops the finished downloads from the journal in one save.
+pub fn flush_journal(app: &AppHandle) {
+  let Some(queue) = app.try_state::<SharedDownloadQueue>() else {
+    return;
+  };
+  let removed = std::mem::take(&mut *UNJOURNALED.lock().unwrap());
+  if removed.is_empty() {
+    return;
+  }
+  if let Err(e) = queue.update(app, |value| value.remove_many(&removed)) {
+    tracing::warn!(error = %e, "Failed to remove finished downloads from journal");
+  }
+}
+
 fn should_report_to_sentry(err: &YtdlpDownloadError) -> bool {
   matches!(
     err,
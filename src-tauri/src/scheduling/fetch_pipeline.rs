@@ -1,16 +1,25 @@
 use crate::models::download::{DownloadOverrides, FormatOptions};
-use crate::models::payloads::MediaAddWithFormatPayload;
-use crate::models::{MediaAddPayload, MediaFatalPayload, PlaylistEntry};
+use crate::models::parsed::ParsedLivestream;
+use crate::models::payloads::{GroupKind, MediaAddWithFormatPayload};
+use crate::models::{MediaAddPayload, ParsedSingleVideo, PlaylistEntry};
+use crate::runners::ytdlp_args::resolve_format_size;
 use crate::runners::ytdlp_info::{run_ytdlp_info_fetch, YtdlpInfoFetchError};
+use crate::runners::ytdlp_info_batch::{run_ytdlp_info_batch, InfoBatchItem};
+use crate::runners::ytdlp_playlist_stream::run_ytdlp_playlist_stream;
+use crate::scheduling::group_state::is_group_running;
+use crate::scheduling::group_summary::{GroupSummaries, ItemResult};
+use crate::scheduling::host_limiter::{host_key, HostLimiter, HostPermit};
 use crate::{
   models::{ParsedMedia, ParsedPlaylist},
   scheduling::concurrency::DynamicSemaphore,
-  scheduling::dispatcher::{DispatchEntry, DispatchRequest, GenericDispatcher},
+  scheduling::dispatcher::{DispatchEntry, DispatchOptions, DispatchRequest, GenericDispatcher},
 };
+use crate::{FetchHostLimiter, SharedConfig};
+use indexmap::{IndexMap, IndexSet};
 use std::collections::HashMap;
 use std::sync::LazyLock;
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::mpsc::UnboundedSender;
 use uuid::Uuid;
 
@@ -30,16 +39,34 @@ pub enum FetchRequest {
     entries: Vec<PlaylistEntry>,
     overrides: Box<Option<DownloadOverrides>>,
   },
+  /// Lists a playlist line by line, emitting `playlist_entries` chunks as they arrive.
+  PlaylistStream {
+    group_id: String,
+    id: String,
+    url: String,
+    overrides: Box<Option<DownloadOverrides>>,
+  },
   Size {
     group_id: String,
     id: String,
     url: String,
     format: FormatOptions,
+    overrides: Box<Option<DownloadOverrides>>,
   },
   SizePlaylist {
     group_id: String,
     playlist: ParsedPlaylist,
     format: FormatOptions,
+    overrides: Box<Option<DownloadOverrides>>,
+  },
+  /// An info fetch a batch had no result for, fetched on its own so a failing URL only fails its
+  /// own entry. It already counts towards its group.
+  Unbatched {
+    group_id: String,
+    id: String,
+    url: String,
+    total: usize,
+    overrides: Box<Option<DownloadOverrides>>,
   },
 }
 
@@ -51,28 +78,66 @@ pub struct FetchEntry {
   pub total: usize,
   pub format: Option<FormatOptions>,
   pub overrides: Option<DownloadOverrides>,
+  pub streamed: bool,
+  /// Whether the entry was offered for batching, see `offer_for_batch`.
+  pub batchable: bool,
 }
 
 impl DispatchEntry for FetchEntry {
   fn group_id(&self) -> &String {
     &self.group_id
   }
+  fn id(&self) -> &String {
+    &self.id
+  }
   fn group_key(&self) -> Option<&String> {
     None
   }
   fn set_numbering(&mut self, _autonumber: u64, _group_autonumber: Option<u64>) {}
+  fn limit_key(&self) -> Option<String> {
+    host_key(&self.url)
+  }
 }
 
 static GROUP_COUNTERS: LazyLock<Mutex<HashMap<String, usize>>> =
   LazyLock::new(|| Mutex::new(HashMap::new()));
 
+static FETCH_SUMMARIES: LazyLock<GroupSummaries> =
+  LazyLock::new(|| GroupSummaries::new(GroupKind::Fetch));
+
+/// Recently fetched videos by URL and overrides, see `fetched_key`, so size lookups can be
+/// answered from their formats.
+static FETCHED_MEDIA: LazyLock<Mutex<IndexMap<String, ParsedSingleVideo>>> =
+  LazyLock::new(|| Mutex::new(IndexMap::new()));
+
+const FETCHED_MEDIA_LIMIT: usize = 1_000;
+
+/// Keys of recently fetched livestreams, see `fetched_key`, so their downloads record the stream.
+static FETCHED_LIVESTREAMS: LazyLock<Mutex<IndexSet<String>>> =
+  LazyLock::new(|| Mutex::new(IndexSet::new()));
+
+/// Info fetches waiting in the dispatcher queue, which a fetch that starts may take along into
+/// one yt-dlp run instead of starting yt-dlp for each of them.
+static BATCHABLE: LazyLock<Mutex<IndexMap<String, FetchEntry>>> =
+  LazyLock::new(|| Mutex::new(IndexMap::new()));
+
+/// URLs per batched info fetch. Small enough to keep several yt-dlp processes busy when many
+/// links are added at once.
+const INFO_BATCH_SIZE: usize = 10;
+
 pub fn setup_fetch_dispatcher(
   app: &AppHandle,
   sem: Arc<DynamicSemaphore>,
+  host_limiter: Arc<HostLimiter>,
 ) -> GenericDispatcher<FetchRequest> {
-  GenericDispatcher::start(
+  let options = DispatchOptions {
+    host_limiter,
+    ..DispatchOptions::default()
+  };
+  GenericDispatcher::start_with_options(
     app.clone(),
     sem,
+    options,
     expand_fetch_request,
     |tx: UnboundedSender<DispatchRequest<FetchRequest>>, app: AppHandle, entry: FetchEntry| async move {
       handle_fetch_entry(tx, app, entry).await;
@@ -88,14 +153,19 @@ fn expand_fetch_request(req: FetchRequest) -> Vec<FetchEntry> {
       url,
       overrides,
     } => {
-      vec![FetchEntry {
+      track_fetches(&group_id, 1);
+      let entries = vec![FetchEntry {
         group_id,
         id,
         url,
         total: 1,
         format: None,
         overrides: *overrides,
-      }]
+        streamed: false,
+        batchable: true,
+      }];
+      offer_for_batch(&entries);
+      entries
     }
     FetchRequest::Playlist {
       group_id,
@@ -103,11 +173,8 @@ fn expand_fetch_request(req: FetchRequest) -> Vec<FetchEntry> {
       overrides,
     } => {
       let total = entries.len();
-      GROUP_COUNTERS
-        .lock()
-        .unwrap()
-        .insert(group_id.clone(), total);
-      entries
+      track_fetches(&group_id, total);
+      let entries: Vec<FetchEntry> = entries
         .into_iter()
         .map(|e| FetchEntry {
           group_id: group_id.clone(),
@@ -116,14 +183,37 @@ fn expand_fetch_request(req: FetchRequest) -> Vec<FetchEntry> {
           total,
           format: None,
           overrides: *overrides.clone(),
+          streamed: false,
+          batchable: true,
         })
-        .collect()
+        .collect();
+      offer_for_batch(&entries);
+      entries
+    }
+    FetchRequest::PlaylistStream {
+      group_id,
+      id,
+      url,
+      overrides,
+    } => {
+      track_fetches(&group_id, 1);
+      vec![FetchEntry {
+        group_id,
+        id,
+        url,
+        total: 1,
+        format: None,
+        overrides: *overrides,
+        streamed: true,
+        batchable: false,
+      }]
     }
     FetchRequest::Size {
       group_id,
       id,
       url,
       format,
+      overrides,
     } => {
       vec![FetchEntry {
         group_id,
@@ -131,13 +221,16 @@ fn expand_fetch_request(req: FetchRequest) -> Vec<FetchEntry> {
         url,
         total: 1,
         format: Some(format),
-        overrides: None,
+        overrides: *overrides,
+        streamed: false,
+        batchable: false,
       }]
     }
     FetchRequest::SizePlaylist {
       group_id,
       playlist,
       format,
+      overrides,
     } => {
       let total = playlist.entries.len();
       playlist
@@ -149,10 +242,28 @@ fn expand_fetch_request(req: FetchRequest) -> Vec<FetchEntry> {
           url: e.video_url,
           total,
           format: Some(format.clone()),
-          overrides: None,
+          overrides: (*overrides).clone(),
+          streamed: false,
+          batchable: false,
         })
         .collect()
     }
+    FetchRequest::Unbatched {
+      group_id,
+      id,
+      url,
+      total,
+      overrides,
+    } => vec![FetchEntry {
+      group_id,
+      id,
+      url,
+      total,
+      format: None,
+      overrides: *overrides,
+      streamed: false,
+      batchable: false,
+    }],
   }
 }
 
@@ -160,6 +271,60 @@ async fn handle_fetch_entry(
   tx: UnboundedSender<DispatchRequest<FetchRequest>>,
   app: AppHandle,
   entry: FetchEntry,
+) {
+  if entry.streamed {
+    let FetchEntry {
+      group_id,
+      id,
+      url,
+      overrides,
+      ..
+    } = entry;
+    let result = run_ytdlp_playlist_stream(&app, &id, &group_id, &url, overrides.as_ref()).await;
+    let item_result = match result {
+      Ok(_) => ItemResult::Succeeded { bytes: 0 },
+      Err(e) => {
+        tracing::warn!(
+          fetch_id = %id,
+          group_id = %group_id,
+          url = %url,
+          error = %e,
+          "run_ytdlp_playlist_stream failed"
+        );
+        if should_report_to_sentry(&e) {
+          sentry::capture_error(&e);
+        }
+        ItemResult::Failed {
+          id,
+          code: e.diagnostic_code().map(str::to_string),
+        }
+      }
+    };
+    finish_fetch(&tx, &app, &group_id, item_result);
+    return;
+  }
+
+  if entry.batchable {
+    // Another fetch already took this entry along in its batch.
+    let limiter = app
+      .try_state::<FetchHostLimiter>()
+      .map(|limiter| limiter.0.clone());
+    let Some(batch) = take_batch(&entry, limiter.as_ref()) else {
+      return;
+    };
+    if batch.len() > 1 {
+      fetch_batch(&tx, &app, batch).await;
+      return;
+    }
+  }
+
+  fetch_single(&tx, &app, entry).await;
+}
+
+async fn fetch_single(
+  tx: &UnboundedSender<DispatchRequest<FetchRequest>>,
+  app: &AppHandle,
+  entry: FetchEntry,
 ) {
   let FetchEntry {
     group_id,
@@ -168,10 +333,26 @@ async fn handle_fetch_entry(
     total,
     format,
     overrides,
+    ..
   } = entry.clone();
+  // Size lookups refresh existing items and don't count towards the group.
+  let counts_towards_group = format.is_none();
+
+  if let Some(format) = &format {
+    if let Some(item) = resolve_size_locally(app, &id, &url, overrides.as_ref(), format) {
+      let payload = MediaAddWithFormatPayload {
+        group_id,
+        total,
+        item,
+        format: format.clone(),
+      };
+      let _ = app.emit("media_size", payload);
+      return;
+    }
+  }
 
   let result = run_ytdlp_info_fetch(
-    &app,
+    app,
     id.clone(),
     group_id.clone(),
     &url,
@@ -194,15 +375,101 @@ async fn handle_fetch_entry(
         sentry::capture_error(&e);
       }
 
-      None
+      // The fatal event has already been sent, only the group bookkeeping is left.
+      if counts_towards_group {
+        let failure = ItemResult::Failed {
+          id,
+          code: e.diagnostic_code().map(str::to_string),
+        };
+        finish_fetch(tx, app, &group_id, failure);
+      }
+      return;
     }
   };
 
-  match result {
+  let item_result = emit_fetched(tx, app, entry, result);
+  if counts_towards_group {
+    finish_fetch(tx, app, &group_id, item_result);
+  }
+}
+
+/// Fetches a batch taken by `take_batch` with one yt-dlp run. Entries it has no result for go
+/// back to the dispatcher to be fetched on their own, so a failing URL only fails its own entry
+/// and the retries stay within the fetch concurrency. Each entry's host permit is released once
+/// that entry is handled.
+async fn fetch_batch(
+  tx: &UnboundedSender<DispatchRequest<FetchRequest>>,
+  app: &AppHandle,
+  batch: Vec<(FetchEntry, Option<HostPermit>)>,
+) {
+  let items: Vec<InfoBatchItem> = batch
+    .iter()
+    .map(|(entry, _)| InfoBatchItem {
+      id: entry.id.clone(),
+      url: entry.url.clone(),
+    })
+    .collect();
+  let group_id = batch[0].0.group_id.clone();
+  let overrides = batch[0].0.overrides.clone();
+  let result = run_ytdlp_info_batch(app, &group_id, &items, overrides.as_ref()).await;
+  let mut fetched = match result {
+    Ok(fetched) => fetched,
+    Err(e) => {
+      tracing::warn!(size = items.len(), error = %e, "run_ytdlp_info_batch failed");
+      HashMap::new()
+    }
+  };
+  tracing::debug!(
+    size = items.len(),
+    fetched = fetched.len(),
+    "Fetched info batch"
+  );
+
+  for (entry, permit) in batch {
+    if !is_group_running(&entry.group_id) {
+      continue;
+    }
+    match fetched.remove(&entry.id) {
+      Some(media) => {
+        let item_result = emit_fetched(tx, app, entry, Some(media));
+        finish_fetch(tx, app, &group_id, item_result);
+      }
+      None => {
+        let _ = tx.send(DispatchRequest::Pipeline(FetchRequest::Unbatched {
+          group_id: entry.group_id,
+          id: entry.id,
+          url: entry.url,
+          total: entry.total,
+          overrides: Box::new(entry.overrides),
+        }));
+      }
+    }
+    drop(permit);
+  }
+}
+
+/// Sends fetched media to the frontend, queueing size lookups for playlist entries.
+fn emit_fetched(
+  tx: &UnboundedSender<DispatchRequest<FetchRequest>>,
+  app: &AppHandle,
+  entry: FetchEntry,
+  media: Option<ParsedMedia>,
+) -> ItemResult {
+  let FetchEntry {
+    group_id,
+    id,
+    url,
+    total,
+    format,
+    overrides,
+    ..
+  } = entry;
+  match media {
     Some(ParsedMedia::Single(single)) => {
+      remember_media(&url, overrides.as_ref(), &single);
       if let Some(format) = format {
         let payload = MediaAddWithFormatPayload {
-          group_id: group_id.clone(),
+          group_id,
           total,
           item: single,
           format,
@@ -210,30 +477,21 @@ async fn handle_fetch_entry(
         let _ = app.emit("media_size", payload);
       } else {
         let payload = MediaAddPayload {
-          group_id: group_id.clone(),
+          group_id,
           total,
           item: single,
         };
         let _ = app.emit("media_add", payload);
       }
-
-      let mut counters = GROUP_COUNTERS.lock().unwrap();
-      if let Some(cnt) = counters.get_mut(&group_id) {
-        *cnt -= 1;
-        if *cnt == 0 {
-          counters.remove(&group_id);
-          let _ = tx.send(DispatchRequest::Cleanup {
-            group_id: group_id.clone(),
-          });
-        }
-      }
+      ItemResult::Succeeded { bytes: 0 }
     }
     Some(ParsedMedia::Playlist(pl)) => {
       if let Some(format) = format {
         let _ = tx.send(DispatchRequest::Pipeline(FetchRequest::SizePlaylist {
-          group_id: group_id.clone(),
+          group_id,
           playlist: pl,
           format,
+          overrides: Box::new(overrides),
         }));
       } else {
         let payload = MediaAddPayload {
@@ -243,28 +501,208 @@ async fn handle_fetch_entry(
         };
         let _ = app.emit("media_add", payload);
       }
+      ItemResult::Succeeded { bytes: 0 }
+    }
+    Some(ParsedMedia::Livestream(live)) => {
+      // A stream that is still growing has no size to report.
+      if format.is_some() {
+        tracing::debug!(fetch_id = %id, "Skipping size lookup for a livestream");
+        return ItemResult::Failed { id, code: None };
+      }
+      remember_livestream(&url, overrides.as_ref(), &live);
+      let payload = MediaAddPayload {
+        group_id,
+        total,
+        item: live,
+      };
+      let _ = app.emit("media_add", payload);
+      ItemResult::Succeeded { bytes: 0 }
+    }
+    None => ItemResult::Failed { id, code: None },
+  }
+}
+
+/// Registers info fetches that may run as part of another entry's batch.
+fn offer_for_batch(entries: &[FetchEntry]) {
+  let mut batchable = BATCHABLE.lock().unwrap();
+  for entry in entries {
+    batchable.insert(entry.id.clone(), entry.clone());
+  }
+}
+
+/// Takes `entry` and up to `INFO_BATCH_SIZE - 1` other waiting info fetches of the same group,
+/// and so the same priority, with the same overrides out of the batch pool. Entries are only
+/// taken along while their host has capacity, each holding its own host permit; the permit of
+/// `entry` is held by the dispatcher. Returns `None` when another fetch already took `entry`.
+fn take_batch(
+  entry: &FetchEntry,
+  limiter: Option<&Arc<HostLimiter>>,
+) -> Option<Vec<(FetchEntry, Option<HostPermit>)>> {
+  let mut batchable = BATCHABLE.lock().unwrap();
+  let entry = batchable.shift_remove(&entry.id)?;
+  batchable.retain(|_, pending| is_group_running(&pending.group_id));
+
+  let key = overrides_key(entry.overrides.as_ref());
+  let mut batch = vec![(entry, None)];
+  let candidates: Vec<String> = batchable
+    .values()
+    .filter(|pending| {
+      pending.group_id == batch[0].0.group_id && overrides_key(pending.overrides.as_ref()) == key
+    })
+    .map(|pending| pending.id.clone())
+    .collect();
+  for id in candidates {
+    if batch.len() >= INFO_BATCH_SIZE {
+      break;
     }
-    Some(ParsedMedia::Livestream(_)) => {
-      let payload =
-        MediaFatalPayload::internal(group_id.clone(), id, "Livestreams unsupported".into(), None);
-      let _ = app.emit("media_fatal", payload);
-      let mut counters = GROUP_COUNTERS.lock().unwrap();
-      if let Some(cnt) = counters.get_mut(&group_id) {
-        *cnt -= 1;
-        if *cnt == 0 {
-          counters.remove(&group_id);
-          let _ = tx.send(DispatchRequest::Cleanup {
-            group_id: group_id.clone(),
-          });
+    let host = batchable.get(&id).and_then(|pending| pending.limit_key());
+    let permit = match (limiter, host) {
+      (Some(limiter), Some(host)) => {
+        if !limiter.has_capacity(&host) {
+          continue;
         }
+        Some(limiter.acquire(host))
       }
+      _ => None,
+    };
+    if let Some(pending) = batchable.shift_remove(&id) {
+      batch.push((pending, permit));
     }
-    None => {
-      // Do nothing if no parsed result is returned. The events have already been sent.
+  }
+  Some(batch)
+}
+
+fn overrides_key(overrides: Option<&DownloadOverrides>) -> String {
+  serde_json::to_string(&overrides).unwrap_or_default()
+}
+
+/// Key of `url` in `FETCHED_MEDIA`. Overrides like cookies or a proxy can change the formats a
+/// site offers, so fetches with different overrides are kept apart.
+fn fetched_key(url: &str, overrides: Option<&DownloadOverrides>) -> String {
+  format!("{}\n{url}", overrides_key(overrides))
+}
+
+/// Answers a size lookup from the formats of an earlier fetch of `url` with the same overrides,
+/// if they are complete enough to do so.
+fn resolve_size_locally(
+  app: &AppHandle,
+  id: &str,
+  url: &str,
+  overrides: Option<&DownloadOverrides>,
+  format: &FormatOptions,
+) -> Option<ParsedSingleVideo> {
+  let mut item = FETCHED_MEDIA
+    .lock()
+    .unwrap()
+    .get(&fetched_key(url, overrides))
+    .cloned()?;
+  let filesize = resolve_format_size(
+    &item.formats,
+    format,
+    &app.state::<SharedConfig>().load().output,
+  )?;
+  item.id = id.to_string();
+  item.filesize = Some(filesize);
+  Some(item)
+}
+
+fn remember_media(url: &str, overrides: Option<&DownloadOverrides>, single: &ParsedSingleVideo) {
+  let mut fetched = FETCHED_MEDIA.lock().unwrap();
+  let urls = [Some(url), single.url.as_deref()];
+  for url in urls.into_iter().flatten() {
+    let key = fetched_key(url, overrides);
+    fetched.shift_remove(&key);
+    fetched.insert(key, single.clone());
+  }
+  while fetched.len() > FETCHED_MEDIA_LIMIT {
+    fetched.shift_remove_index(0);
+  }
+}
+
+fn remember_livestream(url: &str, overrides: Option<&DownloadOverrides>, live: &ParsedLivestream) {
+  let mut fetched = FETCHED_LIVESTREAMS.lock().unwrap();
+  for url in [Some(url), live.url.as_deref()].into_iter().flatten() {
+    let key = fetched_key(url, overrides);
+    fetched.shift_remove(&key);
+    fetched.insert(key);
+  }
+  while fetched.len() > FETCHED_MEDIA_LIMIT {
+    fetched.shift_remove_index(0);
+  }
+}
+
+/// Whether an earlier fetch of `url` with the same overrides found a livestream.
+pub fn is_fetched_livestream(url: &str, overrides: Option<&DownloadOverrides>) -> bool {
+  FETCHED_LIVESTREAMS
+    .lock()
+    .unwrap()
+    .contains(&fetched_key(url, overrides))
+}
+
+/// When an earlier fetch of `url` with the same overrides said it premieres or goes live.
+pub fn scheduled_start(url: &str, overrides: Option<&DownloadOverrides>) -> Option<i64> {
+  FETCHED_MEDIA
+    .lock()
+    .unwrap()
+    .get(&fetched_key(url, overrides))?
+    .scheduled_start
+}
+
+/// Counts `count` more fetches towards the group's `group_complete`. A group that gets more work
+/// while it is still being fetched, e.g. an expansion right after its initial fetch, completes
+/// once everything was fetched.
+fn track_fetches(group_id: &str, count: usize) {
+  *GROUP_COUNTERS
+    .lock()
+    .unwrap()
+    .entry(group_id.to_string())
+    .or_default() += count;
+  FETCH_SUMMARIES.start(group_id);
+}
+
+/// Info fetches per group that have not finished yet.
+pub fn fetch_counters() -> HashMap<String, usize> {
+  GROUP_COUNTERS.lock().unwrap().clone()
+}
+
+fn finish_fetch(
+  tx: &UnboundedSender<DispatchRequest<FetchRequest>>,
+  app: &AppHandle,
+  group_id: &str,
+  result: ItemResult,
+) {
+  FETCH_SUMMARIES.record(group_id, result);
+
+  let mut counters = GROUP_COUNTERS.lock().unwrap();
+  if let Some(cnt) = counters.get_mut(group_id) {
+    *cnt -= 1;
+    if *cnt == 0 {
+      counters.remove(group_id);
+      let _ = tx.send(DispatchRequest::Cleanup {
+        group_id: group_id.to_string(),
+      });
+      emit_group_complete(app, group_id);
     }
   }
 }
 
+/// Completes a cancelled playlist expansion, counting entries that never ran as cancelled.
+pub fn complete_cancelled_fetches(app: &AppHandle, group_id: &str) {
+  let Some(remaining) = GROUP_COUNTERS.lock().unwrap().remove(group_id) else {
+    return;
+  };
+  for _ in 0..remaining {
+    FETCH_SUMMARIES.record(group_id, ItemResult::Cancelled);
+  }
+  emit_group_complete(app, group_id);
+}
+
+fn emit_group_complete(app: &AppHandle, group_id: &str) {
+  if let Some(summary) = FETCH_SUMMARIES.finish(group_id) {
+    let _ = app.emit("group_complete", summary);
+  }
+}
+
 fn should_report_to_sentry(err: &YtdlpInfoFetchError) -> bool {
   matches!(
     err,
@@ -1,10 +1,20 @@
-use std::collections::HashMap;
+use crate::models::download::QueuePriority;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{LazyLock, Mutex as StdMutex};
 use tokio::sync::watch;
 
 static RUNNING_GROUPS: LazyLock<StdMutex<HashMap<String, watch::Sender<bool>>>> =
   LazyLock::new(|| StdMutex::new(HashMap::new()));
 
+/// Groups that were cleaned up after finishing, most recent last, for introspection.
+static FINISHED_GROUPS: LazyLock<StdMutex<VecDeque<String>>> =
+  LazyLock::new(|| StdMutex::new(VecDeque::new()));
+
+const FINISHED_GROUPS_KEPT: usize = 100;
+
+static GROUP_PRIORITIES: LazyLock<StdMutex<HashMap<String, QueuePriority>>> =
+  LazyLock::new(|| StdMutex::new(HashMap::new()));
+
 pub fn ensure_group_running(group_id: &str) {
   let mut map = RUNNING_GROUPS.lock().unwrap();
   if let Some(tx) = map.get(group_id) {
@@ -26,7 +36,46 @@ pub fn cancel_group(group_id: &str) {
 }
 
 pub fn remove_group(group_id: &str) {
-  RUNNING_GROUPS.lock().unwrap().remove(group_id);
+  let removed = RUNNING_GROUPS.lock().unwrap().remove(group_id);
+  GROUP_PRIORITIES.lock().unwrap().remove(group_id);
+  if removed.is_some_and(|tx| *tx.borrow()) {
+    let mut finished = FINISHED_GROUPS.lock().unwrap();
+    finished.retain(|gid| gid != group_id);
+    finished.push_back(group_id.to_string());
+    if finished.len() > FINISHED_GROUPS_KEPT {
+      finished.pop_front();
+    }
+  }
+}
+
+/// Known groups and whether each is still running (`false` once cancelled).
+pub fn group_states() -> Vec<(String, bool)> {
+  RUNNING_GROUPS
+    .lock()
+    .unwrap()
+    .iter()
+    .map(|(group_id, tx)| (group_id.clone(), *tx.borrow()))
+    .collect()
+}
+
+pub fn finished_groups() -> Vec<String> {
+  FINISHED_GROUPS.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn set_group_priority(group_id: &str, priority: QueuePriority) {
+  GROUP_PRIORITIES
+    .lock()
+    .unwrap()
+    .insert(group_id.to_string(), priority);
+}
+
+pub fn group_priority(group_id: &str) -> QueuePriority {
+  GROUP_PRIORITIES
+    .lock()
+    .unwrap()
+    .get(group_id)
+    .copied()
+    .unwrap_or_default()
 }
 
 pub fn subscribe_group(group_id: &str) -> watch::Receiver<bool> {
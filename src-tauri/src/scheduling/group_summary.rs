@@ -0,0 +1,142 @@
+use crate::models::payloads::{GroupCompletePayload, GroupFailure, GroupKind};
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::time::Instant;
+
+pub enum ItemResult {
+  Succeeded {
+    bytes: u64,
+  },
+  Failed {
+    id: String,
+    code: Option<String>,
+  },
+  /// Nothing to do, e.g. because the media is in the download archive.
+  Skipped,
+  Cancelled,
+}
+
+struct GroupTally {
+  started_at: Instant,
+  succeeded: usize,
+  failed: usize,
+  skipped: usize,
+  cancelled: usize,
+  total_bytes: u64,
+  failures: Vec<GroupFailure>,
+}
+
+/// Per-group outcome counts of one pipeline, turned into a `group_complete` payload once the
+/// group is done.
+pub struct GroupSummaries {
+  kind: GroupKind,
+  tallies: StdMutex<HashMap<String, GroupTally>>,
+}
+
+impl GroupSummaries {
+  pub fn new(kind: GroupKind) -> Self {
+    Self {
+      kind,
+      tallies: StdMutex::new(HashMap::new()),
+    }
+  }
+
+  /// Starts tracking a group. Batches added to a group that is still tracked keep its start time.
+  pub fn start(&self, group_id: &str) {
+    self
+      .tallies
+      .lock()
+      .unwrap()
+      .entry(group_id.to_string())
+      .or_insert_with(|| GroupTally {
+        started_at: Instant::now(),
+        succeeded: 0,
+        failed: 0,
+        skipped: 0,
+        cancelled: 0,
+        total_bytes: 0,
+        failures: Vec::new(),
+      });
+  }
+
+  /// Records the outcome of one item. Outcomes for groups that are not tracked are ignored.
+  pub fn record(&self, group_id: &str, result: ItemResult) {
+    let mut tallies = self.tallies.lock().unwrap();
+    let Some(tally) = tallies.get_mut(group_id) else {
+      return;
+    };
+    match result {
+      ItemResult::Succeeded { bytes } => {
+        tally.succeeded += 1;
+        tally.total_bytes += bytes;
+      }
+      ItemResult::Failed { id, code } => {
+        tally.failed += 1;
+        tally.failures.push(GroupFailure { id, code });
+      }
+      ItemResult::Skipped => tally.skipped += 1,
+      ItemResult::Cancelled => tally.cancelled += 1,
+    }
+  }
+
+  /// Stops tracking the group and returns its summary.
+  pub fn finish(&self, group_id: &str) -> Option<GroupCompletePayload> {
+    let tally = self.tallies.lock().unwrap().remove(group_id)?;
+    Some(GroupCompletePayload {
+      group_id: group_id.to_string(),
+      kind: self.kind,
+      succeeded: tally.succeeded,
+      failed: tally.failed,
+      skipped: tally.skipped,
+      cancelled: tally.cancelled,
+      total_bytes: tally.total_bytes,
+      elapsed_ms: tally.started_at.elapsed().as_millis() as u64,
+      failures: tally.failures,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn summarizes_recorded_outcomes() {
+    let summaries = GroupSummaries::new(GroupKind::Download);
+    summaries.start("group");
+    summaries.record("group", ItemResult::Succeeded { bytes: 100 });
+    summaries.record("group", ItemResult::Succeeded { bytes: 50 });
+    summaries.record(
+      "group",
+      ItemResult::Failed {
+        id: "item-3".into(),
+        code: Some("server5xx".into()),
+      },
+    );
+    summaries.record("group", ItemResult::Skipped);
+    summaries.record("group", ItemResult::Cancelled);
+
+    let summary = summaries.finish("group").expect("summary");
+    assert_eq!(summary.kind, GroupKind::Download);
+    assert_eq!(summary.succeeded, 2);
+    assert_eq!(summary.failed, 1);
+    assert_eq!(summary.skipped, 1);
+    assert_eq!(summary.cancelled, 1);
+    assert_eq!(summary.total_bytes, 150);
+    assert_eq!(
+      summary.failures,
+      vec![GroupFailure {
+        id: "item-3".into(),
+        code: Some("server5xx".into()),
+      }]
+    );
+    assert!(summaries.finish("group").is_none());
+  }
+
+  #[test]
+  fn ignores_untracked_groups() {
+    let summaries = GroupSummaries::new(GroupKind::Fetch);
+    summaries.record("group", ItemResult::Cancelled);
+    assert!(summaries.finish("group").is_none());
+  }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// Diagnostic codes that mean a site is pushing back and we should slow down.
+pub const THROTTLING_CODES: [&str; 2] = ["rateLimited429", "signInRequiredForBotDetection"];
+
+/// How often `recover` should be called.
+pub const RECOVERY_TICK: Duration = Duration::from_secs(10);
+
+/// Parallel jobs often hit a rate limit at the same time; only the first one halves the limit.
+const DECREASE_COOLDOWN: Duration = Duration::from_secs(10);
+
+/// Quiet period after which a throttled key gets one more slot back.
+const RECOVERY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Caps how many entries sharing a key (an extractor or host) run at the same time.
+///
+/// This sits on top of the dispatcher semaphore: a permit is only taken for an entry whose key
+/// still has capacity. A limit of `0` means unlimited.
+///
+/// Keys that report throttling get their own limit, adjusted additive-increase /
+/// multiplicative-decrease: halved on every throttling signal and raised by one after each quiet
+/// period until the normal limit applies again.
+pub struct HostLimiter {
+  limit: AtomicUsize,
+  active: StdMutex<HashMap<String, usize>>,
+  throttled: StdMutex<HashMap<String, ThrottleState>>,
+  released: Notify,
+}
+
+struct ThrottleState {
+  limit: usize,
+  ceiling: usize,
+  changed_at: Instant,
+}
+
+pub struct HostPermit {
+  limiter: Arc<HostLimiter>,
+  key: String,
+}
+
+impl HostLimiter {
+  pub fn new(limit: usize) -> Self {
+    Self {
+      limit: AtomicUsize::new(limit),
+      active: StdMutex::new(HashMap::new()),
+      throttled: StdMutex::new(HashMap::new()),
+      released: Notify::new(),
+    }
+  }
+
+  pub fn set_limit(&self, limit: usize) {
+    self.limit.store(limit, Ordering::SeqCst);
+    self.released.notify_one();
+  }
+
+  pub fn has_capacity(&self, key: &str) -> bool {
+    let limit = self.effective_limit(key);
+    limit == 0 || self.active_count(key) < limit
+  }
+
+  pub fn effective_limit(&self, key: &str) -> usize {
+    match self.throttled.lock().unwrap().get(key) {
+      Some(state) => state.limit,
+      None => self.limit.load(Ordering::SeqCst),
+    }
+  }
+
+  /// Halves the limit for `key` after it reported rate limiting or bot detection.
+  pub fn throttle(&self, key: &str) {
+    self.throttle_at(key, Instant::now());
+  }
+
+  /// Gives throttled keys one slot back after a quiet period.
+  pub fn recover(&self) {
+    self.recover_at(Instant::now());
+  }
+
+  fn throttle_at(&self, key: &str, now: Instant) {
+    let active = self.active_count(key);
+    let base = self.limit.load(Ordering::SeqCst);
+    let mut throttled = self.throttled.lock().unwrap();
+    let limit = match throttled.get_mut(key) {
+      Some(state) => {
+        if now.duration_since(state.changed_at) < DECREASE_COOLDOWN {
+          return;
+        }
+        state.limit = (state.limit / 2).max(1);
+        state.changed_at = now;
+        state.limit
+      }
+      None => {
+        let current = if base == 0 { active.max(1) } else { base };
+        let limit = (current / 2).max(1);
+        throttled.insert(
+          key.to_string(),
+          ThrottleState {
+            limit,
+            ceiling: current,
+            changed_at: now,
+          },
+        );
+        limit
+      }
+    };
+    tracing::info!(key = %key, limit, "Throttling downloads after rate limiting");
+  }
+
+  fn recover_at(&self, now: Instant) {
+    let mut recovered = false;
+    self.throttled.lock().unwrap().retain(|key, state| {
+      if now.duration_since(state.changed_at) < RECOVERY_INTERVAL {
+        return true;
+      }
+      state.limit += 1;
+      state.changed_at = now;
+      recovered = true;
+      tracing::debug!(key = %key, limit = state.limit, "Recovering throttled downloads");
+      state.limit < state.ceiling
+    });
+    if recovered {
+      self.released.notify_one();
+    }
+  }
+
+  fn active_count(&self, key: &str) -> usize {
+    self.active.lock().unwrap().get(key).copied().unwrap_or(0)
+  }
+
+  pub fn acquire(self: &Arc<Self>, key: String) -> HostPermit {
+    *self.active.lock().unwrap().entry(key.clone()).or_insert(0) += 1;
+    HostPermit {
+      limiter: self.clone(),
+      key,
+    }
+  }
+
+  /// Resolves once a permit was released or the limit changed.
+  pub async fn released(&self) {
+    self.released.notified().await;
+  }
+}
+
+impl Drop for HostPermit {
+  fn drop(&mut self) {
+    {
+      let mut active = self.limiter.active.lock().unwrap();
+      if let Some(count) = active.get_mut(&self.key) {
+        *count = count.saturating_sub(1);
+        if *count == 0 {
+          active.remove(&self.key);
+        }
+      }
+    }
+    self.limiter.released.notify_one();
+  }
+}
+
+/// Normalizes a URL to the host used as a limit key, e.g. `www.youtube.com` → `youtube.com`.
+pub fn host_key(url: &str) -> Option<String> {
+  let parsed = tauri::Url::parse(url).ok()?;
+  let host = parsed.host_str()?.to_lowercase();
+  let host = host
+    .strip_prefix("www.")
+    .or_else(|| host.strip_prefix("m."))
+    .unwrap_or(&host);
+  Some(host.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn caps_entries_per_key() {
+    let limiter = Arc::new(HostLimiter::new(1));
+    let permit = limiter.acquire("youtube.com".into());
+    assert!(!limiter.has_capacity("youtube.com"));
+    assert!(limiter.has_capacity("vimeo.com"));
+
+    drop(permit);
+    assert!(limiter.has_capacity("youtube.com"));
+  }
+
+  #[test]
+  fn zero_limit_is_unlimited() {
+    let limiter = Arc::new(HostLimiter::new(0));
+    let _a = limiter.acquire("youtube.com".into());
+    let _b = limiter.acquire("youtube.com".into());
+    assert!(limiter.has_capacity("youtube.com"));
+  }
+
+  #[test]
+  fn throttling_halves_and_recovers_additively() {
+    let limiter = HostLimiter::new(8);
+    let start = Instant::now();
+
+    limiter.throttle_at("youtube.com", start);
+    assert_eq!(limiter.effective_limit("youtube.com"), 4);
+    assert_eq!(limiter.effective_limit("vimeo.com"), 8);
+
+    // Signals from jobs that failed together only count once.
+    limiter.throttle_at("youtube.com", start + Duration::from_secs(1));
+    assert_eq!(limiter.effective_limit("youtube.com"), 4);
+
+    limiter.throttle_at("youtube.com", start + DECREASE_COOLDOWN);
+    assert_eq!(limiter.effective_limit("youtube.com"), 2);
+
+    let mut now = start + DECREASE_COOLDOWN;
+    limiter.recover_at(now + Duration::from_secs(1));
+    assert_eq!(limiter.effective_limit("youtube.com"), 2);
+
+    for expected in [3, 4, 5, 6, 7] {
+      now += RECOVERY_INTERVAL;
+      limiter.recover_at(now);
+      assert_eq!(limiter.effective_limit("youtube.com"), expected);
+    }
+    now += RECOVERY_INTERVAL;
+    limiter.recover_at(now);
+    assert_eq!(limiter.effective_limit("youtube.com"), 8);
+  }
+
+  #[test]
+  fn throttling_an_unlimited_key_uses_its_active_count() {
+    let limiter = Arc::new(HostLimiter::new(0));
+    let _permits: Vec<_> = (0..4)
+      .map(|_| limiter.acquire("youtube.com".into()))
+      .collect();
+
+    limiter.throttle_at("youtube.com", Instant::now());
+    assert_eq!(limiter.effective_limit("youtube.com"), 2);
+    assert!(!limiter.has_capacity("youtube.com"));
+  }
+
+  #[test]
+  fn host_key_strips_common_prefixes() {
+    assert_eq!(
+      host_key("https://www.youtube.com/watch?v=abc").as_deref(),
+      Some("youtube.com")
+    );
+    assert_eq!(
+      host_key("https://m.YouTube.com/watch?v=abc").as_deref(),
+      Some("youtube.com")
+    );
+    assert_eq!(host_key("not a url"), None);
+  }
+}
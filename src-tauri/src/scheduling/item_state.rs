@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex as StdMutex};
+use tokio::sync::watch;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemControl {
+  Run,
+  Pause,
+  Cancel,
+  /// Ends a livestream recording and keeps what was recorded.
+  Stop,
+}
+
+static ITEM_CONTROLS: LazyLock<StdMutex<HashMap<String, watch::Sender<ItemControl>>>> =
+  LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+pub fn set_item_control(id: &str, control: ItemControl) {
+  let mut map = ITEM_CONTROLS.lock().unwrap();
+  if let Some(tx) = map.get(id) {
+    tx.send_replace(control);
+  } else {
+    let (tx, _rx) = watch::channel(control);
+    map.insert(id.to_string(), tx);
+  }
+}
+
+pub fn item_control(id: &str) -> ItemControl {
+  ITEM_CONTROLS
+    .lock()
+    .unwrap()
+    .get(id)
+    .map(|tx| *tx.borrow())
+    .unwrap_or(ItemControl::Run)
+}
+
+pub fn subscribe_item(id: &str) -> watch::Receiver<ItemControl> {
+  let mut map = ITEM_CONTROLS.lock().unwrap();
+  if let Some(tx) = map.get(id) {
+    tx.subscribe()
+  } else {
+    let (tx, rx) = watch::channel(ItemControl::Run);
+    map.insert(id.to_string(), tx);
+    rx
+  }
+}
+
+pub fn remove_item(id: &str) {
+  ITEM_CONTROLS.lock().unwrap().remove(id);
+}
@@ -1,6 +1,16 @@
+pub mod bandwidth;
 pub mod concurrency;
+pub mod dispatch_queue;
+pub mod dispatch_stats;
 pub mod dispatcher;
 pub mod download_pipeline;
 pub mod fetch_pipeline;
 pub mod group_state;
+pub mod group_summary;
+pub mod host_limiter;
+pub mod item_state;
 pub mod numbering;
+pub mod queue_status;
+pub mod retry;
+pub mod schedule;
+pub mod subscriptions;
@@ -0,0 +1,225 @@
+use crate::models::payloads::{
+  GroupKind, GroupRunState, LimiterStatus, QueueGroupStatus, QueueJobStatus, QueueSnapshotPayload,
+};
+use crate::scheduling::concurrency::DynamicSemaphore;
+use crate::scheduling::dispatch_stats::{DispatchStats, RunningJob};
+use crate::scheduling::download_pipeline::{download_counters, paused_download_counts};
+use crate::scheduling::fetch_pipeline::fetch_counters;
+use crate::scheduling::group_state::{finished_groups, group_states};
+use crate::{DownloadDispatchStats, DownloadLimiter, FetchDispatchStats, FetchLimiter};
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How often a `queue_snapshot` event is emitted while there is something to report.
+pub const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Dispatcher and pipeline state of one kind of job, per group id.
+struct PipelineView {
+  pending: HashMap<String, usize>,
+  in_flight: HashMap<String, usize>,
+  remaining: HashMap<String, usize>,
+}
+
+impl PipelineView {
+  fn new(stats: &DispatchStats, running: &[RunningJob], remaining: HashMap<String, usize>) -> Self {
+    let mut in_flight = HashMap::new();
+    for job in running {
+      *in_flight.entry(job.group_id.clone()).or_insert(0) += 1;
+    }
+    Self {
+      pending: stats.pending(),
+      in_flight,
+      remaining,
+    }
+  }
+
+  fn group_ids(&self) -> impl Iterator<Item = &String> {
+    self
+      .pending
+      .keys()
+      .chain(self.in_flight.keys())
+      .chain(self.remaining.keys())
+  }
+}
+
+pub fn queue_snapshot(app: &AppHandle) -> QueueSnapshotPayload {
+  let fetch_stats = app.state::<FetchDispatchStats>();
+  let download_stats = app.state::<DownloadDispatchStats>();
+  let fetch_running = fetch_stats.0.running();
+  let download_running = download_stats.0.running();
+
+  let fetch = PipelineView::new(&fetch_stats.0, &fetch_running, fetch_counters());
+  let download = PipelineView::new(&download_stats.0, &download_running, download_counters());
+  let groups = group_statuses(
+    group_states(),
+    finished_groups(),
+    &fetch,
+    &download,
+    &paused_download_counts(),
+  );
+
+  let jobs = fetch_running
+    .iter()
+    .map(|job| job_status(job, GroupKind::Fetch))
+    .chain(
+      download_running
+        .iter()
+        .map(|job| job_status(job, GroupKind::Download)),
+    )
+    .collect();
+
+  QueueSnapshotPayload {
+    groups,
+    jobs,
+    fetch_limiter: limiter_status(&app.state::<FetchLimiter>().0),
+    download_limiter: limiter_status(&app.state::<DownloadLimiter>().0),
+  }
+}
+
+/// Emits a `queue_snapshot` every `SNAPSHOT_INTERVAL`, plus one more once the queue went idle.
+pub fn start_snapshot_ticker(app: &AppHandle) {
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    let mut ticker = tokio::time::interval(SNAPSHOT_INTERVAL);
+    let mut was_idle = true;
+    loop {
+      ticker.tick().await;
+      let snapshot = queue_snapshot(&app);
+      let idle = snapshot.jobs.is_empty()
+        && snapshot
+          .groups
+          .iter()
+          .all(|group| group.state == GroupRunState::Finished);
+      if !(idle && was_idle) {
+        let _ = app.emit("queue_snapshot", snapshot);
+      }
+      was_idle = idle;
+    }
+  });
+}
+
+fn group_statuses(
+  states: Vec<(String, bool)>,
+  finished: Vec<String>,
+  fetch: &PipelineView,
+  download: &PipelineView,
+  paused: &HashMap<String, usize>,
+) -> Vec<QueueGroupStatus> {
+  let running: HashMap<String, bool> = states.into_iter().collect();
+  let group_ids: BTreeSet<&String> = running
+    .keys()
+    .chain(finished.iter())
+    .chain(fetch.group_ids())
+    .chain(download.group_ids())
+    .chain(paused.keys())
+    .collect();
+
+  let count =
+    |map: &HashMap<String, usize>, group_id: &str| map.get(group_id).copied().unwrap_or(0);
+  group_ids
+    .into_iter()
+    .map(|group_id| {
+      let status = QueueGroupStatus {
+        group_id: group_id.clone(),
+        state: GroupRunState::Finished,
+        fetch_pending: count(&fetch.pending, group_id),
+        fetch_in_flight: count(&fetch.in_flight, group_id),
+        fetch_remaining: fetch.remaining.get(group_id).copied(),
+        download_pending: count(&download.pending, group_id),
+        download_in_flight: count(&download.in_flight, group_id),
+        download_paused: count(paused, group_id),
+        download_remaining: download.remaining.get(group_id).copied(),
+      };
+      let state = if running.get(group_id) == Some(&false) {
+        GroupRunState::Cancelled
+      } else if status.fetch_in_flight + status.download_in_flight > 0 {
+        GroupRunState::Running
+      } else if status.fetch_pending + status.download_pending + status.download_paused > 0
+        || status.fetch_remaining.is_some_and(|n| n > 0)
+        || status.download_remaining.is_some_and(|n| n > 0)
+      {
+        GroupRunState::Queued
+      } else {
+        GroupRunState::Finished
+      };
+      QueueGroupStatus { state, ..status }
+    })
+    .collect()
+}
+
+fn job_status(job: &RunningJob, kind: GroupKind) -> QueueJobStatus {
+  QueueJobStatus {
+    group_id: job.group_id.clone(),
+    id: job.id.clone(),
+    kind,
+    age_ms: job.age().as_millis() as u64,
+  }
+}
+
+fn limiter_status(limiter: &DynamicSemaphore) -> LimiterStatus {
+  LimiterStatus {
+    max_permits: limiter.max(),
+    available_permits: limiter.available_permits(),
+    paused: limiter.is_paused(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn counts(values: &[(&str, usize)]) -> HashMap<String, usize> {
+    values
+      .iter()
+      .map(|(group_id, count)| (group_id.to_string(), *count))
+      .collect()
+  }
+
+  #[test]
+  fn derives_group_states() {
+    let fetch = PipelineView {
+      pending: counts(&[("expanding", 3)]),
+      in_flight: counts(&[("expanding", 1)]),
+      remaining: counts(&[("expanding", 4)]),
+    };
+    let download = PipelineView {
+      pending: counts(&[("waiting", 2)]),
+      in_flight: HashMap::new(),
+      remaining: counts(&[("waiting", 2), ("retrying", 1)]),
+    };
+    let groups = group_statuses(
+      vec![
+        ("expanding".into(), true),
+        ("cancelled".into(), false),
+        ("idle".into(), true),
+      ],
+      vec!["done".into()],
+      &fetch,
+      &download,
+      &counts(&[("paused", 1)]),
+    );
+
+    let states: Vec<(&str, GroupRunState)> = groups
+      .iter()
+      .map(|group| (group.group_id.as_str(), group.state))
+      .collect();
+    assert_eq!(
+      states,
+      vec![
+        ("cancelled", GroupRunState::Cancelled),
+        ("done", GroupRunState::Finished),
+        ("expanding", GroupRunState::Running),
+        ("idle", GroupRunState::Finished),
+        ("paused", GroupRunState::Queued),
+        ("retrying", GroupRunState::Queued),
+        ("waiting", GroupRunState::Queued),
+      ]
+    );
+    let expanding = &groups[2];
+    assert_eq!(expanding.fetch_pending, 3);
+    assert_eq!(expanding.fetch_in_flight, 1);
+    assert_eq!(expanding.fetch_remaining, Some(4));
+    assert_eq!(expanding.download_remaining, None);
+  }
+}
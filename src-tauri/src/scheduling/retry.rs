@@ -0,0 +1,68 @@
+use crate::state::config_models::RetrySettings;
+use rand::RngExt;
+use std::time::Duration;
+
+/// Returns how long to wait before retrying a download that failed with `code`,
+/// or `None` when the failure is not retryable or the retries are used up.
+///
+/// `retries` is the number of retries that already happened for the download.
+pub fn retry_delay(settings: &RetrySettings, code: &str, retries: u32) -> Option<Duration> {
+  if !settings.enabled || retries >= settings.max_retries {
+    return None;
+  }
+  if !settings.retryable_codes.iter().any(|c| c == code) {
+    return None;
+  }
+  let jitter = rand::rng().random_range(0.0..=1.0);
+  Some(backoff_delay(
+    settings.base_delay_ms,
+    settings.max_delay_ms,
+    retries,
+    jitter,
+  ))
+}
+
+/// Exponential backoff with "equal jitter": half of the delay is fixed, the other half is
+/// scaled by `jitter` (0.0..=1.0) so parallel failures don't all retry at once.
+fn backoff_delay(base_ms: u64, max_ms: u64, retries: u32, jitter: f64) -> Duration {
+  let exp = base_ms.saturating_mul(1u64 << retries.min(32));
+  let capped = exp.min(max_ms.max(base_ms));
+  let half = capped / 2;
+  let jittered = half + ((capped - half) as f64 * jitter.clamp(0.0, 1.0)) as u64;
+  Duration::from_millis(jittered)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn backoff_doubles_until_capped() {
+    assert_eq!(backoff_delay(1_000, 60_000, 0, 1.0).as_millis(), 1_000);
+    assert_eq!(backoff_delay(1_000, 60_000, 1, 1.0).as_millis(), 2_000);
+    assert_eq!(backoff_delay(1_000, 60_000, 3, 1.0).as_millis(), 8_000);
+    assert_eq!(backoff_delay(1_000, 5_000, 10, 1.0).as_millis(), 5_000);
+    assert_eq!(backoff_delay(1_000, 60_000, 40, 1.0).as_millis(), 60_000);
+  }
+
+  #[test]
+  fn jitter_keeps_at_least_half_the_delay() {
+    assert_eq!(backoff_delay(1_000, 60_000, 2, 0.0).as_millis(), 2_000);
+    assert_eq!(backoff_delay(1_000, 60_000, 2, 0.5).as_millis(), 3_000);
+  }
+
+  #[test]
+  fn only_retries_known_codes_within_budget() {
+    let settings = RetrySettings::default();
+    assert!(retry_delay(&settings, "rateLimited429", 0).is_some());
+    assert!(retry_delay(&settings, "server5xx", 2).is_some());
+    assert!(retry_delay(&settings, "server5xx", 3).is_none());
+    assert!(retry_delay(&settings, "signInRequired", 0).is_none());
+
+    let disabled = RetrySettings {
+      enabled: false,
+      ..RetrySettings::default()
+    };
+    assert!(retry_delay(&disabled, "rateLimited429", 0).is_none());
+  }
+}
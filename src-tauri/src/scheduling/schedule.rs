@@ -0,0 +1,135 @@
+use crate::state::config_models::{DownloadWindow, ScheduleSettings};
+use crate::{SharedConfig, SharedGroupStarts};
+use chrono::{Local, Timelike};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+/// Whether downloads of `group_id` may start now, given the configured windows and the
+/// group's scheduled start time.
+pub fn download_allowed_now(app: &AppHandle, group_id: &str) -> bool {
+  let may_start = app
+    .try_state::<SharedGroupStarts>()
+    .is_none_or(|starts| starts.load().may_start(group_id, now_ms()));
+  let in_window = app
+    .try_state::<SharedConfig>()
+    .is_none_or(|config| in_download_window(&config.load().schedule, local_minute_of_day()));
+  may_start && in_window
+}
+
+pub fn local_minute_of_day() -> u32 {
+  let now = Local::now();
+  now.hour() * 60 + now.minute()
+}
+
+/// Whether `minute_of_day` falls inside `window`. Invalid windows never match.
+pub fn window_active(window: &DownloadWindow, minute_of_day: u32) -> bool {
+  parse_window(window).is_some_and(|(start, end)| window_contains(start, end, minute_of_day))
+}
+
+/// Schedules `group_id` to start at `start_at` (unix time in milliseconds), or clears its
+/// scheduled start when `None`.
+pub fn set_group_start(
+  app: &AppHandle,
+  group_id: &str,
+  start_at: Option<u64>,
+) -> Result<(), String> {
+  let Some(starts) = app.try_state::<SharedGroupStarts>() else {
+    return Ok(());
+  };
+  if starts.load().entries.get(group_id).copied() == start_at {
+    return Ok(());
+  }
+  starts
+    .update(app, |value| {
+      value.set(group_id, start_at);
+    })
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+fn in_download_window(schedule: &ScheduleSettings, minute_of_day: u32) -> bool {
+  if !schedule.windows_enabled {
+    return true;
+  }
+  let mut windows = schedule.windows.iter().filter_map(parse_window).peekable();
+  // Without a single valid window there is nothing to restrict to.
+  if windows.peek().is_none() {
+    return true;
+  }
+  windows.any(|(start, end)| window_contains(start, end, minute_of_day))
+}
+
+fn window_contains(start: u32, end: u32, minute_of_day: u32) -> bool {
+  if start <= end {
+    (start..end).contains(&minute_of_day)
+  } else {
+    minute_of_day >= start || minute_of_day < end
+  }
+}
+
+fn parse_window(window: &DownloadWindow) -> Option<(u32, u32)> {
+  Some((parse_clock(&window.start)?, parse_clock(&window.end)?))
+}
+
+fn parse_clock(value: &str) -> Option<u32> {
+  let (hours, minutes) = value.trim().split_once(':')?;
+  let hours: u32 = hours.parse().ok()?;
+  let minutes: u32 = minutes.parse().ok()?;
+  (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+pub fn now_ms() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis() as u64)
+    .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn window(start: &str, end: &str) -> DownloadWindow {
+    DownloadWindow {
+      start: start.into(),
+      end: end.into(),
+    }
+  }
+
+  #[test]
+  fn windows_restrict_only_when_enabled() {
+    let mut schedule = ScheduleSettings {
+      windows: vec![window("01:00", "07:00")],
+      ..ScheduleSettings::default()
+    };
+    assert!(in_download_window(&schedule, 12 * 60));
+
+    schedule.windows_enabled = true;
+    assert!(!in_download_window(&schedule, 12 * 60));
+    assert!(in_download_window(&schedule, 60));
+    assert!(!in_download_window(&schedule, 7 * 60));
+  }
+
+  #[test]
+  fn windows_wrap_around_midnight() {
+    let schedule = ScheduleSettings {
+      windows_enabled: true,
+      windows: vec![window("23:00", "06:00")],
+    };
+    assert!(in_download_window(&schedule, 23 * 60 + 30));
+    assert!(in_download_window(&schedule, 2 * 60));
+    assert!(!in_download_window(&schedule, 6 * 60));
+    assert!(!in_download_window(&schedule, 22 * 60));
+  }
+
+  #[test]
+  fn invalid_windows_are_ignored() {
+    let schedule = ScheduleSettings {
+      windows_enabled: true,
+      windows: vec![window("25:00", "07:00"), window("nope", "")],
+    };
+    assert!(in_download_window(&schedule, 12 * 60));
+    assert_eq!(parse_clock("07:30"), Some(450));
+    assert_eq!(parse_clock("7:60"), None);
+  }
+}
@@ -0,0 +1,238 @@
+use crate::models::download::QueuePriority;
+use crate::models::payloads::{SubscriptionSyncItem, SubscriptionSyncPayload};
+use crate::models::{DownloadItem, ParsedMedia, ParsedPlaylist, PlaylistEntry};
+use crate::runners::template_context::TemplateContext;
+use crate::runners::ytdlp_info::{archived_ids, run_ytdlp_listing};
+use crate::scheduling::dispatcher::DispatchRequest;
+use crate::scheduling::download_pipeline::{DownloadRequest, DownloadSender};
+use crate::scheduling::group_state::ensure_group_running;
+use crate::scheduling::schedule::now_ms;
+use crate::state::subscriptions_models::{Subscription, SubscriptionDownload};
+use crate::SharedSubscriptions;
+use chrono::Local;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use uuid::Uuid;
+
+const SYNC_TICK: Duration = Duration::from_secs(60);
+
+/// Subscriptions with a sync in flight, so a manual sync does not overlap the background one.
+static SYNCING: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+struct SyncOutcome {
+  group_id: Option<String>,
+  queued: Vec<SubscriptionSyncItem>,
+  retained: Vec<String>,
+  title: Option<String>,
+}
+
+/// Periodically syncs the subscriptions that are due.
+pub fn start_subscription_sync(app: &AppHandle) {
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    let mut ticker = tokio::time::interval(SYNC_TICK);
+    loop {
+      ticker.tick().await;
+      let due = app.state::<SharedSubscriptions>().load().due(now_ms());
+      for id in due {
+        if let Err(e) = sync_subscription(&app, &id).await {
+          tracing::warn!(subscription = %id, error = %e, "Subscription sync failed");
+        }
+      }
+    }
+  });
+}
+
+/// Lists the subscribed channel or playlist and queues the entries that are new since the last
+/// sync. Returns the number of queued entries.
+pub async fn sync_subscription(app: &AppHandle, id: &str) -> Result<usize, String> {
+  let subscriptions = app.state::<SharedSubscriptions>();
+  let Some(subscription) = subscriptions.load().entries.get(id).cloned() else {
+    return Err(format!("Unknown subscription: {id}"));
+  };
+  if !SYNCING.lock().unwrap().insert(id.to_string()) {
+    return Ok(0);
+  }
+  let outcome = run_sync(app, &subscription).await;
+  SYNCING.lock().unwrap().remove(id);
+
+  let now = now_ms();
+  let mut pruned = Vec::new();
+  let update = subscriptions.update(app, |value| match &outcome {
+    Ok(outcome) => {
+      pruned = value.mark_synced(id, now, Ok(outcome.retained.clone()));
+      if let Some(entry) = value.entries.get_mut(id) {
+        entry.title = entry.title.take().or_else(|| outcome.title.clone());
+        entry
+          .downloads
+          .extend(outcome.queued.iter().map(|item| SubscriptionDownload {
+            id: item.id.clone(),
+            url: item.url.clone(),
+            path: None,
+          }));
+      }
+    }
+    Err(e) => {
+      value.mark_synced(id, now, Err(e.clone()));
+    }
+  });
+  match update {
+    Ok(_) => remove_pruned_files(id, &pruned),
+    Err(e) => {
+      tracing::warn!(subscription = %id, error = %e, "Failed to store subscription sync");
+    }
+  }
+
+  let payload = match &outcome {
+    Ok(outcome) => SubscriptionSyncPayload {
+      subscription_id: id.to_string(),
+      group_id: outcome.group_id.clone(),
+      queued: outcome.queued.clone(),
+      error: None,
+    },
+    Err(e) => SubscriptionSyncPayload {
+      subscription_id: id.to_string(),
+      group_id: None,
+      queued: Vec::new(),
+      error: Some(e.clone()),
+    },
+  };
+  let _ = app.emit("subscription_sync", payload);
+
+  outcome.map(|outcome| outcome.queued.len())
+}
+
+/// Deletes the files of downloads that fell out of the retention policy.
+fn remove_pruned_files(id: &str, pruned: &[SubscriptionDownload]) {
+  for path in pruned
+    .iter()
+    .filter_map(|download| download.path.as_deref())
+  {
+    match std::fs::remove_file(path) {
+      Ok(()) => tracing::info!(subscription = %id, path = %path, "Pruned subscription download"),
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+      Err(e) => {
+        tracing::warn!(subscription = %id, path = %path, error = %e, "Failed to prune download");
+      }
+    }
+  }
+}
+
+/// Remembers the file of a completed download if a subscription queued it, so it can be pruned
+/// later.
+pub fn record_subscription_download(app: &AppHandle, id: &str, destination: Option<&Path>) {
+  let Some(path) = destination.and_then(Path::to_str) else {
+    return;
+  };
+  let subscriptions = app.state::<SharedSubscriptions>();
+  let queued = subscriptions
+    .load()
+    .entries
+    .values()
+    .any(|subscription| subscription.downloads.iter().any(|d| d.id == id));
+  if !queued {
+    return;
+  }
+  if let Err(e) = subscriptions.update(app, |value| {
+    value.record_download(id, path);
+  }) {
+    tracing::warn!(download = %id, error = %e, "Failed to record subscription download");
+  }
+}
+
+async fn run_sync(app: &AppHandle, subscription: &Subscription) -> Result<SyncOutcome, String> {
+  let media = run_ytdlp_listing(
+    app,
+    &subscription.id,
+    &subscription.url,
+    subscription.overrides.as_ref(),
+  )
+  .await
+  .map_err(|e| e.to_string())?;
+  let ParsedMedia::Playlist(playlist) = media else {
+    return Err("The subscribed URL is not a channel or playlist".into());
+  };
+
+  let retained = subscription.retained(&playlist.entries, Local::now().date_naive());
+  let archived = archived_ids(app, subscription.overrides.as_ref());
+  let new_entries = subscription.new_entries(&retained, archived.as_ref());
+
+  let mut outcome = SyncOutcome {
+    group_id: None,
+    queued: Vec::new(),
+    retained: retained
+      .iter()
+      .map(|entry| entry.video_url.clone())
+      .collect(),
+    title: playlist.title.clone(),
+  };
+  if new_entries.is_empty() {
+    return Ok(outcome);
+  }
+
+  let items: Vec<DownloadItem> = new_entries
+    .iter()
+    .map(|entry| download_item(subscription, &playlist, entry))
+    .collect();
+  outcome.queued = items
+    .iter()
+    .zip(&new_entries)
+    .map(|(item, entry)| SubscriptionSyncItem {
+      id: item.id.clone(),
+      url: item.url.clone(),
+      title: entry.title.clone(),
+    })
+    .collect();
+
+  let group_id = Uuid::new_v4().to_string();
+  ensure_group_running(&group_id);
+  app
+    .state::<DownloadSender>()
+    .0
+    .send(DispatchRequest::Pipeline(DownloadRequest::Batch {
+      group_id: group_id.clone(),
+      items,
+    }))
+    .map_err(|e| e.to_string())?;
+  outcome.group_id = Some(group_id);
+  Ok(outcome)
+}
+
+fn download_item(
+  subscription: &Subscription,
+  playlist: &ParsedPlaylist,
+  entry: &PlaylistEntry,
+) -> DownloadItem {
+  let mut values = HashMap::new();
+  let mut insert = |key: &str, value: Option<String>| {
+    if let Some(value) = value {
+      values.insert(key.to_string(), value);
+    }
+  };
+  insert("playlist_index", Some(entry.index.to_string()));
+  insert("playlist_id", playlist.playlist_id.clone());
+  insert("playlist_title", playlist.title.clone());
+  insert("playlist", playlist.title.clone());
+  insert("playlist_uploader", playlist.uploader.clone());
+  insert("playlist_uploader_id", playlist.uploader_id.clone());
+  insert(
+    "playlist_count",
+    playlist.playlist_count.map(|count| count.to_string()),
+  );
+  insert("n_entries", Some(playlist.entries.len().to_string()));
+
+  DownloadItem {
+    id: Uuid::new_v4().to_string(),
+    url: entry.video_url.clone(),
+    format: subscription.format.clone(),
+    subtitle_inventory: None,
+    overrides: subscription.overrides.clone(),
+    template_context: TemplateContext { values },
+    priority: QueuePriority::Normal,
+    direct_playlist: false,
+    live: None,
+  }
+}
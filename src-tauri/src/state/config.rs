@@ -1,10 +1,13 @@
+use crate::api::api_server::sync_api_server;
+use crate::clipboard::clipboard_watch::sync_clipboard_watch;
 use crate::commands::{register_shortcuts, unregister_shortcuts};
 use crate::i18n::I18nManager;
+use crate::scheduling::bandwidth::rebalance_with;
 use crate::state::config_models::Config;
 use crate::state::json_handle::JsonStoreHandle;
 use crate::state::json_state::JsonBackedState;
 use crate::tray::{create_tray, destroy_tray};
-use crate::{DownloadLimiter, FetchLimiter};
+use crate::{DownloadHostLimiter, DownloadLimiter, FetchHostLimiter, FetchLimiter};
 use tauri::{AppHandle, Manager, Wry};
 use tauri_plugin_autostart::ManagerExt;
 
@@ -40,11 +43,22 @@ impl JsonBackedState for Config {
     }
     if let Some(limiter) = app.try_state::<FetchLimiter>() {
       let limiter = limiter.0.clone();
-      let max = new_value.performance.max_concurrency;
+      let max = new_value.performance.max_fetch_concurrency;
       tauri::async_runtime::spawn(async move {
         limiter.resize(max).await;
       });
     }
+    if let Some(limiter) = app.try_state::<DownloadHostLimiter>() {
+      limiter
+        .0
+        .set_limit(new_value.performance.max_concurrency_per_host.unwrap_or(0));
+    }
+    if let Some(limiter) = app.try_state::<FetchHostLimiter>() {
+      limiter
+        .0
+        .set_limit(new_value.performance.max_concurrency_per_host.unwrap_or(0));
+    }
+    rebalance_with(&new_value.performance);
 
     if new_value.input.global_shortcuts {
       register_shortcuts(app);
@@ -74,6 +88,9 @@ impl JsonBackedState for Config {
       }
     }
 
+    sync_api_server(app, &new_value.api);
+    sync_clipboard_watch(app, &new_value.clipboard_watch);
+
     if new_value.system.auto_start_enabled {
       let _ = app.autolaunch().enable();
     } else {
@@ -1,8 +1,9 @@
 use crate::commands::NotificationKind;
 use crate::models::download::{
-  AudioFormat, AudioPostprocessPreset, TranscodePolicy, VideoContainer, VideoPostprocessMode,
-  VideoPostprocessPreset,
+  AudioFormat, AudioPostprocessPreset, HookFailurePolicy, TranscodePolicy, VideoContainer,
+  VideoPostprocessMode, VideoPostprocessPreset,
 };
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::thread;
 
@@ -66,6 +67,14 @@ pub struct InputSettings {
   pub auto_fill_clipboard: bool,
   pub prefer_video_in_mixed_links: bool,
   pub global_shortcuts: bool,
+  /// Pass the managed download archive to yt-dlp so media is only downloaded once. Off by default,
+  /// since it silently skips media that was downloaded before and then deleted or moved.
+  pub use_download_archive: bool,
+  /// Keep premieres and streams that have not started yet queued until they go live.
+  pub wait_for_scheduled: bool,
+  /// Let `ovd://` links start downloads with `start=1` and pick a profile. Off by default, since
+  /// any web page can open such a link, so links only add their URLs to the UI.
+  pub deep_link_downloads: bool,
 }
 
 impl Default for InputSettings {
@@ -74,6 +83,9 @@ impl Default for InputSettings {
       auto_fill_clipboard: true,
       prefer_video_in_mixed_links: false,
       global_shortcuts: true,
+      use_download_archive: false,
+      wait_for_scheduled: false,
+      deep_link_downloads: false,
     }
   }
 }
@@ -210,7 +222,18 @@ impl Default for OutputSettings {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct PerformanceSettings {
+  /// Maximum number of parallel downloads.
   pub max_concurrency: usize,
+  /// Maximum number of parallel metadata fetches.
+  pub max_fetch_concurrency: usize,
+  /// Maximum number of parallel downloads from the same extractor or host.
+  pub max_concurrency_per_host: Option<usize>,
+  /// Bandwidth in KiB/s shared by all active downloads, unlimited when unset.
+  pub bandwidth_limit_kib: Option<u64>,
+  /// Budget that replaces `bandwidth_limit_kib` inside `alternate_bandwidth_window`,
+  /// e.g. a higher limit at night.
+  pub alternate_bandwidth_limit_kib: Option<u64>,
+  pub alternate_bandwidth_window: Option<DownloadWindow>,
   pub split_playlist_threshold: usize,
   pub auto_load_size: bool,
 }
@@ -221,12 +244,212 @@ impl Default for PerformanceSettings {
       max_concurrency: thread::available_parallelism()
         .map(|n| n.get().div_ceil(2))
         .unwrap_or(1),
+      max_fetch_concurrency: thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1),
+      max_concurrency_per_host: None,
+      bandwidth_limit_kib: None,
+      alternate_bandwidth_limit_kib: None,
+      alternate_bandwidth_window: None,
       split_playlist_threshold: 50,
       auto_load_size: true,
     }
   }
 }
 
+/// A daily time window in local time, as `HH:MM`. Windows that end before they start wrap
+/// around midnight, e.g. `23:00`–`06:00`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadWindow {
+  pub start: String,
+  pub end: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScheduleSettings {
+  /// Only hand out download permits inside `windows`.
+  pub windows_enabled: bool,
+  pub windows: Vec<DownloadWindow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RetrySettings {
+  pub enabled: bool,
+  /// Retries after the first failed attempt.
+  pub max_retries: u32,
+  pub base_delay_ms: u64,
+  pub max_delay_ms: u64,
+  /// Diagnostic codes from `diagnostic_rules.json` that count as transient.
+  pub retryable_codes: Vec<String>,
+}
+
+impl Default for RetrySettings {
+  fn default() -> Self {
+    Self {
+      enabled: true,
+      max_retries: 3,
+      base_delay_ms: 2_000,
+      max_delay_ms: 60_000,
+      retryable_codes: vec![
+        "rateLimited429".into(),
+        "server5xx".into(),
+        "networkNameResolutionFailed".into(),
+        "unableToConnectToProxy".into(),
+      ],
+    }
+  }
+}
+
+/// On-disk cache of fetched media info, keyed by URL and the options that affect it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MetadataCacheSettings {
+  pub enabled: bool,
+  pub ttl_secs: u64,
+  pub max_size_mb: u64,
+}
+
+impl Default for MetadataCacheSettings {
+  fn default() -> Self {
+    Self {
+      enabled: true,
+      ttl_secs: 6 * 60 * 60,
+      max_size_mb: 256,
+    }
+  }
+}
+
+/// Local HTTP API for browser extensions and scripts, bound to `127.0.0.1:{port}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ApiSettings {
+  pub enabled: bool,
+  pub port: u16,
+  /// Origins, e.g. `chrome-extension://<id>`, whose pages may call the API. Requests from any
+  /// other origin are refused; clients that send no `Origin`, like scripts, are not affected.
+  pub allowed_origins: Vec<String>,
+}
+
+impl Default for ApiSettings {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      port: 47_819,
+      allowed_origins: Vec::new(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ClipboardWatchAction {
+  /// Hand the URL to the frontend as a `clipboard_url` event.
+  #[default]
+  Emit,
+  /// Queue the URL for download straight away.
+  Queue,
+}
+
+/// Watches the clipboard in the background, also while the app sits in the tray, and picks up
+/// copied URLs that yt-dlp has an extractor for or that match one of `patterns`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ClipboardWatchSettings {
+  pub enabled: bool,
+  pub action: ClipboardWatchAction,
+  /// Download profile for queued URLs, the default format is used when unset.
+  pub profile: Option<String>,
+  pub match_extractors: bool,
+  /// Regular expressions matched against the whole URL.
+  pub patterns: Vec<String>,
+  pub poll_interval_ms: u64,
+}
+
+impl Default for ClipboardWatchSettings {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      action: ClipboardWatchAction::Emit,
+      profile: None,
+      match_extractors: true,
+      patterns: Vec::new(),
+      poll_interval_ms: 1_000,
+    }
+  }
+}
+
+/// A program that runs after each download, with details about it in `OVD_*` environment
+/// variables.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PostDownloadHookSettings {
+  /// Run the default hook for downloads that don't pick one of the `profiles`.
+  pub enabled: bool,
+  /// Program to run, looked up on `PATH` when not absolute.
+  pub command: Option<String>,
+  pub args: Vec<String>,
+  /// Also run the hook for failed downloads, with their exit status.
+  pub run_on_failure: bool,
+  pub timeout_secs: u64,
+  pub failure_policy: HookFailurePolicy,
+  /// Named hooks that downloads pick with `PostDownloadHookOverrides::profile` instead of the
+  /// default one above.
+  pub profiles: IndexMap<String, HookProfile>,
+}
+
+impl Default for PostDownloadHookSettings {
+  fn default() -> Self {
+    Self {
+      enabled: false,
+      command: None,
+      args: Vec::new(),
+      run_on_failure: false,
+      timeout_secs: 300,
+      failure_policy: HookFailurePolicy::Ignore,
+      profiles: IndexMap::new(),
+    }
+  }
+}
+
+impl PostDownloadHookSettings {
+  /// The default hook as a profile.
+  pub fn default_profile(&self) -> HookProfile {
+    HookProfile {
+      command: self.command.clone().unwrap_or_default(),
+      args: self.args.clone(),
+      run_on_failure: self.run_on_failure,
+      timeout_secs: self.timeout_secs,
+      failure_policy: self.failure_policy,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HookProfile {
+  /// Program to run, looked up on `PATH` when not absolute.
+  pub command: String,
+  pub args: Vec<String>,
+  pub run_on_failure: bool,
+  pub timeout_secs: u64,
+  pub failure_policy: HookFailurePolicy,
+}
+
+impl Default for HookProfile {
+  fn default() -> Self {
+    Self {
+      command: String::new(),
+      args: Vec::new(),
+      run_on_failure: false,
+      timeout_secs: 300,
+      failure_policy: HookFailurePolicy::Ignore,
+    }
+  }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct SponsorBlockSettings {
@@ -361,11 +584,17 @@ pub struct Config {
   pub network: NetworkSettings,
   pub input: InputSettings,
   pub input_filters: InputFilterSettings,
+  pub metadata_cache: MetadataCacheSettings,
   pub output: OutputSettings,
   pub performance: PerformanceSettings,
+  pub post_download_hook: PostDownloadHookSettings,
+  pub retry: RetrySettings,
+  pub schedule: ScheduleSettings,
   pub sponsor_block: SponsorBlockSettings,
   pub subtitles: SubtitleSettings,
   pub update: UpdateSettings,
   pub system: SystemConfig,
   pub notifications: NotificationConfig,
+  pub api: ApiSettings,
+  pub clipboard_watch: ClipboardWatchSettings,
 }
@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+const ARCHIVE_FILE: &str = "download-archive.txt";
+
+/// One line of the archive: the lowercase extractor key and the media id, as yt-dlp writes them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveEntry {
+  pub extractor: String,
+  pub id: String,
+}
+
+impl ArchiveEntry {
+  fn parse(line: &str) -> Option<Self> {
+    let (extractor, id) = line.trim().split_once(char::is_whitespace)?;
+    let id = id.trim();
+    if extractor.is_empty() || id.is_empty() {
+      return None;
+    }
+    Some(Self {
+      extractor: extractor.to_string(),
+      id: id.to_string(),
+    })
+  }
+
+  /// The key yt-dlp matches against, e.g. `youtube dQw4w9WgXcQ`.
+  pub fn archive_id(&self) -> String {
+    archive_id(&self.extractor, &self.id)
+  }
+}
+
+/// Builds the archive id yt-dlp would record for a media item.
+pub fn archive_id(extractor_key: &str, id: &str) -> String {
+  format!("{} {}", extractor_key.to_lowercase(), id)
+}
+
+/// The `--download-archive` file shared by all downloads. yt-dlp appends to it after every
+/// successful download; the app only reads it and rewrites it to remove entries.
+///
+/// yt-dlp doesn't take the app's lock, so a rewrite could drop lines appended while it runs.
+/// Downloads hold a [`ArchiveLease`] while their process runs and removal is refused until none
+/// are left.
+pub struct DownloadArchive {
+  path: PathBuf,
+  write_lock: Mutex<()>,
+  users: AtomicUsize,
+}
+
+/// Marks a yt-dlp run that may append to the archive, until it is dropped.
+pub struct ArchiveLease<'a> {
+  archive: &'a DownloadArchive,
+}
+
+impl Drop for ArchiveLease<'_> {
+  fn drop(&mut self) {
+    self.archive.users.fetch_sub(1, Ordering::SeqCst);
+  }
+}
+
+impl DownloadArchive {
+  pub fn new(app_dir: &Path) -> Self {
+    Self {
+      path: app_dir.join(ARCHIVE_FILE),
+      write_lock: Mutex::new(()),
+      users: AtomicUsize::new(0),
+    }
+  }
+
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  pub fn entries(&self) -> io::Result<Vec<ArchiveEntry>> {
+    match std::fs::read_to_string(&self.path) {
+      Ok(contents) => Ok(contents.lines().filter_map(ArchiveEntry::parse).collect()),
+      Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+      Err(e) => Err(e),
+    }
+  }
+
+  pub fn archive_ids(&self) -> io::Result<HashSet<String>> {
+    Ok(
+      self
+        .entries()?
+        .iter()
+        .map(ArchiveEntry::archive_id)
+        .collect(),
+    )
+  }
+
+  /// Entries whose id or extractor contains `query`, ignoring case.
+  pub fn search(&self, query: &str) -> io::Result<Vec<ArchiveEntry>> {
+    let query = query.trim().to_lowercase();
+    let mut entries = self.entries()?;
+    entries.retain(|entry| {
+      entry.id.to_lowercase().contains(&query) || entry.extractor.to_lowercase().contains(&query)
+    });
+    Ok(entries)
+  }
+
+  /// Registers a yt-dlp run that uses the archive. Waits for a removal in progress so the run
+  /// doesn't start appending halfway through a rewrite.
+  pub fn lease(&self) -> ArchiveLease<'_> {
+    let _guard = self.write_lock.lock().unwrap();
+    self.users.fetch_add(1, Ordering::SeqCst);
+    ArchiveLease { archive: self }
+  }
+
+  /// Removes `entries` from the archive so they are downloaded again. Returns how many lines
+  /// were removed. Fails while downloads that use the archive are running, since the file is
+  /// rewritten and lines they append in the meantime would be lost.
+  pub fn remove(&self, entries: &[ArchiveEntry]) -> io::Result<usize> {
+    let _guard = self.write_lock.lock().unwrap();
+    if self.users.load(Ordering::SeqCst) > 0 {
+      return Err(io::Error::new(
+        ErrorKind::ResourceBusy,
+        "Downloads that use the archive are running, try again once they finish",
+      ));
+    }
+    let contents = match std::fs::read_to_string(&self.path) {
+      Ok(contents) => contents,
+      Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+      Err(e) => return Err(e),
+    };
+    let remove: HashSet<&ArchiveEntry> = entries.iter().collect();
+    let mut kept = String::with_capacity(contents.len());
+    let mut removed = 0;
+    for line in contents.lines() {
+      if ArchiveEntry::parse(line).is_some_and(|entry| remove.contains(&entry)) {
+        removed += 1;
+        continue;
+      }
+      kept.push_str(line);
+      kept.push('\n');
+    }
+    if removed > 0 {
+      let tmp_path = self.path.with_extension("txt.tmp");
+      let mut file = std::fs::File::create(&tmp_path)?;
+      file.write_all(kept.as_bytes())?;
+      file.sync_all()?;
+      std::fs::rename(&tmp_path, &self.path)?;
+    }
+    Ok(removed)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn archive_with(contents: &str) -> DownloadArchive {
+    let dir = std::env::temp_dir().join(format!("ovd-archive-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let archive = DownloadArchive::new(&dir);
+    std::fs::write(archive.path(), contents).unwrap();
+    archive
+  }
+
+  fn entry(extractor: &str, id: &str) -> ArchiveEntry {
+    ArchiveEntry {
+      extractor: extractor.into(),
+      id: id.into(),
+    }
+  }
+
+  #[test]
+  fn reads_and_searches_entries() {
+    let archive = archive_with("youtube abc123\n\nvimeo 42\nbroken\n");
+    assert_eq!(
+      archive.entries().unwrap(),
+      vec![entry("youtube", "abc123"), entry("vimeo", "42")]
+    );
+    assert_eq!(
+      archive.search("ABC").unwrap(),
+      vec![entry("youtube", "abc123")]
+    );
+    assert_eq!(archive.search("vim").unwrap(), vec![entry("vimeo", "42")]);
+    assert!(archive
+      .archive_ids()
+      .unwrap()
+      .contains(&archive_id("Youtube", "abc123")));
+  }
+
+  #[test]
+  fn removes_entries() {
+    let archive = archive_with("youtube abc123\nvimeo 42\nyoutube def456\n");
+    let removed = archive
+      .remove(&[entry("youtube", "abc123"), entry("youtube", "missing")])
+      .unwrap();
+    assert_eq!(removed, 1);
+    assert_eq!(
+      std::fs::read_to_string(archive.path()).unwrap(),
+      "vimeo 42\nyoutube def456\n"
+    );
+  }
+
+  #[test]
+  fn refuses_removal_while_downloads_use_the_archive() {
+    let archive = archive_with("youtube abc123\n");
+    let lease = archive.lease();
+    let err = archive.remove(&[entry("youtube", "abc123")]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ResourceBusy);
+    drop(lease);
+    assert_eq!(archive.remove(&[entry("youtube", "abc123")]).unwrap(), 1);
+  }
+
+  #[test]
+  fn missing_archive_is_empty() {
+    let dir = std::env::temp_dir().join(format!("ovd-archive-{}", uuid::Uuid::new_v4()));
+    let archive = DownloadArchive::new(&dir);
+    assert!(archive.entries().unwrap().is_empty());
+    assert_eq!(archive.remove(&[entry("youtube", "abc123")]).unwrap(), 0);
+  }
+}
@@ -0,0 +1,20 @@
+use crate::state::download_queue_models::DownloadQueue;
+use crate::state::json_handle::JsonStoreHandle;
+use crate::state::json_state::JsonBackedState;
+use tauri::{AppHandle, Wry};
+
+impl JsonBackedState for DownloadQueue {
+  const STORE_FILE: &'static str = "download_queue.store.json";
+  const ROOT_KEY: &'static str = "queue";
+
+  fn default_value() -> Self {
+    Self::default()
+  }
+
+  fn before_initialized(_app: &AppHandle<Wry>, value: &mut Self) {
+    // Anything still journaled at startup was queued or running when the app last exited.
+    value.mark_restored();
+  }
+}
+
+pub type DownloadQueueHandle = JsonStoreHandle<DownloadQueue>;
@@ -0,0 +1,174 @@
+use crate::models::download::{DownloadOverrides, FormatOptions, LiveRecording, QueuePriority};
+use crate::models::SubtitleInventory;
+use crate::runners::template_context::TemplateContext;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedDownload {
+  pub group_id: String,
+  pub id: String,
+  pub url: String,
+  pub format: FormatOptions,
+  #[serde(default)]
+  pub subtitle_inventory: Option<SubtitleInventory>,
+  #[serde(default)]
+  pub overrides: Option<DownloadOverrides>,
+  pub template_context: TemplateContext,
+  #[serde(default)]
+  pub priority: QueuePriority,
+  pub queued_at: u64,
+  #[serde(default)]
+  pub restored: bool,
+  #[serde(default)]
+  pub direct_playlist: bool,
+  #[serde(default)]
+  pub live: Option<LiveRecording>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DownloadQueue {
+  pub entries: IndexMap<String, QueuedDownload>,
+}
+
+impl DownloadQueue {
+  pub fn insert(&mut self, entry: QueuedDownload) {
+    self.entries.insert(entry.id.clone(), entry);
+  }
+
+  pub fn remove_many(&mut self, ids: &[String]) {
+    if ids.is_empty() {
+      return;
+    }
+    let ids: HashSet<&str> = ids.iter().map(String::as_str).collect();
+    self.entries.retain(|id, _| !ids.contains(id.as_str()));
+  }
+
+  pub fn remove_group(&mut self, group_id: &str) {
+    self.entries.retain(|_, entry| entry.group_id != group_id);
+  }
+
+  pub fn mark_restored(&mut self) {
+    for entry in self.entries.values_mut() {
+      entry.restored = true;
+    }
+  }
+
+  pub fn restored(&self) -> Vec<QueuedDownload> {
+    self
+      .entries
+      .values()
+      .filter(|entry| entry.restored)
+      .cloned()
+      .collect()
+  }
+
+  /// Drops restored entries. When `ids` is `None`, every restored entry is discarded.
+  pub fn discard_restored(&mut self, ids: Option<&[String]>) {
+    self.entries.retain(|id, entry| {
+      if !entry.restored {
+        return true;
+      }
+      ids.is_some_and(|ids| !ids.contains(id))
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::TrackType;
+  use std::collections::HashMap;
+
+  fn queued(id: &str, group_id: &str) -> QueuedDownload {
+    QueuedDownload {
+      group_id: group_id.into(),
+      id: id.into(),
+      url: format!("https://example.com/{id}"),
+      format: FormatOptions {
+        track_type: TrackType::Both,
+        abr: None,
+        height: Some(720),
+        fps: None,
+        audio_encoding: None,
+        video_encoding: None,
+        audio_track: None,
+        video_track: None,
+      },
+      subtitle_inventory: None,
+      overrides: None,
+      priority: QueuePriority::Normal,
+      template_context: TemplateContext {
+        values: HashMap::from([("title".to_string(), id.to_string())]),
+      },
+      queued_at: 0,
+      restored: false,
+      direct_playlist: false,
+      live: None,
+    }
+  }
+
+  #[test]
+  fn restored_only_lists_entries_from_a_previous_session() {
+    let mut queue = DownloadQueue::default();
+    queue.insert(queued("a", "g1"));
+    queue.mark_restored();
+    queue.insert(queued("b", "g2"));
+
+    let restored = queue.restored();
+    assert_eq!(restored.len(), 1);
+    assert_eq!(restored[0].id, "a");
+  }
+
+  #[test]
+  fn discard_restored_keeps_live_entries() {
+    let mut queue = DownloadQueue::default();
+    queue.insert(queued("a", "g1"));
+    queue.insert(queued("b", "g1"));
+    queue.mark_restored();
+    queue.insert(queued("c", "g2"));
+
+    queue.discard_restored(Some(&["a".to_string()]));
+    assert!(queue.entries.contains_key("b"));
+    assert!(queue.entries.contains_key("c"));
+    assert!(!queue.entries.contains_key("a"));
+
+    queue.discard_restored(None);
+    assert_eq!(queue.entries.keys().collect::<Vec<_>>(), vec!["c"]);
+  }
+
+  #[test]
+  fn requeueing_a_restored_entry_clears_the_flag() {
+    let mut queue = DownloadQueue::default();
+    queue.insert(queued("a", "g1"));
+    queue.mark_restored();
+    queue.insert(queued("a", "g3"));
+
+    assert!(queue.restored().is_empty());
+    assert_eq!(queue.entries["a"].group_id, "g3");
+  }
+
+  #[test]
+  fn remove_many_keeps_the_order_of_the_rest() {
+    let mut queue = DownloadQueue::default();
+    for id in ["a", "b", "c", "d"] {
+      queue.insert(queued(id, "g1"));
+    }
+
+    queue.remove_many(&["c".to_string(), "a".to_string(), "x".to_string()]);
+    assert_eq!(queue.entries.keys().collect::<Vec<_>>(), vec!["b", "d"]);
+  }
+
+  #[test]
+  fn remove_group_only_drops_matching_entries() {
+    let mut queue = DownloadQueue::default();
+    queue.insert(queued("a", "g1"));
+    queue.insert(queued("b", "g2"));
+
+    queue.remove_group("g1");
+    assert_eq!(queue.entries.keys().collect::<Vec<_>>(), vec!["b"]);
+  }
+}
@@ -0,0 +1,31 @@
+use crate::state::group_starts_models::GroupStarts;
+use crate::state::json_handle::JsonStoreHandle;
+use crate::state::json_state::JsonBackedState;
+use crate::SharedDownloadQueue;
+use std::collections::HashSet;
+use tauri::{AppHandle, Manager, Wry};
+
+impl JsonBackedState for GroupStarts {
+  const STORE_FILE: &'static str = "group_starts.store.json";
+  const ROOT_KEY: &'static str = "groupStarts";
+
+  fn default_value() -> Self {
+    Self::default()
+  }
+
+  fn before_initialized(app: &AppHandle<Wry>, value: &mut Self) {
+    // Only groups restored from the download journal can still start after a restart.
+    let Some(queue) = app.try_state::<SharedDownloadQueue>() else {
+      return;
+    };
+    let queue = queue.load();
+    let journaled: HashSet<&str> = queue
+      .entries
+      .values()
+      .map(|entry| entry.group_id.as_str())
+      .collect();
+    value.prune(|group_id| journaled.contains(group_id));
+  }
+}
+
+pub type GroupStartsHandle = JsonStoreHandle<GroupStarts>;
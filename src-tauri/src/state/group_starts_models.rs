@@ -0,0 +1,60 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// Groups that may not start downloading before a given unix time in milliseconds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GroupStarts {
+  pub entries: IndexMap<String, u64>,
+}
+
+impl GroupStarts {
+  /// Sets or clears the start of `group_id`. Returns whether anything changed.
+  pub fn set(&mut self, group_id: &str, start_at: Option<u64>) -> bool {
+    match start_at {
+      Some(start_at) => self.entries.insert(group_id.to_string(), start_at) != Some(start_at),
+      None => self.entries.shift_remove(group_id).is_some(),
+    }
+  }
+
+  pub fn may_start(&self, group_id: &str, now_ms: u64) -> bool {
+    self
+      .entries
+      .get(group_id)
+      .is_none_or(|start_at| *start_at <= now_ms)
+  }
+
+  /// Drops the starts of groups that `exists` no longer knows about.
+  pub fn prune<F: Fn(&str) -> bool>(&mut self, exists: F) {
+    self.entries.retain(|group_id, _| exists(group_id));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn groups_wait_for_their_start_time() {
+    let mut starts = GroupStarts::default();
+    assert!(starts.set("later", Some(2_000)));
+    assert!(!starts.set("later", Some(2_000)));
+    assert!(!starts.may_start("later", 1_000));
+    assert!(starts.may_start("later", 2_000));
+    assert!(starts.may_start("other", 1_000));
+
+    assert!(starts.set("later", None));
+    assert!(!starts.set("later", None));
+    assert!(starts.may_start("later", 1_000));
+  }
+
+  #[test]
+  fn prune_keeps_known_groups() {
+    let mut starts = GroupStarts::default();
+    starts.set("a", Some(1));
+    starts.set("b", Some(2));
+
+    starts.prune(|group_id| group_id == "b");
+    assert_eq!(starts.entries.keys().collect::<Vec<_>>(), vec!["b"]);
+  }
+}
@@ -2,13 +2,17 @@ use crate::paths::PathsManager;
 use crate::state::json_state::{json_merge, JsonBackedState};
 use arc_swap::ArcSwap;
 use serde_json::{Map, Value};
-use std::{error::Error, sync::Arc};
+use std::{
+  error::Error,
+  sync::{Arc, Mutex},
+};
 use tauri::{AppHandle, Manager, Wry};
 use tauri_plugin_store::{Store, StoreExt};
 
 pub struct JsonStoreHandle<T: JsonBackedState> {
   swap: Arc<ArcSwap<T>>,
   store: Arc<Store<Wry>>,
+  write_lock: Mutex<()>,
 }
 
 impl<T: JsonBackedState> JsonStoreHandle<T> {
@@ -32,6 +36,7 @@ impl<T: JsonBackedState> JsonStoreHandle<T> {
     Ok(Self {
       swap: Arc::new(ArcSwap::from_pointee(initial)),
       store,
+      write_lock: Mutex::new(()),
     })
   }
 
@@ -41,6 +46,7 @@ impl<T: JsonBackedState> JsonStoreHandle<T> {
   }
 
   pub fn apply_patch(&self, patch: &Value, app: &AppHandle<Wry>) -> Result<T, Box<dyn Error>> {
+    let _guard = self.write_lock.lock().unwrap();
     let mut raw = serde_json::to_value(self.swap.load_full().as_ref())?;
 
     json_merge(&mut raw, patch);
@@ -56,7 +62,28 @@ impl<T: JsonBackedState> JsonStoreHandle<T> {
     Ok(new_value)
   }
 
+  /// Mutates the current value in place. Unlike `apply_patch`, this can remove
+  /// entries from maps, which a JSON merge patch cannot express.
+  pub fn update<F>(&self, app: &AppHandle<Wry>, mutate: F) -> Result<T, Box<dyn Error>>
+  where
+    F: FnOnce(&mut T),
+  {
+    let _guard = self.write_lock.lock().unwrap();
+    let mut new_value = self.swap.load_full().as_ref().clone();
+
+    mutate(&mut new_value);
+    T::on_updated(app, &new_value);
+
+    let to_store = serde_json::to_value(&new_value)?;
+    self.store.set(T::ROOT_KEY, to_store);
+    self.store.save()?;
+    self.swap.store(Arc::new(new_value.clone()));
+
+    Ok(new_value)
+  }
+
   pub fn reset(&self, app: &AppHandle<Wry>) -> Result<T, Box<dyn Error>> {
+    let _guard = self.write_lock.lock().unwrap();
     let default_value = T::default_value();
     let raw = serde_json::to_value(&default_value)?;
 
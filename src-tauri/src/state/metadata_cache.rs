@@ -0,0 +1,297 @@
+use crate::models::download::{DownloadOverrides, FormatOptions};
+use crate::models::ParsedMedia;
+use crate::runners::override_resolver::resolve_with_patch;
+use crate::state::config_models::Config;
+use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const CACHE_DIR: &str = "metadata-cache";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedMedia {
+  stored_at: u64,
+  media: ParsedMedia,
+}
+
+/// Parsed `yt-dlp -J` results stored as one JSON file per key under the app dir.
+pub struct MetadataCache {
+  dir: PathBuf,
+  /// Approximate size of the cache directory, computed on first use.
+  size: Mutex<Option<u64>>,
+}
+
+impl MetadataCache {
+  pub fn new(app_dir: &Path) -> Self {
+    Self {
+      dir: app_dir.join(CACHE_DIR),
+      size: Mutex::new(None),
+    }
+  }
+
+  /// Returns the cached media for `key` unless it is older than `ttl`.
+  pub fn get(&self, key: &str, ttl: Duration) -> Option<ParsedMedia> {
+    let path = self.entry_path(key);
+    let contents = std::fs::read(&path).ok()?;
+    let cached: Option<CachedMedia> = serde_json::from_slice(&contents).ok();
+    match cached {
+      Some(cached) if now_ms().saturating_sub(cached.stored_at) <= ttl.as_millis() as u64 => {
+        Some(cached.media)
+      }
+      _ => {
+        let _ = std::fs::remove_file(&path);
+        if let Some(size) = self.size.lock().unwrap().as_mut() {
+          *size = size.saturating_sub(contents.len() as u64);
+        }
+        None
+      }
+    }
+  }
+
+  /// Stores `media` under `key`, then evicts the oldest entries while the cache is larger than
+  /// `max_bytes`. Livestreams and media that has not premiered yet are never cached.
+  pub fn insert(&self, key: &str, media: &ParsedMedia, max_bytes: u64) -> io::Result<()> {
+    match media {
+      ParsedMedia::Livestream(_) => return Ok(()),
+      ParsedMedia::Single(single) if single.scheduled_start.is_some() => return Ok(()),
+      _ => {}
+    }
+    let contents = serde_json::to_vec(&json!({
+      "storedAt": now_ms(),
+      "media": media,
+    }))
+    .map_err(io::Error::other)?;
+    let size = {
+      let mut size = self.size.lock().unwrap();
+      let current = match *size {
+        Some(current) => current,
+        None => self.files()?.iter().map(|(_, len, _)| len).sum(),
+      };
+      std::fs::create_dir_all(&self.dir)?;
+      std::fs::write(self.entry_path(key), &contents)?;
+      let next = current + contents.len() as u64;
+      *size = Some(next);
+      next
+    };
+    if size > max_bytes {
+      self.prune(max_bytes)?;
+    }
+    Ok(())
+  }
+
+  /// Removes every cached entry and returns how many there were.
+  pub fn clear(&self) -> io::Result<usize> {
+    let files = self.files()?;
+    for (path, _, _) in &files {
+      std::fs::remove_file(path)?;
+    }
+    *self.size.lock().unwrap() = Some(0);
+    Ok(files.len())
+  }
+
+  fn prune(&self, max_bytes: u64) -> io::Result<()> {
+    let mut files = self.files()?;
+    files.sort_by_key(|(_, _, modified)| *modified);
+    let mut size: u64 = files.iter().map(|(_, len, _)| len).sum();
+    for (path, len, _) in files {
+      if size <= max_bytes {
+        break;
+      }
+      std::fs::remove_file(path)?;
+      size -= len;
+    }
+    *self.size.lock().unwrap() = Some(size);
+    Ok(())
+  }
+
+  /// Cache files with their size and modification time.
+  fn files(&self) -> io::Result<Vec<(PathBuf, u64, SystemTime)>> {
+    let read_dir = match std::fs::read_dir(&self.dir) {
+      Ok(read_dir) => read_dir,
+      Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+      Err(e) => return Err(e),
+    };
+    let mut files = Vec::new();
+    for entry in read_dir {
+      let entry = entry?;
+      let metadata = entry.metadata()?;
+      if metadata.is_file() {
+        let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+        files.push((entry.path(), metadata.len(), modified));
+      }
+    }
+    Ok(files)
+  }
+
+  fn entry_path(&self, key: &str) -> PathBuf {
+    self.dir.join(format!("{key}.json"))
+  }
+}
+
+/// Builds the cache key for fetching `url` with the options that change what yt-dlp returns:
+/// the global auth, network and input settings with the download's overrides applied, so adding
+/// cookies or a proxy in the settings doesn't keep serving what was fetched without them.
+/// Credentials from the vault are left out, only the ones a download overrides count.
+pub fn cache_key(
+  url: &str,
+  format: Option<&FormatOptions>,
+  config: &Config,
+  overrides: Option<&DownloadOverrides>,
+) -> String {
+  let auth_overrides = overrides.and_then(|value| value.auth.as_ref());
+  let material = json!({
+    "url": normalize_url(url),
+    "format": format,
+    "auth": resolve_with_patch(&config.auth, auth_overrides),
+    "authOverrides": auth_overrides,
+    "network": resolve_with_patch(
+      &config.network,
+      overrides.and_then(|value| value.network.as_ref()),
+    ),
+    "input": resolve_with_patch(
+      &config.input,
+      overrides.and_then(|value| value.input.as_ref()),
+    ),
+    "inputFilters": overrides.and_then(|value| value.input_filters.as_ref()),
+  });
+  let mut hasher = Sha256::new();
+  hasher.update(material.to_string().as_bytes());
+  hex::encode(hasher.finalize())
+}
+
+/// Lowercases scheme and host and drops the fragment, so trivially different spellings of the
+/// same URL share an entry.
+fn normalize_url(url: &str) -> String {
+  let url = url.trim();
+  match tauri::Url::parse(url) {
+    Ok(mut parsed) => {
+      parsed.set_fragment(None);
+      parsed.to_string()
+    }
+    Err(_) => url.to_string(),
+  }
+}
+
+fn now_ms() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis() as u64)
+    .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::download::NetworkOverrides;
+  use crate::models::{ParsedPlaylist, PlaylistEntry};
+
+  fn cache() -> MetadataCache {
+    let dir = std::env::temp_dir().join(format!("ovd-metadata-{}", uuid::Uuid::new_v4()));
+    MetadataCache::new(&dir)
+  }
+
+  fn playlist(entries: usize) -> ParsedMedia {
+    ParsedMedia::Playlist(ParsedPlaylist {
+      id: "request".into(),
+      url: Some("https://example.com/playlist".into()),
+      title: Some("Playlist".into()),
+      thumbnail: None,
+      uploader: None,
+      uploader_id: None,
+      entries: (0..entries)
+        .map(|index| PlaylistEntry {
+          video_url: format!("https://example.com/watch?v={index}"),
+          index,
+          archive_id: Some(format!("example {index}")),
+          title: None,
+          duration: None,
+          thumbnail: None,
+          uploader: None,
+          upload_date: None,
+          availability: None,
+        })
+        .collect(),
+      playlist_id: Some("PL".into()),
+      playlist_count: Some(entries as u64),
+    })
+  }
+
+  #[test]
+  fn serves_entries_within_their_ttl() {
+    let cache = cache();
+    cache.insert("key", &playlist(2), u64::MAX).unwrap();
+
+    let Some(ParsedMedia::Playlist(cached)) = cache.get("key", Duration::from_secs(60)) else {
+      panic!("expected a cached playlist");
+    };
+    assert_eq!(cached.entries.len(), 2);
+    assert_eq!(cached.entries[1].archive_id.as_deref(), Some("example 1"));
+
+    assert!(cache.get("missing", Duration::from_secs(60)).is_none());
+
+    std::thread::sleep(Duration::from_millis(5));
+    assert!(cache.get("key", Duration::ZERO).is_none());
+    assert!(!cache.entry_path("key").exists());
+  }
+
+  #[test]
+  fn evicts_oldest_entries_over_the_size_cap() {
+    let cache = cache();
+    cache.insert("old", &playlist(50), u64::MAX).unwrap();
+    std::thread::sleep(Duration::from_millis(20));
+    cache.insert("new", &playlist(50), u64::MAX).unwrap();
+    let one_entry = std::fs::metadata(cache.entry_path("new")).unwrap().len();
+
+    cache
+      .insert("newest", &playlist(50), one_entry * 2)
+      .unwrap();
+    assert!(!cache.entry_path("old").exists());
+    assert!(cache.entry_path("newest").exists());
+
+    assert_eq!(cache.clear().unwrap(), 2);
+    assert!(cache.get("newest", Duration::from_secs(60)).is_none());
+  }
+
+  #[test]
+  fn keys_ignore_fragments_but_not_options() {
+    let config = Config::default();
+    let key =
+      |url: &str, overrides: Option<&DownloadOverrides>| cache_key(url, None, &config, overrides);
+    let plain = key("https://Example.com/watch?v=1", None);
+    assert_eq!(plain, key(" https://example.com/watch?v=1#t=10 ", None));
+    assert_ne!(plain, key("https://example.com/watch?v=2", None));
+
+    let overrides = DownloadOverrides {
+      network: Some(NetworkOverrides {
+        proxy: Some("socks5://127.0.0.1:1080".into()),
+        ..Default::default()
+      }),
+      ..Default::default()
+    };
+    assert_ne!(
+      plain,
+      key("https://example.com/watch?v=1", Some(&overrides))
+    );
+  }
+
+  #[test]
+  fn keys_follow_global_settings() {
+    let mut config = Config::default();
+    let plain = cache_key("https://example.com/watch?v=1", None, &config, None);
+
+    config.auth.cookie_file = Some("/tmp/cookies.txt".into());
+    let with_cookies = cache_key("https://example.com/watch?v=1", None, &config, None);
+    assert_ne!(plain, with_cookies);
+
+    config.auth.cookie_file = None;
+    config.network.proxy = Some("http://proxy.example:8080".into());
+    let with_proxy = cache_key("https://example.com/watch?v=1", None, &config, None);
+    assert_ne!(plain, with_proxy);
+    assert_ne!(with_cookies, with_proxy);
+  }
+}
@@ -1,8 +1,16 @@
 pub mod config;
 pub mod config_models;
+pub mod download_archive;
+pub mod download_queue;
+pub mod download_queue_models;
+pub mod group_starts;
+pub mod group_starts_models;
 mod json_handle;
 mod json_state;
+pub mod metadata_cache;
 pub mod preferences;
 pub mod preferences_models;
+pub mod subscriptions;
+pub mod subscriptions_models;
 
 pub use json_state::json_merge;
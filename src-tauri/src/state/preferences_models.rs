@@ -1,3 +1,4 @@
+use crate::models::download::{DownloadOverrides, FormatOptions};
 use crate::models::TrackType;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
@@ -62,6 +63,15 @@ pub struct WindowPreferences {
   pub prev_y: i32,
 }
 
+/// Named format and overrides, picked with `--profile` on the command line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProfile {
+  pub format: FormatOptions,
+  #[serde(default)]
+  pub overrides: Option<DownloadOverrides>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct Preferences {
@@ -69,4 +79,32 @@ pub struct Preferences {
   pub recents: RecentPreferences,
   pub formats: FormatPreferences,
   pub window: WindowPreferences,
+  pub profiles: IndexMap<String, DownloadProfile>,
+}
+
+impl Preferences {
+  /// The profile called `name`, or the preferred track type without overrides when no name is
+  /// given.
+  pub fn download_profile(&self, name: Option<&str>) -> Result<DownloadProfile, String> {
+    match name {
+      Some(name) => self
+        .profiles
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("Unknown profile: {name}")),
+      None => Ok(DownloadProfile {
+        format: FormatOptions {
+          track_type: self.formats.track_type.clone(),
+          abr: None,
+          height: None,
+          fps: None,
+          audio_encoding: None,
+          video_encoding: None,
+          audio_track: None,
+          video_track: None,
+        },
+        overrides: None,
+      }),
+    }
+  }
 }
@@ -0,0 +1,14 @@
+use crate::state::json_handle::JsonStoreHandle;
+use crate::state::json_state::JsonBackedState;
+use crate::state::subscriptions_models::Subscriptions;
+
+impl JsonBackedState for Subscriptions {
+  const STORE_FILE: &'static str = "subscriptions.store.json";
+  const ROOT_KEY: &'static str = "subscriptions";
+
+  fn default_value() -> Self {
+    Self::default()
+  }
+}
+
+pub type SubscriptionsHandle = JsonStoreHandle<Subscriptions>;
@@ -0,0 +1,453 @@
+use crate::models::download::{DownloadOverrides, FormatOptions};
+use crate::models::PlaylistEntry;
+use chrono::NaiveDate;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Which entries of a subscribed channel or playlist are kept in sync.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Retention {
+  #[default]
+  All,
+  /// The first `count` entries of the listing, channels list their newest uploads first.
+  LastItems { count: usize },
+  /// Entries uploaded within the last `days` days. Entries without an upload date are kept.
+  LastDays { days: u32 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Subscription {
+  pub id: String,
+  pub url: String,
+  #[serde(default)]
+  pub title: Option<String>,
+  pub format: FormatOptions,
+  #[serde(default)]
+  pub overrides: Option<DownloadOverrides>,
+  #[serde(default = "default_interval_mins")]
+  pub interval_mins: u64,
+  #[serde(default)]
+  pub retention: Retention,
+  #[serde(default = "default_enabled")]
+  pub enabled: bool,
+  /// Unix time in milliseconds of the last sync attempt.
+  #[serde(default)]
+  pub last_synced_at: Option<u64>,
+  #[serde(default)]
+  pub last_error: Option<String>,
+  /// URLs of retained entries that were already queued, so they are not queued again when the
+  /// download archive is not in use.
+  #[serde(default)]
+  pub seen: Vec<String>,
+  /// Set until the first successful sync, which only marks the listed entries as seen unless
+  /// `backfill` is set. Keeps a new subscription from queueing a whole back catalog.
+  #[serde(default)]
+  pub pending_first_sync: bool,
+  /// Makes the first sync queue the retained entries that were listed before subscribing.
+  #[serde(default)]
+  pub backfill: bool,
+  /// Entries this subscription queued, pruned with their files once they fall out of the
+  /// retention policy.
+  #[serde(default)]
+  pub downloads: Vec<SubscriptionDownload>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionDownload {
+  /// Id of the download item.
+  pub id: String,
+  pub url: String,
+  /// The downloaded file, once the download completed.
+  #[serde(default)]
+  pub path: Option<String>,
+}
+
+/// The fields of a subscription the user may change, see `subscription_set`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SubscriptionPatch {
+  pub title: Option<String>,
+  pub format: Option<FormatOptions>,
+  pub overrides: Option<DownloadOverrides>,
+  pub interval_mins: Option<u64>,
+  pub retention: Option<Retention>,
+  pub enabled: Option<bool>,
+}
+
+fn default_interval_mins() -> u64 {
+  60
+}
+
+fn default_enabled() -> bool {
+  true
+}
+
+impl Subscription {
+  pub fn is_due(&self, now_ms: u64) -> bool {
+    let interval_ms = self.interval_mins.max(1).saturating_mul(60_000);
+    self.enabled
+      && self
+        .last_synced_at
+        .is_none_or(|synced_at| now_ms.saturating_sub(synced_at) >= interval_ms)
+  }
+
+  /// Entries of the listing that fall within the retention policy.
+  pub fn retained<'a>(
+    &self,
+    entries: &'a [PlaylistEntry],
+    today: NaiveDate,
+  ) -> Vec<&'a PlaylistEntry> {
+    match &self.retention {
+      Retention::All => entries.iter().collect(),
+      Retention::LastItems { count } => entries.iter().take(*count).collect(),
+      Retention::LastDays { days } => {
+        let cutoff = (today - chrono::Days::new(u64::from(*days)))
+          .format("%Y%m%d")
+          .to_string();
+        entries
+          .iter()
+          .filter(|entry| {
+            entry
+              .upload_date
+              .as_ref()
+              .is_none_or(|date| date.as_str() >= cutoff.as_str())
+          })
+          .collect()
+      }
+    }
+  }
+
+  /// Retained entries that are neither archived nor queued by an earlier sync. The first sync
+  /// returns none, unless a backfill was asked for.
+  pub fn new_entries<'a>(
+    &self,
+    retained: &[&'a PlaylistEntry],
+    archived: Option<&HashSet<String>>,
+  ) -> Vec<&'a PlaylistEntry> {
+    if self.pending_first_sync && !self.backfill {
+      return Vec::new();
+    }
+    let seen: HashSet<&str> = self.seen.iter().map(String::as_str).collect();
+    retained
+      .iter()
+      .copied()
+      .filter(|entry| !seen.contains(entry.video_url.as_str()))
+      .filter(|entry| {
+        !archived.is_some_and(|archived| {
+          entry
+            .archive_id
+            .as_ref()
+            .is_some_and(|id| archived.contains(id))
+        })
+      })
+      .collect()
+  }
+
+  pub fn apply_patch(&mut self, patch: SubscriptionPatch) {
+    let SubscriptionPatch {
+      title,
+      format,
+      overrides,
+      interval_mins,
+      retention,
+      enabled,
+    } = patch;
+    if let Some(title) = title {
+      self.title = Some(title);
+    }
+    if let Some(format) = format {
+      self.format = format;
+    }
+    if let Some(overrides) = overrides {
+      self.overrides = Some(overrides);
+    }
+    if let Some(interval_mins) = interval_mins {
+      self.interval_mins = interval_mins;
+    }
+    if let Some(retention) = retention {
+      self.retention = retention;
+    }
+    if let Some(enabled) = enabled {
+      self.enabled = enabled;
+    }
+  }
+
+  /// Drops the downloads whose entries are no longer retained, returning them so their files
+  /// can be deleted. Nothing is pruned while every entry is retained.
+  pub fn prune(&mut self, retained: &[String]) -> Vec<SubscriptionDownload> {
+    if self.retention == Retention::All {
+      return Vec::new();
+    }
+    let retained: HashSet<&str> = retained.iter().map(String::as_str).collect();
+    let (kept, pruned) = std::mem::take(&mut self.downloads)
+      .into_iter()
+      .partition(|download| retained.contains(download.url.as_str()));
+    self.downloads = kept;
+    pruned
+  }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Subscriptions {
+  pub entries: IndexMap<String, Subscription>,
+}
+
+impl Subscriptions {
+  pub fn insert(&mut self, subscription: Subscription) {
+    self.entries.insert(subscription.id.clone(), subscription);
+  }
+
+  pub fn remove(&mut self, id: &str) -> Option<Subscription> {
+    self.entries.shift_remove(id)
+  }
+
+  pub fn due(&self, now_ms: u64) -> Vec<String> {
+    self
+      .entries
+      .values()
+      .filter(|subscription| subscription.is_due(now_ms))
+      .map(|subscription| subscription.id.clone())
+      .collect()
+  }
+
+  /// Records a sync attempt. Only the retained entries are remembered, which keeps the list
+  /// bounded by the retention policy. Returns the downloads that were pruned.
+  pub fn mark_synced(
+    &mut self,
+    id: &str,
+    now_ms: u64,
+    result: Result<Vec<String>, String>,
+  ) -> Vec<SubscriptionDownload> {
+    let Some(subscription) = self.entries.get_mut(id) else {
+      return Vec::new();
+    };
+    subscription.last_synced_at = Some(now_ms);
+    match result {
+      Ok(retained) => {
+        let pruned = subscription.prune(&retained);
+        subscription.seen = retained;
+        subscription.last_error = None;
+        subscription.pending_first_sync = false;
+        subscription.backfill = false;
+        pruned
+      }
+      Err(e) => {
+        subscription.last_error = Some(e);
+        Vec::new()
+      }
+    }
+  }
+
+  /// Remembers where a download queued by a subscription was saved.
+  pub fn record_download(&mut self, id: &str, path: &str) -> bool {
+    let download = self
+      .entries
+      .values_mut()
+      .flat_map(|subscription| subscription.downloads.iter_mut())
+      .find(|download| download.id == id);
+    match download {
+      Some(download) => {
+        download.path = Some(path.to_string());
+        true
+      }
+      None => false,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::TrackType;
+
+  fn subscription(retention: Retention) -> Subscription {
+    Subscription {
+      id: "s1".into(),
+      url: "https://example.com/channel".into(),
+      title: None,
+      format: FormatOptions {
+        track_type: TrackType::Both,
+        abr: None,
+        height: Some(720),
+        fps: None,
+        audio_encoding: None,
+        video_encoding: None,
+        audio_track: None,
+        video_track: None,
+      },
+      overrides: None,
+      interval_mins: 60,
+      retention,
+      enabled: true,
+      last_synced_at: None,
+      last_error: None,
+      seen: Vec::new(),
+      pending_first_sync: false,
+      backfill: false,
+      downloads: Vec::new(),
+    }
+  }
+
+  fn download(index: usize) -> SubscriptionDownload {
+    SubscriptionDownload {
+      id: format!("d{index}"),
+      url: format!("https://example.com/v{index}"),
+      path: Some(format!("/downloads/v{index}.mp4")),
+    }
+  }
+
+  fn entry(index: usize, upload_date: Option<&str>) -> PlaylistEntry {
+    PlaylistEntry {
+      video_url: format!("https://example.com/v{index}"),
+      index,
+      archive_id: Some(format!("example v{index}")),
+      title: None,
+      duration: None,
+      thumbnail: None,
+      uploader: None,
+      upload_date: upload_date.map(Into::into),
+      availability: None,
+    }
+  }
+
+  fn indexes(entries: &[&PlaylistEntry]) -> Vec<usize> {
+    entries.iter().map(|entry| entry.index).collect()
+  }
+
+  fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, 10).unwrap()
+  }
+
+  #[test]
+  fn last_items_keeps_the_head_of_the_listing() {
+    let entries: Vec<_> = (1..=5).map(|i| entry(i, None)).collect();
+    let sub = subscription(Retention::LastItems { count: 2 });
+    assert_eq!(indexes(&sub.retained(&entries, today())), vec![1, 2]);
+  }
+
+  #[test]
+  fn last_days_keeps_recent_and_undated_entries() {
+    let entries = vec![
+      entry(1, Some("20240309")),
+      entry(2, None),
+      entry(3, Some("20240303")),
+      entry(4, Some("20240301")),
+    ];
+    let sub = subscription(Retention::LastDays { days: 7 });
+    assert_eq!(indexes(&sub.retained(&entries, today())), vec![1, 2, 3]);
+  }
+
+  #[test]
+  fn new_entries_skip_seen_and_archived() {
+    let entries: Vec<_> = (1..=4).map(|i| entry(i, None)).collect();
+    let mut sub = subscription(Retention::All);
+    sub.seen = vec!["https://example.com/v1".into()];
+    let archived = HashSet::from(["example v3".to_string()]);
+
+    let retained = sub.retained(&entries, today());
+    assert_eq!(
+      indexes(&sub.new_entries(&retained, Some(&archived))),
+      vec![2, 4]
+    );
+    assert_eq!(indexes(&sub.new_entries(&retained, None)), vec![2, 3, 4]);
+  }
+
+  #[test]
+  fn first_sync_only_seeds_seen_entries() {
+    let entries: Vec<_> = (1..=3).map(|i| entry(i, None)).collect();
+    let mut subs = Subscriptions::default();
+    let mut sub = subscription(Retention::All);
+    sub.pending_first_sync = true;
+    subs.insert(sub);
+
+    let retained = subs.entries["s1"].retained(&entries, today());
+    assert!(subs.entries["s1"].new_entries(&retained, None).is_empty());
+    let urls = retained.iter().map(|e| e.video_url.clone()).collect();
+    subs.mark_synced("s1", 1, Ok(urls));
+
+    let more: Vec<_> = (1..=4).map(|i| entry(i, None)).collect();
+    let retained = subs.entries["s1"].retained(&more, today());
+    assert_eq!(
+      indexes(&subs.entries["s1"].new_entries(&retained, None)),
+      vec![4]
+    );
+  }
+
+  #[test]
+  fn backfill_queues_the_back_catalog() {
+    let entries: Vec<_> = (1..=3).map(|i| entry(i, None)).collect();
+    let mut sub = subscription(Retention::All);
+    sub.pending_first_sync = true;
+    sub.backfill = true;
+
+    let retained = sub.retained(&entries, today());
+    assert_eq!(indexes(&sub.new_entries(&retained, None)), vec![1, 2, 3]);
+  }
+
+  #[test]
+  fn sync_prunes_downloads_outside_retention() {
+    let mut subs = Subscriptions::default();
+    let mut sub = subscription(Retention::LastItems { count: 2 });
+    sub.downloads = (1..=3).map(download).collect();
+    subs.insert(sub);
+
+    let retained = vec![
+      "https://example.com/v2".to_string(),
+      "https://example.com/v3".to_string(),
+    ];
+    let pruned = subs.mark_synced("s1", 1, Ok(retained));
+    assert_eq!(pruned, vec![download(1)]);
+    assert_eq!(subs.entries["s1"].downloads, vec![download(2), download(3)]);
+
+    subs.entries["s1"].retention = Retention::All;
+    assert!(subs.mark_synced("s1", 2, Ok(Vec::new())).is_empty());
+  }
+
+  #[test]
+  fn patch_changes_only_the_given_fields() {
+    let mut sub = subscription(Retention::All);
+    sub.seen = vec!["https://example.com/v1".into()];
+    let patch: SubscriptionPatch =
+      serde_json::from_str(r#"{"intervalMins": 15, "enabled": false, "id": "other", "seen": []}"#)
+        .unwrap();
+    sub.apply_patch(patch);
+
+    assert_eq!(sub.id, "s1");
+    assert_eq!(sub.interval_mins, 15);
+    assert!(!sub.enabled);
+    assert_eq!(sub.seen, vec!["https://example.com/v1"]);
+    assert_eq!(sub.retention, Retention::All);
+  }
+
+  #[test]
+  fn due_respects_interval_and_enabled() {
+    let mut subs = Subscriptions::default();
+    subs.insert(subscription(Retention::All));
+    assert_eq!(subs.due(0), vec!["s1"]);
+
+    subs.mark_synced("s1", 1_000, Ok(Vec::new()));
+    assert!(subs.due(1_000 + 59 * 60_000).is_empty());
+    assert_eq!(subs.due(1_000 + 60 * 60_000), vec!["s1"]);
+
+    subs.entries["s1"].enabled = false;
+    assert!(subs.due(u64::MAX).is_empty());
+  }
+
+  #[test]
+  fn failed_sync_keeps_seen_entries() {
+    let mut subs = Subscriptions::default();
+    subs.insert(subscription(Retention::All));
+    subs.mark_synced("s1", 1, Ok(vec!["https://example.com/v1".into()]));
+    subs.mark_synced("s1", 2, Err("offline".into()));
+
+    let sub = &subs.entries["s1"];
+    assert_eq!(sub.seen, vec!["https://example.com/v1"]);
+    assert_eq!(sub.last_error.as_deref(), Some("offline"));
+    assert_eq!(sub.last_synced_at, Some(2));
+  }
+}
@@ -10,6 +10,7 @@ use tauri_plugin_stronghold::stronghold::Stronghold;
 pub const CLIENT: &[u8] = b"ovd";
 const KR_SERVICE: &str = "com.jelleglebbeek.youtube-dl-gui";
 const KR_ACCOUNT: &str = "master_key";
+const API_TOKEN_KEY: &str = "api.token";
 
 #[derive(Debug, Default, Clone)]
 pub struct AuthSecrets {
@@ -85,6 +86,50 @@ impl StrongholdState {
       headers,
     })
   }
+
+  /// Token clients of the local HTTP API authenticate with.
+  pub fn load_api_token(&self) -> Result<Option<String>, String> {
+    let guard = self
+      .inner
+      .lock()
+      .map_err(|_| "failed to lock stronghold".to_string())?;
+    let sh = guard.as_ref().ok_or_else(|| "vault locked".to_string())?;
+    let client = sh
+      .get_client(CLIENT)
+      .map_err(|e| format!("get_client failed: {e}"))?;
+    match client
+      .store()
+      .get(API_TOKEN_KEY.as_bytes())
+      .map_err(|e| e.to_string())?
+    {
+      Some(bytes) if !bytes.is_empty() => String::from_utf8(bytes)
+        .map(Some)
+        .map_err(|e| e.to_string()),
+      _ => Ok(None),
+    }
+  }
+
+  pub fn store_api_token(&self, token: &str) -> Result<(), String> {
+    let guard = self
+      .inner
+      .lock()
+      .map_err(|_| "failed to lock stronghold".to_string())?;
+    let sh = guard.as_ref().ok_or_else(|| "vault locked".to_string())?;
+    let client = sh
+      .get_client(CLIENT)
+      .map_err(|e| format!("get_client failed: {e}"))?;
+    client
+      .store()
+      .insert(
+        API_TOKEN_KEY.as_bytes().to_vec(),
+        token.as_bytes().to_vec(),
+        None,
+      )
+      .map_err(|e| e.to_string())?;
+    sh.write_client(CLIENT)
+      .map_err(|e| format!("write_client failed: {e}"))?;
+    sh.save().map_err(|e| e.to_string())
+  }
 }
 
 fn generate_master_key() -> [u8; 32] {
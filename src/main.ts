@@ -97,4 +97,10 @@ async function initStores(): Promise<void> {
   } catch (e) {
     console.error(`Unable to load settings: ${e}`);
   }
+
+  try {
+    await useMediaStore().loadRestoredDownloads();
+  } catch (e) {
+    console.error(`Unable to load restored downloads: ${e}`);
+  }
 }
@@ -6,6 +6,8 @@ import { registerUpdaterListeners } from '../tauri/listeners/updater';
 import { registerDiagnosticsListeners } from '../tauri/listeners/diagnostics.ts';
 import { registerAppListeners } from '../tauri/listeners/app.ts';
 import { registerShortcutListeners } from '../tauri/listeners/shortcuts.ts';
+import { registerCliListeners } from '../tauri/listeners/cli.ts';
+import { registerClipboardListeners } from '../tauri/listeners/clipboard.ts';
 
 export default {
   install() {
@@ -17,5 +19,7 @@ export default {
     registerUpdaterListeners();
     registerDiagnosticsListeners();
     registerShortcutListeners();
+    registerCliListeners();
+    registerClipboardListeners();
   },
 };
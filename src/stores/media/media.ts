@@ -6,11 +6,20 @@ import { useMediaGroupStore } from './group';
 import { useMediaDestinationStore } from './destination';
 import { useMediaProgressStore } from './progress';
 import { useMediaOptionsStore } from './options';
-import { DownloadOptions, DownloadOverrides, MediaAddPayload, MediaItem, TrackType } from '../../tauri/types/media';
+import {
+  DownloadOptions,
+  DownloadOverrides,
+  MediaAddPayload,
+  MediaGroupAddPayload,
+  MediaItem,
+  PlaylistEntriesPayload,
+  TrackType,
+} from '../../tauri/types/media';
 import { useMediaSizeStore } from './size.ts';
 import { useMediaDiagnosticsStore } from './diagnostics.ts';
 import { useSettingsStore } from '../settings.ts';
 import { Group } from '../../tauri/types/group.ts';
+import { DownloadGroupPayload, QueuedDownload } from '../../tauri/types/queue.ts';
 import { notify, notifyGroup } from '../../tauri/notifications';
 import { NotificationKind } from '../../tauri/types/app';
 import { resolvePlaylistIndex } from '../../helpers/playlistNumbering';
@@ -148,6 +157,37 @@ export const useMediaStore = defineStore('media', () => {
     stateStore.setState(item.id, next);
   }
 
+  function processPlaylistEntriesPayload(payload: PlaylistEntriesPayload) {
+    const group = groupStore.findGroupById(payload.groupId);
+    const leader = group?.items[payload.id];
+    if (!group || !leader) return;
+
+    const entries = [...(group.entries ?? []), ...payload.entries];
+    group.entries = entries;
+    leader.entries = [...entries];
+    leader.isLeader = true;
+    group.total = entries.length;
+    group.processed = 0;
+
+    if (payload.error) {
+      diagnosticsStore.processMediaDiagnosticPayload({
+        id: payload.id,
+        groupId: payload.groupId,
+        level: 'warning',
+        code: 'playlistListingIncomplete',
+        component: null,
+        message: payload.error,
+        raw: payload.error,
+        timestamp: Date.now(),
+      });
+    }
+    if (!payload.done) {
+      stateStore.setState(payload.id, MediaState.fetchingList);
+    } else if (entries.length > 0) {
+      stateStore.setState(payload.id, MediaState.playlistSelection);
+    }
+  }
+
   async function expandPlaylistGroup(groupId: string, selection: PlaylistSelection) {
     const group = groupStore.findGroupById(groupId);
     const leader = groupStore.findGroupLeader(groupId);
@@ -195,14 +235,13 @@ export const useMediaStore = defineStore('media', () => {
     });
   }
 
-  async function dispatchMediaInfoFetch(
+  function createFetchingGroup(
     url: string,
+    id: string,
+    groupId: string,
     fromShortcut: boolean = false,
     skipPlaylistSelection: boolean = false,
-  ) {
-    const id = uuidv4();
-    const groupId = uuidv4();
-
+  ): Group {
     stateStore.setState(id, MediaState.fetching);
     const newGroup: Group = {
       id: groupId,
@@ -234,6 +273,22 @@ export const useMediaStore = defineStore('media', () => {
       },
     };
     groupStore.createGroup(newGroup);
+    return newGroup;
+  }
+
+  function addFetchingGroup(payload: MediaGroupAddPayload) {
+    if (groupStore.findGroupById(payload.groupId)) return;
+    createFetchingGroup(payload.url, payload.id, payload.groupId);
+  }
+
+  async function dispatchMediaInfoFetch(
+    url: string,
+    fromShortcut: boolean = false,
+    skipPlaylistSelection: boolean = false,
+  ) {
+    const id = uuidv4();
+    const groupId = uuidv4();
+    const newGroup = createFetchingGroup(url, id, groupId, fromShortcut, skipPlaylistSelection);
 
     const inputFiltersOverride = settingsToInputFilterOverride(settingsStore.settings);
     if (inputFiltersOverride) {
@@ -354,6 +409,7 @@ export const useMediaStore = defineStore('media', () => {
           templateContext: {
             values: buildTemplateContext(item, group, overrides),
           },
+          live: item.isLive ? {} : undefined,
         })),
       });
     } catch (e) {
@@ -387,6 +443,15 @@ export const useMediaStore = defineStore('media', () => {
     groupStore.cancelGroup(groupId);
   }
 
+  async function stopGroup(groupId: string) {
+    const group = groupStore.findGroupById(groupId);
+    if (!group) return;
+
+    const recordings = Object.values(group.items)
+      .filter(item => !item.entries && stateStore.getState(item.id) === MediaState.downloading);
+    await Promise.all(recordings.map(item => invoke('media_stop', { groupId, id: item.id })));
+  }
+
   function buildTemplateContext(
     item: MediaItem,
     group: Group,
@@ -441,6 +506,74 @@ export const useMediaStore = defineStore('media', () => {
     }
   }
 
+  function addDownloadingGroup(payload: DownloadGroupPayload) {
+    const { groupId, items } = payload;
+    if (items.length === 0 || groupStore.findGroupById(groupId)) return;
+
+    const toItem = (id: string, url: string, title: string | undefined, isLeader: boolean): MediaItem => ({
+      id,
+      url,
+      title,
+      isLeader,
+      groupId,
+      audioCodecs: [],
+      videoCodecs: [],
+      audioTracks: [],
+      videoTracks: [],
+      formats: [],
+      filesize: 0,
+    });
+
+    const isCombined = items.length > 1;
+    const groupItems: Record<string, MediaItem> = {};
+    if (isCombined) {
+      groupItems[groupId] = toItem(groupId, items[0].url, payload.title, true);
+    }
+    items.forEach((item, index) => {
+      groupItems[item.id] = toItem(item.id, item.url, item.title, !isCombined && index === 0);
+    });
+
+    groupStore.createGroup({
+      id: groupId,
+      total: items.length,
+      processed: items.length,
+      errored: 0,
+      isCombined,
+      url: items[0].url,
+      title: payload.title ?? items[0].title,
+      audioCodecs: [],
+      videoCodecs: [],
+      audioTracks: [],
+      videoTracks: [],
+      formats: [],
+      filesize: 0,
+      items: groupItems,
+    });
+
+    const state = isCombined ? MediaState.downloadingList : MediaState.downloading;
+    for (const id of Object.keys(groupItems)) {
+      stateStore.setState(id, state);
+    }
+  }
+
+  async function loadRestoredDownloads() {
+    const restored = await invoke<QueuedDownload[] | null>('queue_restored_list') ?? [];
+    const groups = new Map<string, DownloadGroupPayload>();
+    for (const entry of restored) {
+      const values = entry.templateContext.values;
+      const group = groups.get(entry.groupId) ?? {
+        groupId: entry.groupId,
+        title: values.playlist_title,
+        items: [],
+      };
+      group.items.push({ id: entry.id, url: entry.url, title: values.title });
+      groups.set(entry.groupId, group);
+    }
+    for (const group of groups.values()) {
+      addDownloadingGroup(group);
+    }
+  }
+
   function deleteGroup(id: string) {
     const group = groupStore.findGroupById(id);
     for (const itemId of Object.keys(group.items)) {
@@ -473,8 +606,10 @@ export const useMediaStore = defineStore('media', () => {
 
   return {
     processMediaAddPayload,
+    processPlaylistEntriesPayload,
     finalizePlaylistGroup,
     dispatchMediaInfoFetch,
+    addFetchingGroup,
     expandPlaylistGroup,
     rejectPendingReadyGroup,
     addAndDownload,
@@ -484,7 +619,10 @@ export const useMediaStore = defineStore('media', () => {
     downloadAllGroups,
     pauseAllGroups,
     pauseGroup,
+    stopGroup,
     resumeAllGroups,
+    addDownloadingGroup,
+    loadRestoredDownloads,
     deleteGroup,
     deleteAllGroups,
     deleteGroupsByState,
@@ -5,6 +5,7 @@ import { MediaState, useMediaStateStore } from './state';
 import {
   MediaGroupProgressPayload,
   MediaItemsProgress,
+  MediaLiveProgressPayload,
   MediaProgressCompletePayload,
   MediaProgressPayload,
   MediaProgressStagePayload,
@@ -19,6 +20,8 @@ export type MediaGroupProgress = MediaGroupProgressPayload & MediaProgressStageP
 
 export const useMediaProgressStore = defineStore('media-progress', () => {
   const progress = ref<Record<string, MediaProgress>>({});
+  const alreadyDownloaded = ref<Record<string, true>>({});
+  const liveProgress = ref<Record<string, MediaLiveProgressPayload>>({});
   const groupStore = useMediaGroupStore();
   const stateStore = useMediaStateStore();
   const recentGroupSpeedBps = new Map<string, { speedBps: number; expiresAt: number }>();
@@ -76,6 +79,27 @@ export const useMediaProgressStore = defineStore('media-progress', () => {
     }
   }
 
+  function processMediaAlreadyDownloadedPayload(payload: MediaProgressCompletePayload) {
+    alreadyDownloaded.value[payload.id] = true;
+    processMediaCompletePayload(payload);
+  }
+
+  function isAlreadyDownloaded(id: string): boolean {
+    return alreadyDownloaded.value[id] === true;
+  }
+
+  function processMediaLiveProgressPayload(payload: MediaLiveProgressPayload) {
+    liveProgress.value[payload.id] = payload;
+  }
+
+  function findLiveProgress(groupId: string): MediaLiveProgressPayload | undefined {
+    const group = groupStore.findGroupById(groupId);
+    if (!group) return;
+    const firstId = Object.keys(group.items)[0];
+    if (!firstId) return;
+    return liveProgress.value[firstId];
+  }
+
   function findGroupProgress(groupId: string): MediaGroupProgress | undefined {
     const group = groupStore.findGroupById(groupId);
     if (!group) return;
@@ -176,6 +200,8 @@ export const useMediaProgressStore = defineStore('media-progress', () => {
 
   function deleteProgress(id: string) {
     delete progress.value[id];
+    delete alreadyDownloaded.value[id];
+    delete liveProgress.value[id];
   }
 
   return {
@@ -183,6 +209,10 @@ export const useMediaProgressStore = defineStore('media-progress', () => {
     processMediaProgressPayload,
     processMediaProgressStagePayload,
     processMediaCompletePayload,
+    processMediaAlreadyDownloadedPayload,
+    isAlreadyDownloaded,
+    processMediaLiveProgressPayload,
+    findLiveProgress,
     findGroupProgress,
     findDownloadProgress,
     findAllProgress,
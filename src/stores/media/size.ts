@@ -9,6 +9,7 @@ import {
   TrackType,
 } from '../../tauri/types/media';
 import { useMediaGroupStore } from './group.ts';
+import { useMediaOptionsStore } from './options';
 
 export type Size = DownloadOptions & { size: number | null };
 
@@ -130,6 +131,7 @@ export const useMediaSizeStore = defineStore('media-size', () => {
       id,
       groupId,
       format,
+      overrides: useMediaOptionsStore().getOverrides(groupId) ?? null,
     });
   }
 
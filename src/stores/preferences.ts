@@ -1,7 +1,7 @@
 import { defineStore } from 'pinia';
 import { ref } from 'vue';
 import { invoke } from '@tauri-apps/api/core';
-import { defaultPreferences, Preferences } from '../tauri/types/preferences.ts';
+import { defaultPreferences, DownloadProfile, Preferences } from '../tauri/types/preferences.ts';
 import { TrackType } from '../tauri/types/media';
 
 type DeepPartial<T> = T extends object ? {
@@ -43,6 +43,16 @@ export const usePreferencesStore = defineStore('preferences', () => {
     await patch({ recents: { recent: preferences.value.recents.recent } });
   }
 
+  async function saveProfile(name: string, profile: DownloadProfile): Promise<void> {
+    const newPrefs = await invoke<Preferences>('profile_set', { name, profile });
+    applyPreferences(newPrefs);
+  }
+
+  async function removeProfile(name: string): Promise<void> {
+    const newPrefs = await invoke<Preferences>('profile_remove', { name });
+    applyPreferences(newPrefs);
+  }
+
   function setPathExample(trackType: TrackType, example: string): void {
     pathExamples.value[trackType] = example;
   }
@@ -75,6 +85,8 @@ export const usePreferencesStore = defineStore('preferences', () => {
     getRecentPaths,
     addRecentPath,
     clearRecentPaths,
+    saveProfile,
+    removeProfile,
     setPathExample,
     setFilenameExample,
     getPathExample,
@@ -0,0 +1,16 @@
+import { listen } from '@tauri-apps/api/event';
+import { CliAddPayload } from '../types/app.ts';
+import { useMediaStore } from '../../stores/media/media.ts';
+import { isValidUrl } from '../../helpers/url.ts';
+
+export function registerCliListeners() {
+  const mediaStore = useMediaStore();
+
+  void listen<CliAddPayload>('cli_add', async (event) => {
+    const urls = event.payload.urls.filter(url => isValidUrl(url));
+    if (urls.length === 0) {
+      return;
+    }
+    await mediaStore.addUrlBatch(urls);
+  });
+}
@@ -0,0 +1,15 @@
+import { listen } from '@tauri-apps/api/event';
+import { ClipboardUrlPayload } from '../types/app.ts';
+import { useMediaStore } from '../../stores/media/media.ts';
+import { isValidUrl } from '../../helpers/url.ts';
+
+export function registerClipboardListeners() {
+  const mediaStore = useMediaStore();
+
+  void listen<ClipboardUrlPayload>('clipboard_url', async (event) => {
+    if (!isValidUrl(event.payload.url)) {
+      return;
+    }
+    await mediaStore.addUrlBatch([event.payload.url]);
+  });
+}
@@ -1,12 +1,26 @@
 import { listen } from '@tauri-apps/api/event';
 import { useMediaStore } from '../../stores/media/media';
 import { useMediaSizeStore } from '../../stores/media/size';
-import { MediaAddPayload, MediaAddWithFormatPayload } from '../types/media';
+import {
+  MediaAddPayload,
+  MediaAddWithFormatPayload,
+  MediaGroupAddPayload,
+  PlaylistEntriesPayload,
+} from '../types/media';
+import { DownloadGroupPayload } from '../types/queue';
 
 export function registerMediaListeners() {
   const mediaStore = useMediaStore();
   const sizeStore = useMediaSizeStore();
 
+  void listen<MediaGroupAddPayload>('media_group_add', (event) => {
+    mediaStore.addFetchingGroup(event.payload);
+  });
+
+  void listen<DownloadGroupPayload>('download_group', (event) => {
+    mediaStore.addDownloadingGroup(event.payload);
+  });
+
   void listen<MediaAddPayload>('media_add', (event) => {
     mediaStore.processMediaAddPayload(event.payload);
     sizeStore.processMediaAddPayload(event.payload);
@@ -15,4 +29,8 @@ export function registerMediaListeners() {
   void listen<MediaAddWithFormatPayload>('media_size', (event) => {
     sizeStore.processMediaSizePayload(event.payload);
   });
+
+  void listen<PlaylistEntriesPayload>('playlist_entries', (event) => {
+    mediaStore.processPlaylistEntriesPayload(event.payload);
+  });
 }
@@ -1,6 +1,11 @@
 import { listen } from '@tauri-apps/api/event';
 import { useMediaProgressStore } from '../../stores/media/progress';
-import { MediaProgressCompletePayload, MediaProgressPayload, MediaProgressStagePayload } from '../types/progress';
+import {
+  MediaLiveProgressPayload,
+  MediaProgressCompletePayload,
+  MediaProgressPayload,
+  MediaProgressStagePayload,
+} from '../types/progress';
 
 export function registerProgressListeners() {
   const progressStore = useMediaProgressStore();
@@ -16,4 +21,12 @@ export function registerProgressListeners() {
   void listen<MediaProgressCompletePayload>('media_complete', (event) => {
     progressStore.processMediaCompletePayload(event.payload);
   });
+
+  void listen<MediaProgressCompletePayload>('media_already_downloaded', (event) => {
+    progressStore.processMediaAlreadyDownloadedPayload(event.payload);
+  });
+
+  void listen<MediaLiveProgressPayload>('media_live_progress', (event) => {
+    progressStore.processMediaLiveProgressPayload(event.payload);
+  });
 }
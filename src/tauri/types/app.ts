@@ -18,4 +18,13 @@ export enum NotificationKind {
   DownloadFailed = 'downloadFailed',
   VideoReady = 'videoReady',
   PlaylistReady = 'playlistReady',
+  QueueStatus = 'queueStatus',
+}
+
+export interface CliAddPayload {
+  urls: string[];
+}
+
+export interface ClipboardUrlPayload {
+  url: string;
 }
@@ -51,6 +51,9 @@ export interface InputSettings {
   autoFillClipboard: boolean;
   preferVideoInMixedLinks: boolean;
   globalShortcuts: boolean;
+  useDownloadArchive: boolean;
+  waitForScheduled: boolean;
+  deepLinkDownloads: boolean;
 }
 
 export type InputFilterSizeUnit = 'B' | 'KB' | 'MB' | 'GB' | 'TB';
@@ -74,6 +77,32 @@ export interface InputFilterSettings {
   breakMatchFilters: string | null;
 }
 
+export interface MetadataCacheSettings {
+  enabled: boolean;
+  ttlSecs: number;
+  maxSizeMb: number;
+}
+
+export interface ApiSettings {
+  enabled: boolean;
+  port: number;
+  allowedOrigins: string[];
+}
+
+export enum ClipboardWatchAction {
+  emit = 'emit',
+  queue = 'queue',
+}
+
+export interface ClipboardWatchSettings {
+  enabled: boolean;
+  action: ClipboardWatchAction;
+  profile: string | null;
+  matchExtractors: boolean;
+  patterns: string[];
+  pollIntervalMs: number;
+}
+
 export enum FormatPreset {
   TitleQuality = 'titleQuality',
   TitleOnly = 'titleOnly',
@@ -107,10 +136,53 @@ export interface OutputSettings {
 
 export interface PerformanceSettings {
   maxConcurrency: number;
+  maxFetchConcurrency: number;
+  maxConcurrencyPerHost: number | null;
+  bandwidthLimitKib: number | null;
+  alternateBandwidthLimitKib: number | null;
+  alternateBandwidthWindow: DownloadWindow | null;
   splitPlaylistThreshold: number;
   autoLoadSize: boolean;
 }
 
+export interface DownloadWindow {
+  start: string;
+  end: string;
+}
+
+export interface ScheduleSettings {
+  windowsEnabled: boolean;
+  windows: DownloadWindow[];
+}
+
+export type HookFailurePolicy = 'ignore' | 'failDownload';
+
+export interface PostDownloadHookSettings {
+  enabled: boolean;
+  command: string | null;
+  args: string[];
+  runOnFailure: boolean;
+  timeoutSecs: number;
+  failurePolicy: HookFailurePolicy;
+  profiles: Record<string, HookProfile>;
+}
+
+export interface HookProfile {
+  command: string;
+  args: string[];
+  runOnFailure: boolean;
+  timeoutSecs: number;
+  failurePolicy: HookFailurePolicy;
+}
+
+export interface RetrySettings {
+  enabled: boolean;
+  maxRetries: number;
+  baseDelayMs: number;
+  maxDelayMs: number;
+  retryableCodes: string[];
+}
+
 export interface SubtitleSettings {
   enabled: boolean;
   includeAutoGenerated: boolean;
@@ -159,13 +231,19 @@ export interface Settings {
   network: NetworkSettings;
   input: InputSettings;
   inputFilters: InputFilterSettings;
+  metadataCache: MetadataCacheSettings;
   output: OutputSettings;
   performance: PerformanceSettings;
+  postDownloadHook: PostDownloadHookSettings;
+  retry: RetrySettings;
+  schedule: ScheduleSettings;
   sponsorBlock: SponsorBlockSettings;
   subtitles: SubtitleSettings;
   update: UpdateSettings;
   system: SystemSettings;
   notifications: NotificationSettings;
+  api: ApiSettings;
+  clipboardWatch: ClipboardWatchSettings;
 }
 
 export const defaultAppearanceSettings: AppearanceSettings = {
@@ -190,6 +268,9 @@ export const defaultInputSettings: InputSettings = {
   autoFillClipboard: true,
   preferVideoInMixedLinks: false,
   globalShortcuts: true,
+  useDownloadArchive: false,
+  waitForScheduled: false,
+  deepLinkDownloads: false,
 };
 
 export const defaultInputFilterSizeFilter: InputFilterSizeFilter = {
@@ -216,6 +297,27 @@ export const defaultInputFilterSettings: InputFilterSettings = {
   breakMatchFilters: null,
 };
 
+export const defaultMetadataCacheSettings: MetadataCacheSettings = {
+  enabled: true,
+  ttlSecs: 6 * 60 * 60,
+  maxSizeMb: 256,
+};
+
+export const defaultApiSettings: ApiSettings = {
+  enabled: false,
+  port: 47819,
+  allowedOrigins: [],
+};
+
+export const defaultClipboardWatchSettings: ClipboardWatchSettings = {
+  enabled: false,
+  action: ClipboardWatchAction.emit,
+  profile: null,
+  matchExtractors: true,
+  patterns: [],
+  pollIntervalMs: 1000,
+};
+
 export const defaultOutputSettings: OutputSettings = {
   video: {
     policy: TranscodePolicy.allowReencode,
@@ -243,10 +345,38 @@ export const defaultOutputSettings: OutputSettings = {
 
 export const defaultPerformanceSettings: PerformanceSettings = {
   maxConcurrency: 1,
+  maxFetchConcurrency: 1,
+  maxConcurrencyPerHost: null,
+  bandwidthLimitKib: null,
+  alternateBandwidthLimitKib: null,
+  alternateBandwidthWindow: null,
   splitPlaylistThreshold: 50,
   autoLoadSize: true,
 };
 
+export const defaultScheduleSettings: ScheduleSettings = {
+  windowsEnabled: false,
+  windows: [],
+};
+
+export const defaultPostDownloadHookSettings: PostDownloadHookSettings = {
+  enabled: false,
+  command: null,
+  args: [],
+  runOnFailure: false,
+  timeoutSecs: 300,
+  failurePolicy: 'ignore',
+  profiles: {},
+};
+
+export const defaultRetrySettings: RetrySettings = {
+  enabled: true,
+  maxRetries: 3,
+  baseDelayMs: 2000,
+  maxDelayMs: 60000,
+  retryableCodes: ['rateLimited429', 'server5xx', 'networkNameResolutionFailed', 'unableToConnectToProxy'],
+};
+
 export const defaultSubtitleSettings: SubtitleSettings = {
   enabled: false,
   includeAutoGenerated: false,
@@ -289,12 +419,26 @@ export const defaultSettings: Settings = {
     maxSize: { ...defaultInputFilterSettings.maxSize },
     dateFilter: { ...defaultInputFilterSettings.dateFilter },
   },
+  metadataCache: defaultMetadataCacheSettings,
   output: {
     ...defaultOutputSettings,
     video: { ...defaultOutputSettings.video },
     audio: { ...defaultOutputSettings.audio },
   },
   performance: defaultPerformanceSettings,
+  postDownloadHook: {
+    ...defaultPostDownloadHookSettings,
+    args: [],
+    profiles: {},
+  },
+  retry: {
+    ...defaultRetrySettings,
+    retryableCodes: [...defaultRetrySettings.retryableCodes],
+  },
+  schedule: {
+    ...defaultScheduleSettings,
+    windows: [],
+  },
   sponsorBlock: defaultSponsorBlockSettings,
   subtitles: {
     ...defaultSubtitleSettings,
@@ -304,4 +448,9 @@ export const defaultSettings: Settings = {
   update: defaultUpdateSettings,
   system: defaultSystemSettings,
   notifications: defaultNotificationSettings,
+  api: defaultApiSettings,
+  clipboardWatch: {
+    ...defaultClipboardWatchSettings,
+    patterns: [],
+  },
 };
@@ -71,6 +71,12 @@ export type DownloadOverrides = {
   sponsorBlock?: SponsorBlockOverrides;
   input?: InputOverrides;
   inputFilters?: InputFilterOptions;
+  postDownloadHook?: PostDownloadHookOverrides;
+};
+
+export type PostDownloadHookOverrides = {
+  enabled?: boolean;
+  profile?: string;
 };
 
 export type OutputOverrides = {
@@ -129,6 +135,7 @@ export type NetworkOverrides = {
   proxy?: string;
   impersonate?: string;
   extractorArgs?: string;
+  limitRate?: string;
 };
 
 export type SubtitleOverrides = {
@@ -149,6 +156,8 @@ export type SponsorBlockOverrides = {
 
 export type InputOverrides = {
   preferVideoInMixedLinks?: boolean;
+  useDownloadArchive?: boolean;
+  waitForScheduled?: boolean;
 };
 
 export enum PlaylistMode {
@@ -189,6 +198,21 @@ export interface MediaAddPayload {
   item: MediaItem;
 }
 
+export interface MediaGroupAddPayload {
+  groupId: string;
+  id: string;
+  url: string;
+}
+
+export interface PlaylistEntriesPayload {
+  groupId: string;
+  id: string;
+  entries: EntryItem[];
+  received: number;
+  done: boolean;
+  error: string | null;
+}
+
 export type MediaAddWithFormatPayload = MediaAddPayload & {
   format: DownloadOptions;
 };
@@ -223,11 +247,24 @@ export interface MediaItem {
   playlistId?: string;
   playlistCount?: number;
   playlistIndex?: number;
+  scheduledStart?: number;
+  isLive?: boolean;
 }
 
+export type LiveRecording = {
+  fromStart?: boolean;
+  maxDurationSecs?: number;
+};
+
 export interface EntryItem {
   index: number;
   videoUrl: string;
+  title?: string;
+  duration?: number;
+  thumbnail?: string;
+  uploader?: string;
+  uploadDate?: string;
+  availability?: string;
 }
 
 export interface MediaFormat {
@@ -239,6 +276,23 @@ export interface MediaFormat {
   videoCodecs: MediaCodec[];
   audioTrackIds?: string[];
   videoTrackIds?: string[];
+  streams?: FormatStream[];
+}
+
+export interface FormatStream {
+  formatId: string;
+  height?: number;
+  fps?: number;
+  abr?: number;
+  tbr?: number;
+  language?: string;
+  audioChannels?: number;
+  ext?: string;
+  vcodec?: string;
+  acodec?: string;
+  hasVideo: boolean;
+  hasAudio: boolean;
+  filesize?: number;
 }
 
 export interface MediaTrack {
@@ -1,4 +1,4 @@
-import { TrackType } from './media.ts';
+import { DownloadOptions, DownloadOverrides, TrackType } from './media.ts';
 
 export interface PathPreferences {
   audioDownloadDir: string | null;
@@ -30,14 +30,21 @@ export const defaultFormatPreferences: FormatPreferences = {
   trackType: TrackType.both,
 };
 
+export interface DownloadProfile {
+  format: DownloadOptions;
+  overrides?: DownloadOverrides;
+}
+
 export interface Preferences {
   paths: PathPreferences;
   recents: RecentPreferences;
   formats: FormatPreferences;
+  profiles: Record<string, DownloadProfile>;
 }
 
 export const defaultPreferences: Preferences = {
   paths: defaultPathPreferences,
   recents: defaultRecentPreferences,
   formats: defaultFormatPreferences,
+  profiles: {},
 };
@@ -23,6 +23,14 @@ export interface MediaProgressPayload {
   etaSecs?: number;
 }
 
+export interface MediaLiveProgressPayload {
+  id: string;
+  groupId: string;
+  elapsedSecs: number;
+  downloadedBytes?: number;
+  speedBps?: number;
+}
+
 export interface MediaGroupProgressPayload extends MediaItemsProgress {
   id: string;
   groupId: string;
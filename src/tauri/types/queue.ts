@@ -0,0 +1,28 @@
+import { DownloadOptions, DownloadOverrides, LiveRecording, SubtitleInventory } from './media';
+
+export type QueuedDownload = {
+  groupId: string;
+  id: string;
+  url: string;
+  format: DownloadOptions;
+  subtitleInventory?: SubtitleInventory;
+  overrides?: DownloadOverrides;
+  templateContext: { values: Record<string, string> };
+  priority: string;
+  queuedAt: number;
+  restored: boolean;
+  directPlaylist: boolean;
+  live?: LiveRecording;
+};
+
+export type DownloadGroupItem = {
+  id: string;
+  url: string;
+  title?: string;
+};
+
+export type DownloadGroupPayload = {
+  groupId: string;
+  title?: string;
+  items: DownloadGroupItem[];
+};
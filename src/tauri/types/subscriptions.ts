@@ -0,0 +1,46 @@
+import { DownloadOptions, DownloadOverrides } from './media';
+
+export type Retention =
+  | { kind: 'all' }
+  | { kind: 'lastItems'; count: number }
+  | { kind: 'lastDays'; days: number };
+
+export type Subscription = {
+  id: string;
+  url: string;
+  title?: string;
+  format: DownloadOptions;
+  overrides?: DownloadOverrides;
+  intervalMins: number;
+  retention: Retention;
+  enabled: boolean;
+  lastSyncedAt?: number;
+  lastError?: string;
+  seen: string[];
+  pendingFirstSync: boolean;
+  backfill: boolean;
+  downloads: SubscriptionDownload[];
+};
+
+export type SubscriptionDownload = {
+  id: string;
+  url: string;
+  path?: string;
+};
+
+export type SubscriptionPatch = Partial<
+  Pick<Subscription, 'title' | 'format' | 'overrides' | 'intervalMins' | 'retention' | 'enabled'>
+>;
+
+export type SubscriptionSyncItem = {
+  id: string;
+  url: string;
+  title?: string;
+};
+
+export type SubscriptionSyncPayload = {
+  subscriptionId: string;
+  groupId?: string;
+  queued: SubscriptionSyncItem[];
+  error?: string;
+};
@@ -2,6 +2,7 @@ import { ensureInvokeArgsObject, IPCHandler } from '../tauriMock';
 import { InvokeArgs } from '@tauri-apps/api/core';
 import { emit } from '@tauri-apps/api/event';
 import { EntryItem, MediaAddPayload, MediaFormat } from '../../../src/tauri/types/media';
+import { QueuedDownload } from '../../../src/tauri/types/queue';
 
 const sampleFormats: MediaFormat[] = [
   { id: 'f1080', height: 1080, fps: 60, audioCodecs: [], videoCodecs: [] },
@@ -37,6 +38,7 @@ export const mediaHandlers: Record<string, IPCHandler> = {
     void emitExpandedPlaylist(groupId, entries);
     return groupId;
   },
+  queue_restored_list: (): QueuedDownload[] => [],
 };
 
 const emitPlaylistLeader = async (id: string, url: string, groupId: string, total: number): Promise<string> => {